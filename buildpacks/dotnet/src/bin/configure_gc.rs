@@ -0,0 +1,383 @@
+//! An exec.d program that sets .NET garbage collector defaults suited to the dyno's memory limit and
+//! CPU quota, which are read from the cgroup filesystem when the app starts.
+
+// Required due to: https://github.com/rust-lang/rust/issues/95513
+#![allow(unused_crate_dependencies)]
+
+use libcnb::data::exec_d::ExecDProgramOutputKey;
+use libcnb::exec_d::write_exec_d_program_output;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::thread;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const MIB: u64 = 1024 * 1024;
+const GIB: u64 = 1024 * MIB;
+/// cgroup v1 reports an unlimited memory limit as a very large number (`PAGE_COUNTER_MAX`)
+const UNLIMITED_MEMORY_THRESHOLD: u64 = 1 << 62;
+
+fn main() {
+    let limits = read_container_limits(
+        Path::new(CGROUP_ROOT),
+        thread::available_parallelism().map_or(1, usize::from),
+    );
+    let settings = gc_settings(&limits, |name| configured_value(name, std::env::var));
+
+    eprintln!("{}", log_message(&limits, &settings));
+    write_exec_d_program_output(
+        settings
+            .into_iter()
+            .map(|setting| {
+                (
+                    format!("DOTNET_{}", setting.name)
+                        .parse::<ExecDProgramOutputKey>()
+                        .expect("GC setting names should be valid exec.d output keys"),
+                    setting.value,
+                )
+            })
+            .collect::<HashMap<_, _>>(),
+    );
+}
+
+/// The resources available to the app's container
+#[derive(Debug, PartialEq)]
+struct ContainerLimits {
+    /// The memory limit in bytes, if any
+    memory_limit: Option<u64>,
+    /// The number of CPUs, rounded up from the CPU quota (if any)
+    cpu_count: usize,
+}
+
+/// A GC setting, set as a `DOTNET_<name>` environment variable
+#[derive(Debug, PartialEq)]
+struct GcSetting {
+    name: &'static str,
+    /// The value, in the (hexadecimal) format read by the .NET runtime
+    value: String,
+    description: String,
+}
+
+/// Reads the memory limit and CPU quota from the cgroup v2 (unified) hierarchy, falling back to
+/// the cgroup v1 memory and cpu controllers. Without a CPU quota, the given CPU count is used.
+fn read_container_limits(cgroup_root: &Path, default_cpu_count: usize) -> ContainerLimits {
+    let memory_limit = read_cgroup_file(cgroup_root, "memory.max")
+        .or_else(|| read_cgroup_file(cgroup_root, "memory/memory.limit_in_bytes"))
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|limit| *limit > 0 && *limit < UNLIMITED_MEMORY_THRESHOLD);
+
+    let cpu_quota = match read_cgroup_file(cgroup_root, "cpu.max") {
+        Some(value) => value.split_once(' ').and_then(|(quota, period)| {
+            Some((quota.parse::<u64>().ok()?, period.parse::<u64>().ok()?))
+        }),
+        None => read_cgroup_file(cgroup_root, "cpu/cpu.cfs_quota_us")
+            .and_then(|quota| quota.parse::<u64>().ok())
+            .zip(
+                read_cgroup_file(cgroup_root, "cpu/cpu.cfs_period_us")
+                    .and_then(|period| period.parse::<u64>().ok()),
+            ),
+    };
+    let cpu_count = cpu_quota
+        .filter(|(_, period)| *period > 0)
+        .and_then(|(quota, period)| usize::try_from(quota.div_ceil(period)).ok())
+        .map_or(default_cpu_count, |count| count.clamp(1, default_cpu_count));
+
+    ContainerLimits {
+        memory_limit,
+        cpu_count,
+    }
+}
+
+fn read_cgroup_file(cgroup_root: &Path, path: &str) -> Option<String> {
+    fs::read_to_string(cgroup_root.join(path))
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+/// Returns the value of a GC setting set by the user, with either the `DOTNET_` or the legacy
+/// `COMPlus_` prefix.
+fn configured_value<E>(name: &str, var: impl Fn(String) -> Result<String, E>) -> Option<String> {
+    ["DOTNET_", "COMPlus_"]
+        .into_iter()
+        .find_map(|prefix| var(format!("{prefix}{name}")).ok())
+}
+
+/// Chooses GC settings for the container's limits, skipping settings the user already set:
+///
+/// * The GC heap is limited to a percentage of the memory limit, leaving more headroom for
+///   native memory (e.g. the runtime itself, JIT-compiled code and thread stacks) on small dynos.
+/// * Server GC (which uses a heap per CPU, and more memory) is only used when there's enough
+///   memory and more than one CPU. Otherwise, workstation GC avoids running out of memory.
+/// * With server GC, the number of heaps is limited to one per GiB of memory.
+///
+/// Without a memory limit, the .NET runtime defaults are used.
+fn gc_settings(
+    limits: &ContainerLimits,
+    configured_value: impl Fn(&str) -> Option<String>,
+) -> Vec<GcSetting> {
+    let Some(memory_limit) = limits.memory_limit else {
+        return Vec::new();
+    };
+    let mut settings = Vec::new();
+
+    if configured_value("GCHeapHardLimit").is_none()
+        && configured_value("GCHeapHardLimitPercent").is_none()
+    {
+        let percent = match memory_limit {
+            limit if limit < GIB => 60,
+            limit if limit < 4 * GIB => 70,
+            _ => 75,
+        };
+        settings.push(GcSetting {
+            name: "GCHeapHardLimitPercent",
+            value: format!("0x{percent:X}"),
+            description: format!("{percent}% of the memory limit"),
+        });
+    }
+
+    // GC settings are read as hexadecimal numbers, with an optional `0x` prefix
+    let server_gc = if let Some(value) = configured_value("gcServer") {
+        let value = value.trim();
+        u64::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16)
+            .is_ok_and(|value| value != 0)
+    } else {
+        let server_gc = memory_limit >= 2 * GIB && limits.cpu_count > 1;
+        settings.push(GcSetting {
+            name: "gcServer",
+            value: String::from(if server_gc { "1" } else { "0" }),
+            description: String::from(if server_gc {
+                "server GC"
+            } else {
+                "workstation GC"
+            }),
+        });
+        server_gc
+    };
+
+    if server_gc && configured_value("GCHeapCount").is_none() {
+        let max_heap_count = usize::try_from(memory_limit / GIB)
+            .unwrap_or(usize::MAX)
+            .max(1);
+        if max_heap_count < limits.cpu_count {
+            settings.push(GcSetting {
+                name: "GCHeapCount",
+                value: format!("0x{max_heap_count:X}"),
+                description: format!(
+                    "{max_heap_count} GC {}",
+                    if max_heap_count == 1 { "heap" } else { "heaps" }
+                ),
+            });
+        }
+    }
+
+    settings
+}
+
+fn log_message(limits: &ContainerLimits, settings: &[GcSetting]) -> String {
+    let Some(memory_limit) = limits.memory_limit else {
+        return String::from(
+            "No container memory limit detected, using the .NET runtime's default GC settings",
+        );
+    };
+    let detected = format!(
+        "Detected a memory limit of {} MiB and {} {}",
+        memory_limit / MIB,
+        limits.cpu_count,
+        if limits.cpu_count == 1 { "CPU" } else { "CPUs" }
+    );
+    if settings.is_empty() {
+        format!("{detected}, using the configured GC settings")
+    } else {
+        format!(
+            "{detected}, setting {}",
+            settings
+                .iter()
+                .map(|setting| format!(
+                    "DOTNET_{}={} ({})",
+                    setting.name, setting.value, setting.description
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_cgroup_files(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let cgroup_root = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            let path = cgroup_root.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        cgroup_root
+    }
+
+    fn limits(memory_limit: Option<u64>, cpu_count: usize) -> ContainerLimits {
+        ContainerLimits {
+            memory_limit,
+            cpu_count,
+        }
+    }
+
+    fn settings(settings: &[GcSetting]) -> Vec<(&str, &str)> {
+        settings
+            .iter()
+            .map(|setting| (setting.name, setting.value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_read_cgroup_v2_limits() {
+        let cgroup_root = write_cgroup_files(&[
+            ("memory.max", "536870912\n"),
+            ("cpu.max", "150000 100000\n"),
+        ]);
+
+        assert_eq!(
+            read_container_limits(cgroup_root.path(), 8),
+            limits(Some(512 * MIB), 2)
+        );
+    }
+
+    #[test]
+    fn test_read_cgroup_v2_unlimited() {
+        let cgroup_root =
+            write_cgroup_files(&[("memory.max", "max\n"), ("cpu.max", "max 100000\n")]);
+
+        assert_eq!(
+            read_container_limits(cgroup_root.path(), 8),
+            limits(None, 8)
+        );
+    }
+
+    #[test]
+    fn test_read_cgroup_v1_limits() {
+        let cgroup_root = write_cgroup_files(&[
+            ("memory/memory.limit_in_bytes", "1073741824\n"),
+            ("cpu/cpu.cfs_quota_us", "400000\n"),
+            ("cpu/cpu.cfs_period_us", "100000\n"),
+        ]);
+
+        assert_eq!(
+            read_container_limits(cgroup_root.path(), 8),
+            limits(Some(GIB), 4)
+        );
+    }
+
+    #[test]
+    fn test_read_cgroup_v1_unlimited() {
+        let cgroup_root = write_cgroup_files(&[
+            ("memory/memory.limit_in_bytes", "9223372036854771712\n"),
+            ("cpu/cpu.cfs_quota_us", "-1\n"),
+            ("cpu/cpu.cfs_period_us", "100000\n"),
+        ]);
+
+        assert_eq!(
+            read_container_limits(cgroup_root.path(), 4),
+            limits(None, 4)
+        );
+    }
+
+    #[test]
+    fn test_read_container_limits_without_cgroup_files() {
+        let cgroup_root = write_cgroup_files(&[]);
+
+        assert_eq!(
+            read_container_limits(cgroup_root.path(), 2),
+            limits(None, 2)
+        );
+    }
+
+    #[test]
+    fn test_gc_settings_small_dyno() {
+        assert_eq!(
+            settings(&gc_settings(&limits(Some(512 * MIB), 8), |_| None)),
+            vec![("GCHeapHardLimitPercent", "0x3C"), ("gcServer", "0")]
+        );
+    }
+
+    #[test]
+    fn test_gc_settings_medium_dyno() {
+        assert_eq!(
+            settings(&gc_settings(&limits(Some(2560 * MIB), 8), |_| None)),
+            vec![
+                ("GCHeapHardLimitPercent", "0x46"),
+                ("gcServer", "1"),
+                ("GCHeapCount", "0x2")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gc_settings_large_dyno() {
+        assert_eq!(
+            settings(&gc_settings(&limits(Some(14 * GIB), 8), |_| None)),
+            vec![("GCHeapHardLimitPercent", "0x4B"), ("gcServer", "1")]
+        );
+    }
+
+    #[test]
+    fn test_gc_settings_single_cpu() {
+        assert_eq!(
+            settings(&gc_settings(&limits(Some(4 * GIB), 1), |_| None)),
+            vec![("GCHeapHardLimitPercent", "0x4B"), ("gcServer", "0")]
+        );
+    }
+
+    #[test]
+    fn test_gc_settings_without_memory_limit() {
+        assert!(gc_settings(&limits(None, 8), |_| None).is_empty());
+    }
+
+    #[test]
+    fn test_gc_settings_skip_configured_settings() {
+        assert_eq!(
+            settings(&gc_settings(&limits(Some(512 * MIB), 8), |name| {
+                (name == "GCHeapHardLimit" || name == "gcServer").then(|| String::from("0x1"))
+            })),
+            vec![("GCHeapCount", "0x1")]
+        );
+        assert_eq!(
+            settings(&gc_settings(&limits(Some(2560 * MIB), 8), |name| {
+                (name == "GCHeapCount").then(|| String::from("4"))
+            })),
+            vec![("GCHeapHardLimitPercent", "0x46"), ("gcServer", "1")]
+        );
+    }
+
+    #[test]
+    fn test_configured_value() {
+        let env = HashMap::from([
+            ("DOTNET_gcServer".to_string(), "1".to_string()),
+            ("COMPlus_GCHeapCount".to_string(), "2".to_string()),
+        ]);
+        let var = |name: String| env.get(&name).cloned().ok_or(());
+
+        assert_eq!(configured_value("gcServer", var), Some("1".to_string()));
+        assert_eq!(configured_value("GCHeapCount", var), Some("2".to_string()));
+        assert_eq!(configured_value("GCHeapHardLimit", var), None);
+    }
+
+    #[test]
+    fn test_log_message() {
+        let limits = limits(Some(512 * MIB), 1);
+        assert_eq!(
+            log_message(&limits, &gc_settings(&limits, |_| None)),
+            "Detected a memory limit of 512 MiB and 1 CPU, setting DOTNET_GCHeapHardLimitPercent=0x3C (60% of the memory limit), DOTNET_gcServer=0 (workstation GC)"
+        );
+        assert_eq!(
+            log_message(
+                &ContainerLimits {
+                    memory_limit: None,
+                    cpu_count: 8
+                },
+                &[]
+            ),
+            "No container memory limit detected, using the .NET runtime's default GC settings"
+        );
+    }
+}
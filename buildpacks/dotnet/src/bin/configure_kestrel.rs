@@ -0,0 +1,47 @@
+//! An exec.d program that configures the ASP.NET Core Kestrel web server to listen on the port
+//! assigned by the platform, which is only known when the app starts.
+
+// Required due to: https://github.com/rust-lang/rust/issues/95513
+#![allow(unused_crate_dependencies)]
+
+use libcnb::data::exec_d::ExecDProgramOutputKey;
+use libcnb::data::exec_d_program_output_key;
+use libcnb::exec_d::write_exec_d_program_output;
+use std::collections::HashMap;
+
+fn main() {
+    write_exec_d_program_output(kestrel_env(std::env::var("PORT").ok().as_deref()));
+}
+
+/// Returns the environment variables that bind Kestrel to all interfaces on `port`. Setting
+/// `ASPNETCORE_URLS` (rather than `ASPNETCORE_HTTP_PORTS`, which requires .NET 8) works for all
+/// supported .NET versions, and takes precedence over any ports set in the app's configuration.
+fn kestrel_env(port: Option<&str>) -> HashMap<ExecDProgramOutputKey, String> {
+    port.map(str::trim)
+        .filter(|port| !port.is_empty())
+        .map(|port| {
+            HashMap::from([(
+                exec_d_program_output_key!("ASPNETCORE_URLS"),
+                format!("http://*:{port}"),
+            )])
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kestrel_env() {
+        assert_eq!(
+            kestrel_env(Some("5000")),
+            HashMap::from([(
+                exec_d_program_output_key!("ASPNETCORE_URLS"),
+                "http://*:5000".to_string()
+            )])
+        );
+        assert!(kestrel_env(Some(" ")).is_empty());
+        assert!(kestrel_env(None).is_empty());
+    }
+}
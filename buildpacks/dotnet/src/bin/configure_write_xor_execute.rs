@@ -0,0 +1,81 @@
+//! An exec.d program that disables the .NET runtime's W^X (write-xor-execute) hardening when the
+//! app runs under emulation, where W^X causes .NET processes to crash. Emulation depends on the
+//! host the image runs on, so it's only known when the app starts.
+
+// Required due to: https://github.com/rust-lang/rust/issues/95513
+#![allow(unused_crate_dependencies)]
+
+#[path = "../emulation.rs"]
+mod emulation;
+
+use libcnb::data::exec_d::ExecDProgramOutputKey;
+use libcnb::data::exec_d_program_output_key;
+use libcnb::exec_d::write_exec_d_program_output;
+use std::collections::HashMap;
+
+fn main() {
+    let env = write_xor_execute_env(emulation::is_emulated(), |name| std::env::var(name).ok());
+    if !env.is_empty() {
+        eprintln!("Emulation detected, setting DOTNET_EnableWriteXorExecute=0");
+    }
+    write_exec_d_program_output(env);
+}
+
+/// Returns the environment variables that disable W^X under emulation, unless W^X is already
+/// configured with either the `DOTNET_` or the legacy `COMPlus_` prefix.
+fn write_xor_execute_env(
+    emulated: bool,
+    var: impl Fn(&str) -> Option<String>,
+) -> HashMap<ExecDProgramOutputKey, String> {
+    let configured = [
+        "DOTNET_EnableWriteXorExecute",
+        "COMPlus_EnableWriteXorExecute",
+    ]
+    .into_iter()
+    .any(|name| var(name).is_some());
+
+    if emulated && !configured {
+        HashMap::from([(
+            exec_d_program_output_key!("DOTNET_EnableWriteXorExecute"),
+            String::from("0"),
+        )])
+    } else {
+        HashMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_xor_execute_env_emulated() {
+        assert_eq!(
+            write_xor_execute_env(true, |_| None),
+            HashMap::from([(
+                exec_d_program_output_key!("DOTNET_EnableWriteXorExecute"),
+                String::from("0")
+            )])
+        );
+    }
+
+    #[test]
+    fn test_write_xor_execute_env_native() {
+        assert!(write_xor_execute_env(false, |_| None).is_empty());
+    }
+
+    #[test]
+    fn test_write_xor_execute_env_configured() {
+        for configured_name in [
+            "DOTNET_EnableWriteXorExecute",
+            "COMPlus_EnableWriteXorExecute",
+        ] {
+            assert!(
+                write_xor_execute_env(true, |name| {
+                    (name == configured_name).then(|| String::from("1"))
+                })
+                .is_empty()
+            );
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Represents the root structure of a `*.deps.json` file, as written by `dotnet publish` next to
+/// an app's entry assembly. It lists the app's dependencies (including transitive packages)
+/// and the files they contribute.
+/// See <https://github.com/dotnet/sdk/blob/main/documentation/specs/runtime-configuration-file.md>
+#[derive(Deserialize, Debug, Default)]
+pub(crate) struct DepsJson {
+    #[serde(default)]
+    targets: BTreeMap<String, BTreeMap<String, TargetLibrary>>,
+    #[serde(default)]
+    libraries: BTreeMap<String, Library>,
+}
+
+/// The assets a library contributes to a target. Only native assets are read.
+#[derive(Deserialize, Debug, Default)]
+struct TargetLibrary {
+    #[serde(default)]
+    native: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Library {
+    #[serde(rename = "type")]
+    library_type: String,
+}
+
+impl DepsJson {
+    /// Returns the names of the packages the app depends on, directly or transitively
+    pub(crate) fn package_names(&self) -> impl Iterator<Item = &str> {
+        self.libraries
+            .iter()
+            .filter(|(_, library)| library.library_type == "package")
+            .map(|(key, _)| library_name(key))
+    }
+
+    /// Returns the name of the package that contributes the native library with the given
+    /// file name, if any.
+    pub(crate) fn native_library_package(&self, file_name: &str) -> Option<&str> {
+        self.targets
+            .values()
+            .flatten()
+            .find(|(_, target_library)| {
+                target_library.native.keys().any(|path| {
+                    Path::new(path)
+                        .file_name()
+                        .is_some_and(|name| name == file_name)
+                })
+            })
+            .filter(|(key, _)| {
+                self.libraries
+                    .get(*key)
+                    .is_some_and(|library| library.library_type == "package")
+            })
+            .map(|(key, _)| library_name(key))
+    }
+}
+
+/// Returns the name of a library from its `<name>/<version>` key
+fn library_name(key: &str) -> &str {
+    key.split_once('/').map_or(key, |(name, _)| name)
+}
+
+impl FromStr for DepsJson {
+    type Err = serde_json::Error;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str::<DepsJson>(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deps_json() {
+        let deps_json = r#"
+        {
+          "runtimeTarget": {
+            "name": ".NETCoreApp,Version=v8.0/linux-x64",
+            "signature": ""
+          },
+          "targets": {
+            ".NETCoreApp,Version=v8.0/linux-x64": {
+              "foo/1.0.0": {
+                "dependencies": {
+                  "SkiaSharp": "2.88.8",
+                  "SkiaSharp.NativeAssets.Linux": "2.88.8"
+                },
+                "runtime": {
+                  "foo.dll": {}
+                }
+              },
+              "SkiaSharp/2.88.8": {
+                "runtime": {
+                  "lib/net6.0/SkiaSharp.dll": {
+                    "assemblyVersion": "2.88.0.0",
+                    "fileVersion": "2.88.8.0"
+                  }
+                }
+              },
+              "SkiaSharp.NativeAssets.Linux/2.88.8": {
+                "native": {
+                  "runtimes/linux-x64/native/libSkiaSharp.so": {
+                    "fileVersion": "0.0.0.0"
+                  }
+                }
+              },
+              "runtimepack.Microsoft.NETCore.App.Runtime.linux-x64/8.0.0": {
+                "native": {
+                  "libcoreclr.so": {
+                    "fileVersion": "0.0.0.0"
+                  }
+                }
+              }
+            }
+          },
+          "libraries": {
+            "foo/1.0.0": {
+              "type": "project",
+              "serviceable": false,
+              "sha512": ""
+            },
+            "SkiaSharp/2.88.8": {
+              "type": "package",
+              "serviceable": true
+            },
+            "SkiaSharp.NativeAssets.Linux/2.88.8": {
+              "type": "package",
+              "serviceable": true
+            },
+            "runtimepack.Microsoft.NETCore.App.Runtime.linux-x64/8.0.0": {
+              "type": "runtimepack",
+              "serviceable": false
+            }
+          }
+        }
+        "#
+        .parse::<DepsJson>()
+        .unwrap();
+
+        assert_eq!(
+            deps_json.package_names().collect::<Vec<_>>(),
+            vec!["SkiaSharp.NativeAssets.Linux", "SkiaSharp"]
+        );
+        assert_eq!(
+            deps_json.native_library_package("libSkiaSharp.so"),
+            Some("SkiaSharp.NativeAssets.Linux")
+        );
+        assert_eq!(deps_json.native_library_package("libcoreclr.so"), None);
+        assert_eq!(deps_json.native_library_package("libfoo.so"), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_deps_json() {
+        assert!("{ invalid".parse::<DepsJson>().is_err());
+        assert_eq!("{}".parse::<DepsJson>().unwrap().package_names().count(), 0);
+    }
+}
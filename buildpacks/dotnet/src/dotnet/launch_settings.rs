@@ -0,0 +1,160 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Represents the root structure of a `Properties/launchSettings.json` file, which configures how
+/// `dotnet run` (and IDEs) start a project during development.
+/// See <https://learn.microsoft.com/en-us/aspnet/core/fundamentals/environments#lsj>
+#[derive(Debug, Default)]
+pub(crate) struct LaunchSettings {
+    /// The launch profiles, in the order they're declared in the file
+    profiles: Vec<(String, LaunchProfile)>,
+}
+
+#[derive(Deserialize)]
+struct LaunchSettingsJson {
+    #[serde(default)]
+    profiles: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A launch profile. Only the settings that apply when running the project's own executable (the
+/// `Project` command) are read: IDE and web server settings such as `applicationUrl` are ignored.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LaunchProfile {
+    pub(crate) command_name: Option<String>,
+    pub(crate) command_line_args: Option<String>,
+    #[serde(default)]
+    pub(crate) environment_variables: BTreeMap<String, String>,
+}
+
+impl LaunchSettings {
+    /// Returns the profile with the given name
+    pub(crate) fn profile(&self, name: &str) -> Option<&LaunchProfile> {
+        self.profiles
+            .iter()
+            .find(|(profile_name, _)| profile_name == name)
+            .map(|(_, profile)| profile)
+    }
+
+    /// Returns the first profile that runs the project itself, which is the profile `dotnet run`
+    /// uses by default.
+    pub(crate) fn default_profile(&self) -> Option<(&str, &LaunchProfile)> {
+        self.profiles
+            .iter()
+            .find(|(_, profile)| profile.command_name.as_deref() == Some("Project"))
+            .map(|(name, profile)| (name.as_str(), profile))
+    }
+}
+
+impl FromStr for LaunchSettings {
+    type Err = serde_json::Error;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let launch_settings = serde_json::from_str::<LaunchSettingsJson>(contents)?;
+        Ok(Self {
+            profiles: launch_settings
+                .profiles
+                .into_iter()
+                .map(|(name, profile)| {
+                    serde_json::from_value::<LaunchProfile>(profile).map(|profile| (name, profile))
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_launch_settings() {
+        let launch_settings = r#"
+        {
+          "$schema": "https://json.schemastore.org/launchsettings.json",
+          "profiles": {
+            "IIS Express": {
+              "commandName": "IISExpress",
+              "launchBrowser": true
+            },
+            "https": {
+              "commandName": "Project",
+              "dotnetRunMessages": true,
+              "commandLineArgs": "--seed 'demo data'",
+              "applicationUrl": "https://localhost:7001;http://localhost:5001",
+              "environmentVariables": {
+                "ASPNETCORE_ENVIRONMENT": "Development",
+                "FEATURE_FLAGS": "beta"
+              }
+            },
+            "http": {
+              "commandName": "Project",
+              "applicationUrl": "http://localhost:5001"
+            }
+          }
+        }
+        "#
+        .parse::<LaunchSettings>()
+        .unwrap();
+
+        let (name, profile) = launch_settings.default_profile().unwrap();
+        assert_eq!(name, "https");
+        assert_eq!(
+            profile,
+            &LaunchProfile {
+                command_name: Some("Project".to_string()),
+                command_line_args: Some("--seed 'demo data'".to_string()),
+                environment_variables: BTreeMap::from([
+                    (
+                        "ASPNETCORE_ENVIRONMENT".to_string(),
+                        "Development".to_string()
+                    ),
+                    ("FEATURE_FLAGS".to_string(), "beta".to_string()),
+                ]),
+            }
+        );
+        assert_eq!(
+            launch_settings.profile("http"),
+            Some(&LaunchProfile {
+                command_name: Some("Project".to_string()),
+                ..LaunchProfile::default()
+            })
+        );
+        assert!(launch_settings.profile("missing").is_none());
+    }
+
+    #[test]
+    fn test_parse_launch_settings_without_project_profile() {
+        let launch_settings = r#"
+        {
+          "profiles": {
+            "Docker": {
+              "commandName": "Docker"
+            }
+          }
+        }
+        "#
+        .parse::<LaunchSettings>()
+        .unwrap();
+
+        assert!(launch_settings.default_profile().is_none());
+        assert!(launch_settings.profile("Docker").is_some());
+        assert!(
+            "{}".parse::<LaunchSettings>()
+                .unwrap()
+                .default_profile()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_launch_settings() {
+        assert!("{ invalid".parse::<LaunchSettings>().is_err());
+        assert!(
+            r#"{ "profiles": { "foo": { "environmentVariables": { "PORT": 5000 } } } }"#
+                .parse::<LaunchSettings>()
+                .is_err()
+        );
+    }
+}
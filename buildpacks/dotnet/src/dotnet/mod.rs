@@ -1,5 +1,10 @@
+pub(crate) mod deps_json;
 pub(crate) mod global_json;
+pub(crate) mod launch_settings;
+pub(crate) mod msbuild_diagnostics;
 pub(crate) mod project;
+pub(crate) mod project_graph;
+pub(crate) mod runtime_config;
 pub(crate) mod runtime_identifier;
 pub(crate) mod slnx;
 pub(crate) mod solution;
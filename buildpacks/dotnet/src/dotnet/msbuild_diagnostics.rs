@@ -0,0 +1,298 @@
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::LazyLock;
+
+// Matches `MSBuild`'s canonical diagnostic format, e.g.:
+// `/workspace/Program.cs(10,5): warning IL2026: Using member 'Foo' ... [/workspace/foo.csproj]`
+// `CSC : error CS5001: Program does not contain a static 'Main' method ... [/workspace/foo.csproj]`
+// See <https://learn.microsoft.com/en-us/visualstudio/msbuild/msbuild-diagnostic-format-for-tasks>
+static DIAGNOSTIC_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:(?<origin>.*?)(?:\((?<line>\d+)(?:,(?<column>\d+))?(?:,\d+,\d+)?\))?\s*:\s*)?(?<severity>error|warning)\s+(?<code>[A-Za-z]+\d+)\s*:\s*(?<message>.*?)(?:\s+\[(?<project>[^\]]+)\])?\s*$",
+    )
+    .expect("regex to be valid")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+}
+
+/// An error or warning reported by `MSBuild` (or one of the tools it runs, such as the compiler).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Diagnostic {
+    pub(crate) severity: Severity,
+    pub(crate) code: String,
+    pub(crate) message: String,
+    /// The file (or tool, e.g. `CSC`) that reported the diagnostic
+    pub(crate) origin: Option<String>,
+    pub(crate) line: Option<u32>,
+    pub(crate) column: Option<u32>,
+    pub(crate) project: Option<String>,
+}
+
+impl Diagnostic {
+    /// Returns whether this is a trimming (`IL2xxx`) or Native AOT (`IL3xxx`) analysis warning
+    pub(crate) fn is_trim_analysis_warning(&self) -> bool {
+        self.severity == Severity::Warning
+            && (self.code.starts_with("IL2") || self.code.starts_with("IL3"))
+    }
+
+    /// Returns the documentation page for the diagnostic code, for the code prefixes with known
+    /// documentation URL patterns.
+    pub(crate) fn documentation_url(&self) -> Option<String> {
+        let code = self.code.to_lowercase();
+        let base_url = if code.starts_with("netsdk") {
+            "https://learn.microsoft.com/dotnet/core/tools/sdk-errors"
+        } else if code.starts_with("nu") {
+            "https://learn.microsoft.com/nuget/reference/errors-and-warnings"
+        } else if code.starts_with("msb") {
+            "https://learn.microsoft.com/visualstudio/msbuild/errors"
+        } else if code.starts_with("cs") {
+            "https://learn.microsoft.com/dotnet/csharp/misc"
+        } else if code.starts_with("il2") {
+            "https://learn.microsoft.com/dotnet/core/deploying/trimming/trim-warnings"
+        } else if code.starts_with("il3") {
+            "https://learn.microsoft.com/dotnet/core/deploying/native-aot/warnings"
+        } else {
+            return None;
+        };
+        Some(format!("{base_url}/{code}"))
+    }
+
+    /// Returns the location of the diagnostic, e.g. `Program.cs(10,5)`
+    pub(crate) fn location(&self) -> Option<String> {
+        let origin = self.origin.as_deref()?;
+        Some(match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{origin}({line},{column})"),
+            (Some(line), None) => format!("{origin}({line})"),
+            _ => origin.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = self.location() {
+            write!(f, "{location}: ")?;
+        }
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+/// Parses the `MSBuild` diagnostics in command output, in the order they first appear. `MSBuild`
+/// repeats diagnostics in its summary at the end of a build, so duplicates are removed.
+pub(crate) fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut seen = HashSet::new();
+    output
+        .lines()
+        .filter_map(parse_diagnostic)
+        .filter(|diagnostic| seen.insert(diagnostic.clone()))
+        .collect()
+}
+
+fn parse_diagnostic(line: &str) -> Option<Diagnostic> {
+    let captures = DIAGNOSTIC_REGEX.captures(line)?;
+    let optional_str = |name| {
+        captures
+            .name(name)
+            .map(|m| m.as_str().trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let optional_number = |name| captures.name(name).and_then(|m| m.as_str().parse().ok());
+
+    Some(Diagnostic {
+        severity: match &captures["severity"] {
+            "error" => Severity::Error,
+            _ => Severity::Warning,
+        },
+        code: captures["code"].to_string(),
+        message: captures["message"].to_string(),
+        origin: optional_str("origin"),
+        line: optional_number("line"),
+        column: optional_number("column"),
+        project: optional_str("project"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diagnostic_with_location_and_project() {
+        assert_eq!(
+            parse_diagnostic(
+                "/workspace/Program.cs(1,16): error CS0117: 'Console' does not contain a definition for 'WriteFoo' [/workspace/consoleapp.csproj]"
+            ),
+            Some(Diagnostic {
+                severity: Severity::Error,
+                code: "CS0117".to_string(),
+                message: "'Console' does not contain a definition for 'WriteFoo'".to_string(),
+                origin: Some("/workspace/Program.cs".to_string()),
+                line: Some(1),
+                column: Some(16),
+                project: Some("/workspace/consoleapp.csproj".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_diagnostic_with_tool_origin() {
+        assert_eq!(
+            parse_diagnostic(
+                "    ILLink : warning IL2104: Assembly 'Newtonsoft.Json' produced trim warnings. [/workspace/foo/foo.csproj]"
+            ),
+            Some(Diagnostic {
+                severity: Severity::Warning,
+                code: "IL2104".to_string(),
+                message: "Assembly 'Newtonsoft.Json' produced trim warnings.".to_string(),
+                origin: Some("ILLink".to_string()),
+                line: None,
+                column: None,
+                project: Some("/workspace/foo/foo.csproj".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_diagnostic_without_origin_or_project() {
+        assert_eq!(
+            parse_diagnostic("error NETSDK1004: Assets file 'project.assets.json' not found."),
+            Some(Diagnostic {
+                severity: Severity::Error,
+                code: "NETSDK1004".to_string(),
+                message: "Assets file 'project.assets.json' not found.".to_string(),
+                origin: None,
+                line: None,
+                column: None,
+                project: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_diagnostic_ignores_other_output() {
+        for line in [
+            "  Restored /workspace/foo.csproj (in 1.2 sec).",
+            "  foo -> /workspace/bin/publish/",
+            "Build FAILED.",
+            "    1 Warning(s)",
+            "    0 Error(s)",
+        ] {
+            assert_eq!(parse_diagnostic(line), None);
+        }
+    }
+
+    #[test]
+    fn test_parse_diagnostics_removes_duplicates() {
+        let output = indoc::indoc! {"
+            /workspace/Program.cs(5,1): warning IL2026: Using member 'Foo' can break functionality when trimming application code. [/workspace/foo.csproj]
+            /workspace/Program.cs(9,1): warning IL3050: Calling 'Bar' might require dynamic code. [/workspace/foo.csproj]
+              foo -> /workspace/bin/publish/
+
+            Build succeeded.
+
+            /workspace/Program.cs(5,1): warning IL2026: Using member 'Foo' can break functionality when trimming application code. [/workspace/foo.csproj]
+            /workspace/Program.cs(9,1): warning IL3050: Calling 'Bar' might require dynamic code. [/workspace/foo.csproj]
+                2 Warning(s)
+                0 Error(s)
+        "};
+
+        let diagnostics = parse_diagnostics(output);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.code.as_str())
+                .collect::<Vec<_>>(),
+            vec!["IL2026", "IL3050"]
+        );
+        assert!(diagnostics.iter().all(Diagnostic::is_trim_analysis_warning));
+    }
+
+    #[test]
+    fn test_is_trim_analysis_warning() {
+        let diagnostic = |severity, code: &str| Diagnostic {
+            severity,
+            code: code.to_string(),
+            message: String::new(),
+            origin: None,
+            line: None,
+            column: None,
+            project: None,
+        };
+
+        assert!(diagnostic(Severity::Warning, "IL2026").is_trim_analysis_warning());
+        assert!(diagnostic(Severity::Warning, "IL3050").is_trim_analysis_warning());
+        assert!(!diagnostic(Severity::Warning, "CS8618").is_trim_analysis_warning());
+        assert!(!diagnostic(Severity::Error, "IL2026").is_trim_analysis_warning());
+    }
+
+    #[test]
+    fn test_documentation_url() {
+        let diagnostic = |code: &str| Diagnostic {
+            severity: Severity::Error,
+            code: code.to_string(),
+            message: String::new(),
+            origin: None,
+            line: None,
+            column: None,
+            project: None,
+        };
+
+        let cases = [
+            (
+                "CS0103",
+                Some("https://learn.microsoft.com/dotnet/csharp/misc/cs0103"),
+            ),
+            (
+                "NETSDK1045",
+                Some("https://learn.microsoft.com/dotnet/core/tools/sdk-errors/netsdk1045"),
+            ),
+            (
+                "NU1101",
+                Some("https://learn.microsoft.com/nuget/reference/errors-and-warnings/nu1101"),
+            ),
+            (
+                "MSB3073",
+                Some("https://learn.microsoft.com/visualstudio/msbuild/errors/msb3073"),
+            ),
+            (
+                "IL2026",
+                Some(
+                    "https://learn.microsoft.com/dotnet/core/deploying/trimming/trim-warnings/il2026",
+                ),
+            ),
+            (
+                "IL3050",
+                Some(
+                    "https://learn.microsoft.com/dotnet/core/deploying/native-aot/warnings/il3050",
+                ),
+            ),
+            ("FS0039", None),
+        ];
+
+        for (code, expected) in cases {
+            assert_eq!(
+                diagnostic(code).documentation_url().as_deref(),
+                expected,
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_display_diagnostic() {
+        let diagnostic = parse_diagnostic(
+            "/workspace/Program.cs(5,1): warning IL2026: Using member 'Foo' is unsafe. [/workspace/foo.csproj]",
+        )
+        .unwrap();
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "/workspace/Program.cs(5,1): IL2026: Using member 'Foo' is unsafe."
+        );
+    }
+}
@@ -2,15 +2,31 @@ use crate::detect;
 use quick_xml::de::from_str;
 use serde::Deserialize;
 use std::io;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Project {
     pub(crate) path: PathBuf,
     pub(crate) target_framework: String,
     #[allow(clippy::struct_field_names)]
     pub(crate) project_type: ProjectType,
     pub(crate) assembly_name: String,
+    pub(crate) self_contained: bool,
+    pub(crate) publish_single_file: bool,
+    pub(crate) publish_aot: bool,
+    pub(crate) publish_trimmed: bool,
+    pub(crate) invariant_globalization: bool,
+    /// Whether the project compiles WebAssembly natively (`RunAOTCompilation` or
+    /// `WasmBuildNative`), which requires the `wasm-tools` workload
+    pub(crate) wasm_native_build: bool,
+    /// The paths of the projects referenced with `ProjectReference` items (or `#:project`
+    /// directives in file-based apps)
+    #[allow(clippy::struct_field_names)]
+    pub(crate) project_references: Vec<PathBuf>,
+    /// The IDs of the `NuGet` packages referenced with `PackageReference` items (or `#:package`
+    /// directives in file-based apps)
+    pub(crate) package_references: Vec<String>,
 }
 
 impl Project {
@@ -51,6 +67,33 @@ impl Project {
             .filter_map(|pg| pg.output_type.as_deref())
             .next_back();
 
+        let self_contained =
+            extract_bool_property(property_groups, |pg| pg.self_contained.as_ref());
+        let publish_single_file =
+            extract_bool_property(property_groups, |pg| pg.publish_single_file.as_ref());
+        let publish_aot = extract_bool_property(property_groups, |pg| pg.publish_aot.as_ref());
+        let publish_trimmed =
+            extract_bool_property(property_groups, |pg| pg.publish_trimmed.as_ref());
+        let invariant_globalization =
+            extract_bool_property(property_groups, |pg| pg.invariant_globalization.as_ref());
+        let wasm_native_build =
+            extract_bool_property(property_groups, |pg| pg.run_aot_compilation.as_ref())
+                || extract_bool_property(property_groups, |pg| pg.wasm_build_native.as_ref());
+
+        let project_references = project_xml
+            .item_groups
+            .iter()
+            .flat_map(|item_group| &item_group.project_references)
+            .map(|project_reference| resolve_project_reference(path, &project_reference.include))
+            .collect();
+
+        let package_references = project_xml
+            .item_groups
+            .iter()
+            .flat_map(|item_group| &item_group.package_references)
+            .map(|package_reference| package_reference.include.trim().to_string())
+            .collect();
+
         let project_type = project_xml
             .sdk_element
             .map(|sdk_element| sdk_element.name)
@@ -64,6 +107,14 @@ impl Project {
             target_framework,
             project_type,
             assembly_name,
+            self_contained,
+            publish_single_file,
+            publish_aot,
+            publish_trimmed,
+            invariant_globalization,
+            wasm_native_build,
+            project_references,
+            package_references,
         })
     }
 
@@ -74,6 +125,13 @@ impl Project {
         let mut sdk_id: Option<&str> = None;
         let mut target_framework: Option<&str> = None;
         let mut assembly_name: Option<&str> = None;
+        let mut self_contained: Option<&str> = None;
+        let mut publish_single_file: Option<&str> = None;
+        let mut publish_aot: Option<&str> = None;
+        let mut publish_trimmed: Option<&str> = None;
+        let mut invariant_globalization: Option<&str> = None;
+        let project_references = extract_project_directives(path, &content);
+        let package_references = extract_package_directives(&content);
 
         for line in content.lines() {
             let trimmed_line = line.trim();
@@ -100,7 +158,45 @@ impl Project {
                 assembly_name = Some(asm_val.trim_start());
             }
 
-            if sdk_id.is_some() && target_framework.is_some() && assembly_name.is_some() {
+            if self_contained.is_none()
+                && let Some(value) = trimmed_line.strip_prefix("#:property SelfContained=")
+            {
+                self_contained = Some(value.trim_start());
+            }
+
+            if publish_single_file.is_none()
+                && let Some(value) = trimmed_line.strip_prefix("#:property PublishSingleFile=")
+            {
+                publish_single_file = Some(value.trim_start());
+            }
+
+            if publish_aot.is_none()
+                && let Some(value) = trimmed_line.strip_prefix("#:property PublishAot=")
+            {
+                publish_aot = Some(value.trim_start());
+            }
+
+            if publish_trimmed.is_none()
+                && let Some(value) = trimmed_line.strip_prefix("#:property PublishTrimmed=")
+            {
+                publish_trimmed = Some(value.trim_start());
+            }
+
+            if invariant_globalization.is_none()
+                && let Some(value) = trimmed_line.strip_prefix("#:property InvariantGlobalization=")
+            {
+                invariant_globalization = Some(value.trim_start());
+            }
+
+            if sdk_id.is_some()
+                && target_framework.is_some()
+                && assembly_name.is_some()
+                && self_contained.is_some()
+                && publish_single_file.is_some()
+                && publish_aot.is_some()
+                && publish_trimmed.is_some()
+                && invariant_globalization.is_some()
+            {
                 break;
             }
         }
@@ -133,16 +229,73 @@ impl Project {
             target_framework: final_target_framework,
             project_type,
             assembly_name: final_assembly_name,
+            self_contained: self_contained.is_some_and(is_true),
+            publish_single_file: publish_single_file.is_some_and(is_true),
+            // File-based apps are published as Native AOT apps by default
+            publish_aot: publish_aot.is_none_or(is_true),
+            publish_trimmed: publish_trimmed.is_some_and(is_true),
+            invariant_globalization: invariant_globalization.is_some_and(is_true),
+            wasm_native_build: false,
+            project_references,
+            package_references,
         })
     }
 }
 
+/// Returns the resolved paths of a file-based app's `#:project` directives
+fn extract_project_directives(path: &Path, content: &str) -> Vec<PathBuf> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#:project "))
+        .map(|reference| resolve_project_reference(path, reference))
+        .collect()
+}
+
+/// Returns the package IDs of a file-based app's `#:package` directives, which specify an optional
+/// version after the package ID (e.g. `#:package Humanizer@2.14.1`)
+fn extract_package_directives(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#:package "))
+        .filter_map(|reference| reference.split('@').next())
+        .map(|package_id| package_id.trim().to_string())
+        .collect()
+}
+
 #[derive(Debug, Deserialize, Default)]
 #[serde(rename_all = "PascalCase")]
 struct PropertyGroup {
     target_framework: Option<String>,
     output_type: Option<String>,
     assembly_name: Option<String>,
+    self_contained: Option<String>,
+    publish_single_file: Option<String>,
+    publish_aot: Option<String>,
+    publish_trimmed: Option<String>,
+    invariant_globalization: Option<String>,
+    #[serde(rename = "RunAOTCompilation")]
+    run_aot_compilation: Option<String>,
+    wasm_build_native: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ItemGroup {
+    #[serde(rename = "ProjectReference", default)]
+    project_references: Vec<ProjectReference>,
+    #[serde(rename = "PackageReference", default)]
+    package_references: Vec<PackageReference>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectReference {
+    #[serde(rename = "@Include")]
+    include: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageReference {
+    #[serde(rename = "@Include")]
+    include: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -153,6 +306,8 @@ struct ProjectXml {
     sdk_element: Option<SdkElement>,
     #[serde(rename = "PropertyGroup", default)]
     property_groups: Vec<PropertyGroup>,
+    #[serde(rename = "ItemGroup", default)]
+    item_groups: Vec<ItemGroup>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -208,6 +363,45 @@ fn extract_target_framework(property_groups: &[PropertyGroup]) -> Option<String>
         .cloned()
 }
 
+/// Returns whether the last value set for a boolean property is `true` (`MSBuild` compares property
+/// values case-insensitively).
+fn extract_bool_property(
+    property_groups: &[PropertyGroup],
+    property: impl Fn(&PropertyGroup) -> Option<&String>,
+) -> bool {
+    property_groups
+        .iter()
+        .filter_map(property)
+        .next_back()
+        .is_some_and(|value| is_true(value))
+}
+
+/// Resolves a project reference relative to the referencing project's directory. References
+/// commonly use Windows path separators, and `..` components are removed so that references to the
+/// same project from different directories resolve to the same path.
+fn resolve_project_reference(project_path: &Path, reference: &str) -> PathBuf {
+    let reference_path = project_path
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(reference.trim().replace('\\', "/"));
+
+    let mut resolved_path = PathBuf::new();
+    for component in reference_path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved_path.pop();
+            }
+            _ => resolved_path.push(component),
+        }
+    }
+    resolved_path
+}
+
+fn is_true(value: &str) -> bool {
+    value.trim().eq_ignore_ascii_case("true")
+}
+
 fn find_target_framework_from_directory_build_props(
     file_path: &Path,
 ) -> Result<Option<String>, FileLoadError> {
@@ -221,6 +415,74 @@ fn find_target_framework_from_directory_build_props(
     Ok(extract_target_framework(&props_xml.property_groups))
 }
 
+/// Builds `Project` values for tests. Projects default to an unknown `net9.0` project without
+/// references, named after the project file.
+#[cfg(test)]
+pub(crate) struct ProjectBuilder {
+    project: Project,
+}
+
+#[cfg(test)]
+impl ProjectBuilder {
+    pub(crate) fn new(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        Self {
+            project: Project {
+                assembly_name: path
+                    .file_stem()
+                    .map(|file_stem| file_stem.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                path,
+                target_framework: "net9.0".to_string(),
+                project_type: ProjectType::Unknown,
+                self_contained: false,
+                publish_single_file: false,
+                publish_aot: false,
+                publish_trimmed: false,
+                invariant_globalization: false,
+                wasm_native_build: false,
+                project_references: vec![],
+                package_references: vec![],
+            },
+        }
+    }
+
+    pub(crate) fn project_type(mut self, project_type: ProjectType) -> Self {
+        self.project.project_type = project_type;
+        self
+    }
+
+    pub(crate) fn assembly_name(mut self, assembly_name: &str) -> Self {
+        self.project.assembly_name = assembly_name.to_string();
+        self
+    }
+
+    pub(crate) fn invariant_globalization(mut self) -> Self {
+        self.project.invariant_globalization = true;
+        self
+    }
+
+    pub(crate) fn wasm_native_build(mut self) -> Self {
+        self.project.wasm_native_build = true;
+        self
+    }
+
+    pub(crate) fn project_references(mut self, project_references: &[&str]) -> Self {
+        self.project.project_references = project_references.iter().map(PathBuf::from).collect();
+        self
+    }
+
+    pub(crate) fn package_references(mut self, package_references: &[&str]) -> Self {
+        self.project.package_references =
+            package_references.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    pub(crate) fn build(self) -> Project {
+        self.project
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,6 +567,115 @@ mod tests {
         assert_eq!(project.project_type, ProjectType::Unknown);
     }
 
+    #[test]
+    fn test_load_project_with_publish_properties() {
+        let project_xml = r#"
+<Project Sdk="Microsoft.NET.Sdk.Web">
+    <PropertyGroup>
+        <TargetFramework>net9.0</TargetFramework>
+        <SelfContained>false</SelfContained>
+        <PublishSingleFile>true</PublishSingleFile>
+        <PublishAot>true</PublishAot>
+        <PublishTrimmed>true</PublishTrimmed>
+        <InvariantGlobalization>true</InvariantGlobalization>
+    </PropertyGroup>
+    <PropertyGroup>
+        <SelfContained>True</SelfContained>
+    </PropertyGroup>
+</Project>
+"#;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_path = temp_dir.path().join("WebApp.csproj");
+        fs::write(&project_path, project_xml).unwrap();
+
+        let project = Project::load_from_path(&project_path).unwrap();
+        assert!(project.self_contained);
+        assert!(project.publish_single_file);
+        assert!(project.publish_aot);
+        assert!(project.publish_trimmed);
+        assert!(project.invariant_globalization);
+        assert!(!project.wasm_native_build);
+    }
+
+    #[test]
+    fn test_load_project_with_wasm_native_build_properties() {
+        for property in ["RunAOTCompilation", "WasmBuildNative"] {
+            let project_xml = format!(
+                r#"
+<Project Sdk="Microsoft.NET.Sdk.BlazorWebAssembly">
+    <PropertyGroup>
+        <TargetFramework>net9.0</TargetFramework>
+        <{property}>true</{property}>
+    </PropertyGroup>
+</Project>
+"#
+            );
+            let temp_dir = tempfile::tempdir().unwrap();
+            let project_path = temp_dir.path().join("BlazorApp.csproj");
+            fs::write(&project_path, project_xml).unwrap();
+
+            let project = Project::load_from_path(&project_path).unwrap();
+            assert!(project.wasm_native_build, "{property}");
+        }
+    }
+
+    #[test]
+    fn test_load_project_with_project_references() {
+        let project_xml = r#"
+<Project Sdk="Microsoft.NET.Sdk.Web">
+    <PropertyGroup>
+        <TargetFramework>net9.0</TargetFramework>
+    </PropertyGroup>
+    <ItemGroup>
+        <ProjectReference Include="..\Shared\Shared.csproj" />
+        <PackageReference Include="Newtonsoft.Json" Version="13.0.3" />
+        <ProjectReference Include="./Data/Data.csproj" />
+    </ItemGroup>
+    <PropertyGroup>
+        <PublishTrimmed>true</PublishTrimmed>
+    </PropertyGroup>
+    <ItemGroup>
+        <ProjectReference Include="../Shared/../Domain/Domain.csproj" />
+    </ItemGroup>
+</Project>
+"#;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_dir = temp_dir.path().join("WebApp");
+        fs::create_dir_all(&project_dir).unwrap();
+        let project_path = project_dir.join("WebApp.csproj");
+        fs::write(&project_path, project_xml).unwrap();
+
+        let project = Project::load_from_path(&project_path).unwrap();
+        assert_eq!(
+            project.project_references,
+            vec![
+                temp_dir.path().join("Shared/Shared.csproj"),
+                project_dir.join("Data/Data.csproj"),
+                temp_dir.path().join("Domain/Domain.csproj"),
+            ]
+        );
+        assert_eq!(project.package_references, vec!["Newtonsoft.Json"]);
+        assert!(project.publish_trimmed);
+    }
+
+    #[test]
+    fn test_load_project_without_publish_properties() {
+        let project_xml = r#"
+<Project Sdk="Microsoft.NET.Sdk.Web">
+    <PropertyGroup>
+        <TargetFramework>net9.0</TargetFramework>
+    </PropertyGroup>
+</Project>
+"#;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_path = temp_dir.path().join("WebApp.csproj");
+        fs::write(&project_path, project_xml).unwrap();
+
+        let project = Project::load_from_path(&project_path).unwrap();
+        assert!(!project.self_contained);
+        assert!(!project.publish_single_file);
+    }
+
     #[test]
     fn test_project_type_inference() {
         assert_eq!(
@@ -451,6 +822,8 @@ Console.WriteLine("foobar");
         // Should default to "net10.0"
         assert_eq!(project.target_framework, "net10.0");
         assert_eq!(project.assembly_name, "DefaultApp");
+        // Should default to Native AOT
+        assert!(project.publish_aot);
     }
 
     #[test]
@@ -477,6 +850,59 @@ Console.WriteLine("foobar");
         // It should find the AssemblyName
         assert_eq!(project.assembly_name, "CustomAssemblyName");
         assert_eq!(project.path, app_path);
+        assert!(!project.self_contained);
+        assert!(!project.publish_single_file);
+        assert!(project.publish_aot);
+    }
+
+    #[test]
+    fn test_load_file_based_app_with_publish_properties() {
+        let project_cs = r#"
+#:property SelfContained=true
+#:property PublishSingleFile=true
+#:property PublishAot=false
+#:property PublishTrimmed=true
+#:property InvariantGlobalization=true
+
+Console.WriteLine("foobar");
+"#;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_path = temp_dir.path().join("MyApp.cs");
+        fs::write(&app_path, project_cs).unwrap();
+
+        let project = Project::load_from_file_based_app(&app_path).unwrap();
+
+        assert!(project.self_contained);
+        assert!(project.publish_single_file);
+        assert!(!project.publish_aot);
+        assert!(project.publish_trimmed);
+        assert!(project.invariant_globalization);
+    }
+
+    #[test]
+    fn test_load_file_based_app_with_project_references() {
+        let project_cs = r#"
+#:project ../Shared/Shared.csproj
+#:package Humanizer@2.14.1
+#:package Microsoft.EntityFrameworkCore.Design
+
+Console.WriteLine("foobar");
+"#;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path().join("app");
+        fs::create_dir_all(&app_dir).unwrap();
+        let app_path = app_dir.join("MyApp.cs");
+        fs::write(&app_path, project_cs).unwrap();
+
+        let project = Project::load_from_file_based_app(&app_path).unwrap();
+        assert_eq!(
+            project.project_references,
+            vec![temp_dir.path().join("Shared/Shared.csproj")]
+        );
+        assert_eq!(
+            project.package_references,
+            vec!["Humanizer", "Microsoft.EntityFrameworkCore.Design"]
+        );
     }
 
     #[test]
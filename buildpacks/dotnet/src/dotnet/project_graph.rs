@@ -0,0 +1,283 @@
+use crate::dotnet::project::Project;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// The `ProjectReference` graph of a set of projects
+#[derive(Debug, Default)]
+pub(crate) struct ProjectGraph {
+    /// The paths of the projects in the graph, in the order they were added
+    paths: Vec<PathBuf>,
+    references: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl ProjectGraph {
+    pub(crate) fn new<'a>(projects: impl IntoIterator<Item = &'a Project>) -> Self {
+        let mut graph = Self::default();
+        for project in projects {
+            if graph
+                .references
+                .insert(project.path.clone(), project.project_references.clone())
+                .is_none()
+            {
+                graph.paths.push(project.path.clone());
+            }
+        }
+        graph
+    }
+
+    pub(crate) fn contains(&self, path: &Path) -> bool {
+        self.references.contains_key(path)
+    }
+
+    /// Returns the projects directly referenced by a project
+    pub(crate) fn references(&self, path: &Path) -> &[PathBuf] {
+        self.references.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns all the projects a project (transitively) references, closest references first.
+    /// References to projects that aren't in the graph are included, but not followed.
+    pub(crate) fn transitive_references(&self, path: &Path) -> Vec<PathBuf> {
+        let mut visited = HashSet::from([path.to_path_buf()]);
+        let mut queue = self.references(path).iter().collect::<VecDeque<_>>();
+        let mut transitive_references = Vec::new();
+
+        while let Some(reference) = queue.pop_front() {
+            if visited.insert(reference.clone()) {
+                transitive_references.push(reference.clone());
+                queue.extend(self.references(reference));
+            }
+        }
+        transitive_references
+    }
+
+    /// Returns the projects that aren't referenced by any other project in the graph
+    pub(crate) fn roots(&self) -> Vec<&Path> {
+        let referenced = self
+            .references
+            .values()
+            .flatten()
+            .map(PathBuf::as_path)
+            .collect::<HashSet<_>>();
+
+        self.paths
+            .iter()
+            .map(PathBuf::as_path)
+            .filter(|path| !referenced.contains(path))
+            .collect()
+    }
+
+    /// Returns the circular references in the graph. Each cycle starts and ends with the same
+    /// project, e.g. `[a, b, a]`.
+    pub(crate) fn cycles(&self) -> Vec<Vec<PathBuf>> {
+        let mut finished = HashSet::new();
+        let mut cycles = Vec::new();
+        for path in &self.paths {
+            self.find_cycles(path, &mut Vec::new(), &mut finished, &mut cycles);
+        }
+        cycles
+    }
+
+    fn find_cycles<'a>(
+        &'a self,
+        path: &'a Path,
+        stack: &mut Vec<&'a Path>,
+        finished: &mut HashSet<&'a Path>,
+        cycles: &mut Vec<Vec<PathBuf>>,
+    ) {
+        if finished.contains(path) {
+            return;
+        }
+        if let Some(start) = stack.iter().position(|&visiting| visiting == path) {
+            let mut cycle = stack[start..]
+                .iter()
+                .map(|path| path.to_path_buf())
+                .collect::<Vec<_>>();
+            cycle.push(path.to_path_buf());
+            cycles.push(cycle);
+            return;
+        }
+
+        stack.push(path);
+        for reference in self.references(path) {
+            self.find_cycles(reference, stack, finished, cycles);
+        }
+        stack.pop();
+        finished.insert(path);
+    }
+
+    /// Formats the dependency tree of the given root projects, with paths relative to `base_dir`
+    pub(crate) fn format_tree(&self, roots: &[&Path], base_dir: &Path) -> String {
+        let mut lines = Vec::new();
+        for root in roots {
+            lines.push(display_path(root, base_dir));
+            self.format_subtree(root, base_dir, "", &mut vec![*root], &mut lines);
+        }
+        lines.join("\n")
+    }
+
+    fn format_subtree<'a>(
+        &'a self,
+        path: &Path,
+        base_dir: &Path,
+        indent: &str,
+        ancestors: &mut Vec<&'a Path>,
+        lines: &mut Vec<String>,
+    ) {
+        let references = self.references(path);
+        for (index, reference) in references.iter().enumerate() {
+            let is_last = index == references.len() - 1;
+            let (branch, child_indent) = if is_last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            let mut line = format!("{indent}{branch}{}", display_path(reference, base_dir));
+            if ancestors.contains(&reference.as_path()) {
+                line.push_str(" (circular reference)");
+                lines.push(line);
+            } else if self.contains(reference) {
+                lines.push(line);
+                ancestors.push(reference);
+                self.format_subtree(
+                    reference,
+                    base_dir,
+                    &format!("{indent}{child_indent}"),
+                    ancestors,
+                    lines,
+                );
+                ancestors.pop();
+            } else {
+                line.push_str(" (not found)");
+                lines.push(line);
+            }
+        }
+    }
+}
+
+fn display_path(path: &Path, base_dir: &Path) -> String {
+    path.strip_prefix(base_dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dotnet::project::ProjectBuilder;
+
+    fn create_test_graph() -> ProjectGraph {
+        ProjectGraph::new(&[
+            ProjectBuilder::new("/app/web/web.csproj")
+                .project_references(&["/app/shared/shared.csproj", "/app/data/data.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/worker/worker.csproj")
+                .project_references(&["/app/shared/shared.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/shared/shared.csproj")
+                .project_references(&["/app/data/data.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/data/data.csproj")
+                .project_references(&["/app/missing/missing.csproj"])
+                .build(),
+        ])
+    }
+
+    #[test]
+    fn test_transitive_references() {
+        let graph = create_test_graph();
+
+        assert_eq!(
+            graph.transitive_references(Path::new("/app/web/web.csproj")),
+            vec![
+                PathBuf::from("/app/shared/shared.csproj"),
+                PathBuf::from("/app/data/data.csproj"),
+                PathBuf::from("/app/missing/missing.csproj"),
+            ]
+        );
+        assert!(
+            graph
+                .transitive_references(Path::new("/app/missing/missing.csproj"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_roots() {
+        assert_eq!(
+            create_test_graph().roots(),
+            vec![
+                Path::new("/app/web/web.csproj"),
+                Path::new("/app/worker/worker.csproj")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cycles() {
+        assert!(create_test_graph().cycles().is_empty());
+
+        let graph = ProjectGraph::new(&[
+            ProjectBuilder::new("/app/a.csproj")
+                .project_references(&["/app/b.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/b.csproj")
+                .project_references(&["/app/c.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/c.csproj")
+                .project_references(&["/app/a.csproj", "/app/d.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/d.csproj").build(),
+        ]);
+        assert_eq!(
+            graph.cycles(),
+            vec![vec![
+                PathBuf::from("/app/a.csproj"),
+                PathBuf::from("/app/b.csproj"),
+                PathBuf::from("/app/c.csproj"),
+                PathBuf::from("/app/a.csproj"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_format_tree() {
+        let graph = create_test_graph();
+
+        assert_eq!(
+            graph.format_tree(&graph.roots(), Path::new("/app")),
+            indoc::indoc! {"
+                web/web.csproj
+                ├── shared/shared.csproj
+                │   └── data/data.csproj
+                │       └── missing/missing.csproj (not found)
+                └── data/data.csproj
+                    └── missing/missing.csproj (not found)
+                worker/worker.csproj
+                └── shared/shared.csproj
+                    └── data/data.csproj
+                        └── missing/missing.csproj (not found)"}
+        );
+    }
+
+    #[test]
+    fn test_format_tree_with_circular_reference() {
+        let graph = ProjectGraph::new(&[
+            ProjectBuilder::new("/app/a.csproj")
+                .project_references(&["/app/b.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/b.csproj")
+                .project_references(&["/app/a.csproj"])
+                .build(),
+        ]);
+
+        assert_eq!(
+            graph.format_tree(&[Path::new("/app/a.csproj")], Path::new("/app")),
+            indoc::indoc! {"
+                a.csproj
+                └── b.csproj
+                    └── a.csproj (circular reference)"}
+        );
+    }
+}
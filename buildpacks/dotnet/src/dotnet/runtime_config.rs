@@ -0,0 +1,272 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Represents the root structure of a `*.runtimeconfig.json` file, as written by `dotnet publish`
+/// next to an app's entry assembly (and shipped with each shared framework).
+#[derive(Deserialize)]
+pub(crate) struct RuntimeConfig {
+    #[serde(rename = "runtimeOptions", default)]
+    runtime_options: RuntimeOptions,
+}
+
+#[derive(Deserialize, Default)]
+struct RuntimeOptions {
+    framework: Option<FrameworkReference>,
+    #[serde(default)]
+    frameworks: Vec<FrameworkReference>,
+    #[serde(rename = "includedFrameworks", default)]
+    included_frameworks: Vec<FrameworkReference>,
+    #[serde(rename = "rollForward")]
+    roll_forward: Option<String>,
+    #[serde(rename = "configProperties", default)]
+    config_properties: BTreeMap<String, serde_json::Value>,
+}
+
+/// A reference to a shared framework (e.g. `Microsoft.AspNetCore.App`) and the minimum version
+/// required.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct FrameworkReference {
+    pub(crate) name: String,
+    pub(crate) version: String,
+}
+
+impl RuntimeConfig {
+    /// Returns the shared frameworks referenced by a framework-dependent app. Self-contained apps
+    /// list their bundled frameworks as `includedFrameworks` instead, and don't reference any.
+    pub(crate) fn framework_references(&self) -> Vec<&FrameworkReference> {
+        self.runtime_options
+            .framework
+            .iter()
+            .chain(&self.runtime_options.frameworks)
+            .collect()
+    }
+
+    /// Returns whether the app uses the given shared framework, either by referencing it
+    /// (framework-dependent apps) or by bundling it (self-contained apps).
+    pub(crate) fn uses_framework(&self, name: &str) -> bool {
+        self.framework_references()
+            .into_iter()
+            .chain(&self.runtime_options.included_frameworks)
+            .any(|framework| framework.name == name)
+    }
+
+    /// Returns whether the app allows rolling forward to a newer major version of its shared
+    /// frameworks.
+    /// See <https://learn.microsoft.com/en-us/dotnet/core/versions/selection#framework-dependent-apps-roll-forward>
+    pub(crate) fn rolls_forward_to_major(&self) -> bool {
+        matches!(
+            self.runtime_options.roll_forward.as_deref(),
+            Some("Major" | "LatestMajor")
+        )
+    }
+
+    /// Returns whether the app runs in globalization-invariant mode (set with the
+    /// `InvariantGlobalization` project property), in which case ICU isn't loaded. This setting
+    /// takes precedence over the `DOTNET_SYSTEM_GLOBALIZATION_INVARIANT` environment variable.
+    /// See <https://learn.microsoft.com/en-us/dotnet/core/runtime-config/globalization#invariant-mode>
+    pub(crate) fn globalization_invariant(&self) -> Option<bool> {
+        match self
+            .runtime_options
+            .config_properties
+            .get("System.Globalization.Invariant")?
+        {
+            serde_json::Value::Bool(value) => Some(*value),
+            serde_json::Value::String(value) => {
+                Some(value.eq_ignore_ascii_case("true") || value == "1")
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the GC settings that the buildpack otherwise sets at launch which the app configures
+    /// (e.g. with the `ServerGarbageCollection` project property), as the equivalent `DOTNET_`
+    /// environment variable names and (hexadecimal) values.
+    /// See <https://learn.microsoft.com/en-us/dotnet/core/runtime-config/garbage-collector>
+    pub(crate) fn gc_settings(&self) -> Vec<(&'static str, String)> {
+        [
+            ("System.GC.Server", "DOTNET_gcServer"),
+            ("System.GC.HeapCount", "DOTNET_GCHeapCount"),
+            ("System.GC.HeapHardLimit", "DOTNET_GCHeapHardLimit"),
+            (
+                "System.GC.HeapHardLimitPercent",
+                "DOTNET_GCHeapHardLimitPercent",
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(property, name)| {
+            let value = match self.runtime_options.config_properties.get(property)? {
+                serde_json::Value::Bool(value) => u64::from(*value),
+                serde_json::Value::Number(value) => value.as_u64()?,
+                serde_json::Value::String(value) => match value.to_ascii_lowercase().as_str() {
+                    "true" => 1,
+                    "false" => 0,
+                    value => value.parse().ok()?,
+                },
+                _ => return None,
+            };
+            Some((name, format!("0x{value:X}")))
+        })
+        .collect()
+    }
+
+    /// Returns the version of the ICU libraries bundled with the app (set with a
+    /// `System.Globalization.AppLocalIcu` runtime host configuration option), if any.
+    /// See <https://learn.microsoft.com/en-us/dotnet/core/extensions/globalization-icu#app-local-icu>
+    pub(crate) fn app_local_icu(&self) -> Option<&str> {
+        self.runtime_options
+            .config_properties
+            .get("System.Globalization.AppLocalIcu")
+            .and_then(serde_json::Value::as_str)
+    }
+}
+
+impl FromStr for RuntimeConfig {
+    type Err = serde_json::Error;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str::<RuntimeConfig>(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn framework(name: &str, version: &str) -> FrameworkReference {
+        FrameworkReference {
+            name: name.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_console_app_runtime_config() {
+        let runtime_config = r#"
+        {
+          "runtimeOptions": {
+            "tfm": "net8.0",
+            "framework": {
+              "name": "Microsoft.NETCore.App",
+              "version": "8.0.0"
+            },
+            "configProperties": {
+              "System.Runtime.Serialization.EnableUnsafeBinaryFormatterSerialization": false
+            }
+          }
+        }
+        "#
+        .parse::<RuntimeConfig>()
+        .unwrap();
+
+        assert_eq!(
+            runtime_config.framework_references(),
+            vec![&framework("Microsoft.NETCore.App", "8.0.0")]
+        );
+        assert!(!runtime_config.rolls_forward_to_major());
+        assert!(!runtime_config.uses_framework("Microsoft.AspNetCore.App"));
+        assert_eq!(runtime_config.globalization_invariant(), None);
+        assert_eq!(runtime_config.app_local_icu(), None);
+        assert!(runtime_config.gc_settings().is_empty());
+    }
+
+    #[test]
+    fn test_parse_config_properties_runtime_config() {
+        let runtime_config = r#"
+        {
+          "runtimeOptions": {
+            "tfm": "net8.0",
+            "framework": {
+              "name": "Microsoft.NETCore.App",
+              "version": "8.0.0"
+            },
+            "configProperties": {
+              "System.Globalization.Invariant": true,
+              "System.Globalization.AppLocalIcu": "72.1.0.3",
+              "System.GC.Server": false,
+              "System.GC.HeapHardLimit": 209715200,
+              "System.GC.HeapHardLimitPercent": "30"
+            }
+          }
+        }
+        "#
+        .parse::<RuntimeConfig>()
+        .unwrap();
+
+        assert_eq!(runtime_config.globalization_invariant(), Some(true));
+        assert_eq!(runtime_config.app_local_icu(), Some("72.1.0.3"));
+        assert_eq!(
+            runtime_config.gc_settings(),
+            vec![
+                ("DOTNET_gcServer", String::from("0x0")),
+                ("DOTNET_GCHeapHardLimit", String::from("0xC800000")),
+                ("DOTNET_GCHeapHardLimitPercent", String::from("0x1E")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_web_app_runtime_config() {
+        let runtime_config = r#"
+        {
+          "runtimeOptions": {
+            "tfm": "net9.0",
+            "rollForward": "LatestMajor",
+            "frameworks": [
+              {
+                "name": "Microsoft.NETCore.App",
+                "version": "9.0.0"
+              },
+              {
+                "name": "Microsoft.AspNetCore.App",
+                "version": "9.0.0"
+              }
+            ]
+          }
+        }
+        "#
+        .parse::<RuntimeConfig>()
+        .unwrap();
+
+        assert_eq!(
+            runtime_config.framework_references(),
+            vec![
+                &framework("Microsoft.NETCore.App", "9.0.0"),
+                &framework("Microsoft.AspNetCore.App", "9.0.0")
+            ]
+        );
+        assert!(runtime_config.rolls_forward_to_major());
+        assert!(runtime_config.uses_framework("Microsoft.AspNetCore.App"));
+    }
+
+    #[test]
+    fn test_parse_self_contained_app_runtime_config() {
+        let runtime_config = r#"
+        {
+          "runtimeOptions": {
+            "tfm": "net8.0",
+            "includedFrameworks": [
+              {
+                "name": "Microsoft.NETCore.App",
+                "version": "8.0.1"
+              },
+              {
+                "name": "Microsoft.AspNetCore.App",
+                "version": "8.0.1"
+              }
+            ]
+          }
+        }
+        "#
+        .parse::<RuntimeConfig>()
+        .unwrap();
+
+        assert!(runtime_config.framework_references().is_empty());
+        assert!(runtime_config.uses_framework("Microsoft.AspNetCore.App"));
+    }
+
+    #[test]
+    fn test_parse_invalid_runtime_config() {
+        assert!("{ invalid".parse::<RuntimeConfig>().is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::dotnet::project::{self, Project};
+use crate::dotnet::project_graph::ProjectGraph;
 use crate::dotnet::slnx;
 use regex::Regex;
+use std::collections::{HashSet, VecDeque};
 use std::io::{self};
 use std::path::{Path, PathBuf};
 
@@ -8,6 +10,9 @@ use std::path::{Path, PathBuf};
 pub(crate) struct Solution {
     pub(crate) path: PathBuf,
     pub(crate) projects: Vec<Project>,
+    /// Projects (transitively) referenced by the solution's projects that aren't part of the
+    /// solution itself
+    pub(crate) referenced_projects: Vec<Project>,
 }
 
 impl Solution {
@@ -23,22 +28,63 @@ impl Solution {
             extract_project_references(&contents)
         };
 
+        let projects = project_paths
+            .into_iter()
+            .filter_map(|project_path| path.parent().map(|dir| dir.join(&project_path)))
+            .map(try_load_project)
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(Self {
             path: path.to_path_buf(),
-            projects: project_paths
-                .into_iter()
-                .filter_map(|project_path| path.parent().map(|dir| dir.join(&project_path)))
-                .map(try_load_project)
-                .collect::<Result<Vec<_>, _>>()?,
+            referenced_projects: load_referenced_projects(&projects),
+            projects,
         })
     }
 
     pub(crate) fn ephemeral(project: Project) -> Self {
+        let projects = vec![project];
         Self {
-            path: project.path.clone(),
-            projects: vec![project],
+            path: projects[0].path.clone(),
+            referenced_projects: load_referenced_projects(&projects),
+            projects,
         }
     }
+
+    /// Returns the solution's projects, followed by the projects they reference that aren't part
+    /// of the solution
+    pub(crate) fn all_projects(&self) -> impl Iterator<Item = &Project> {
+        self.projects.iter().chain(&self.referenced_projects)
+    }
+
+    pub(crate) fn project_graph(&self) -> ProjectGraph {
+        ProjectGraph::new(self.all_projects())
+    }
+}
+
+/// Loads the projects (transitively) referenced by the given projects that aren't part of them.
+/// Like properties, references are read regardless of any `Condition` attributes. References that
+/// can't be loaded are skipped, as `MSBuild` reports them with more context when publishing.
+fn load_referenced_projects(projects: &[Project]) -> Vec<Project> {
+    let mut known_paths = projects
+        .iter()
+        .map(|project| project.path.clone())
+        .collect::<HashSet<_>>();
+    let mut queue = projects
+        .iter()
+        .flat_map(|project| project.project_references.iter().cloned())
+        .collect::<VecDeque<_>>();
+    let mut referenced_projects = Vec::new();
+
+    while let Some(path) = queue.pop_front() {
+        if !known_paths.insert(path.clone()) {
+            continue;
+        }
+        if let Ok(project) = Project::load_from_path(&path) {
+            queue.extend(project.project_references.iter().cloned());
+            referenced_projects.push(project);
+        }
+    }
+    referenced_projects
 }
 
 fn try_load_project(path: PathBuf) -> Result<Project, LoadError> {
@@ -235,6 +281,49 @@ mod tests {
         assert_eq!(solution.projects[1].path, project2_path);
     }
 
+    #[test]
+    fn test_load_from_path_should_load_projects_referenced_outside_the_solution() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let solution_path = temp_dir.path().join("test.sln");
+        fs::write(&solution_path, SOLUTION_WITH_TWO_PROJECTS).unwrap();
+        create_test_project(&temp_dir, "Project2");
+        let shared_path = create_test_project(&temp_dir, "Shared");
+        let project1_dir = temp_dir.path().join("Project1");
+        fs::create_dir(&project1_dir).unwrap();
+        fs::write(
+            project1_dir.join("Project1.csproj"),
+            r#"
+            <Project Sdk="Microsoft.NET.Sdk">
+                <PropertyGroup>
+                    <TargetFramework>net8.0</TargetFramework>
+                </PropertyGroup>
+                <ItemGroup>
+                    <ProjectReference Include="..\Project2\Project2.csproj" />
+                    <ProjectReference Include="..\Shared\Shared.csproj" />
+                    <ProjectReference Include="..\Missing\Missing.csproj" />
+                </ItemGroup>
+            </Project>"#,
+        )
+        .unwrap();
+
+        let solution = Solution::load_from_path(&solution_path).unwrap();
+
+        assert_eq!(solution.projects.len(), 2);
+        assert_eq!(solution.referenced_projects.len(), 1);
+        assert_eq!(solution.referenced_projects[0].path, shared_path);
+        assert_eq!(solution.all_projects().count(), 3);
+        assert_eq!(
+            solution
+                .project_graph()
+                .transitive_references(&project1_dir.join("Project1.csproj")),
+            vec![
+                temp_dir.path().join("Project2/Project2.csproj"),
+                shared_path,
+                temp_dir.path().join("Missing/Missing.csproj"),
+            ]
+        );
+    }
+
     #[test]
     fn test_load_from_path_should_return_error_when_solution_file_does_not_exist() {
         let temp_dir = tempfile::tempdir().unwrap();
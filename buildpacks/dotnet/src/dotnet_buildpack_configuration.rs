@@ -1,23 +1,53 @@
 use crate::app_source::SOLUTION_EXTENSIONS;
-use crate::project_toml::DotnetConfig;
+use crate::project_toml::{DotnetConfig, SdkConfig};
+use reqwest::Url;
 use std::fmt;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct DotnetBuildpackConfiguration {
     pub(crate) build_configuration: Option<String>,
+    pub(crate) ef_migrations: EfMigrationsOptions,
     pub(crate) execution_environment: ExecutionEnvironment,
+    pub(crate) globalization_options: GlobalizationOptions,
+    pub(crate) launch_process_mode: LaunchProcessMode,
+    pub(crate) launch_profile: Option<String>,
+    pub(crate) launch_smoke_check: bool,
     pub(crate) msbuild_verbosity_level: Option<VerbosityLevel>,
+    pub(crate) msbuild_binary_log: bool,
+    pub(crate) procfile_mode: ProcfileMode,
+    pub(crate) publish_mode: Option<PublishMode>,
+    pub(crate) publish_strategy: PublishStrategy,
+    pub(crate) publish_concurrency: NonZeroUsize,
+    pub(crate) publish_ready_to_run: bool,
+    pub(crate) publish_fail_on_trim_warnings: bool,
+    pub(crate) run_image_libraries: Vec<String>,
+    pub(crate) runtime_options: RuntimeOptions,
+    pub(crate) sdk_artifact_source: SdkArtifactSource,
     pub(crate) solution_file: Option<PathBuf>,
+    /// Whether to run `dotnet workload restore` before publishing. Unset, workloads are restored
+    /// when a project is detected to need them.
+    pub(crate) workload_restore: Option<bool>,
+    pub(crate) write_xor_execute: WriteXorExecuteOptions,
 }
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum DotnetBuildpackConfigurationError {
     ExecutionEnvironment(ExecutionEnvironmentError),
+    LaunchProcessMode(ParseLaunchProcessModeError),
+    ProcfileMode(ParseProcfileModeError),
     VerbosityLevel(ParseVerbosityLevelError),
+    PublishMode(ParsePublishModeError),
+    PublishStrategy(ParsePublishStrategyError),
+    PublishConcurrency(String),
+    InvalidBoolean(&'static str, String),
+    SdkMirrorUrl(ParseSdkMirrorUrlError),
     SolutionFileInvalidExtension(PathBuf),
     SolutionFileContainsPath(PathBuf),
+    EfMigrationsConnectionEnvVar(String),
 }
 
 impl DotnetBuildpackConfiguration {
@@ -26,29 +56,16 @@ impl DotnetBuildpackConfiguration {
         project_toml_config: Option<&DotnetConfig>,
     ) -> Result<Self, DotnetBuildpackConfigurationError> {
         let msbuild_config = project_toml_config.and_then(|config| config.msbuild.as_ref());
+        let runtime_config = project_toml_config.and_then(|config| config.runtime.as_ref());
+        let sdk_config = project_toml_config.and_then(|config| config.sdk.as_ref());
 
-        let solution_file = env
-            .get_string_lossy("SOLUTION_FILE")
-            .map(PathBuf::from)
-            .or_else(|| project_toml_config.and_then(|config| config.solution_file.clone()));
-
-        if let Some(path) = solution_file.as_ref() {
-            if path.parent().is_some_and(|p| p != Path::new("")) {
-                Err(DotnetBuildpackConfigurationError::SolutionFileContainsPath(
-                    path.clone(),
-                ))?;
-            }
-
-            let extension = path.extension().and_then(|ext| ext.to_str());
-            if !extension.is_some_and(|ext| SOLUTION_EXTENSIONS.contains(&ext)) {
-                Err(DotnetBuildpackConfigurationError::SolutionFileInvalidExtension(path.clone()))?;
-            }
-        }
+        let solution_file = configured_solution_file(env, project_toml_config)?;
 
         Ok(Self {
             build_configuration: env
                 .get_string_lossy("BUILD_CONFIGURATION")
                 .or_else(|| msbuild_config?.configuration.clone()),
+            ef_migrations: configured_ef_migrations_options(env, project_toml_config)?,
             execution_environment: env
                 .get_string_lossy("CNB_EXEC_ENV")
                 .as_deref()
@@ -57,6 +74,19 @@ impl DotnetBuildpackConfiguration {
                     ExecutionEnvironment::from_str,
                 )
                 .map_err(DotnetBuildpackConfigurationError::ExecutionEnvironment)?,
+            globalization_options: configured_globalization_options(env, project_toml_config)?,
+            launch_process_mode: env
+                .get_string_lossy("LAUNCH_PROCESS_MODE")
+                .as_deref()
+                .or_else(|| project_toml_config?.launch_process_mode.as_deref())
+                .map_or(Ok(LaunchProcessMode::Exec), str::parse)
+                .map_err(DotnetBuildpackConfigurationError::LaunchProcessMode)?,
+            launch_profile: env
+                .get_string_lossy("LAUNCH_PROFILE")
+                .or_else(|| project_toml_config?.launch_profile.clone()),
+            launch_smoke_check: parse_bool_env(env, "LAUNCH_SMOKE_CHECK")?
+                .or_else(|| project_toml_config?.launch_smoke_check)
+                .unwrap_or(false),
             msbuild_verbosity_level: env
                 .get_string_lossy("MSBUILD_VERBOSITY_LEVEL")
                 .as_deref()
@@ -64,8 +94,354 @@ impl DotnetBuildpackConfiguration {
                 .map(str::parse)
                 .transpose()
                 .map_err(DotnetBuildpackConfigurationError::VerbosityLevel)?,
+            msbuild_binary_log: parse_bool_env(env, "MSBUILD_BINARY_LOG")?
+                .or_else(|| msbuild_config?.binary_log)
+                .unwrap_or(false),
+            procfile_mode: env
+                .get_string_lossy("PROCFILE_MODE")
+                .as_deref()
+                .or_else(|| project_toml_config?.procfile_mode.as_deref())
+                .map_or(Ok(ProcfileMode::Skip), str::parse)
+                .map_err(DotnetBuildpackConfigurationError::ProcfileMode)?,
+            publish_mode: env
+                .get_string_lossy("PUBLISH_MODE")
+                .as_deref()
+                .or_else(|| project_toml_config?.publish_mode.as_deref())
+                .map(str::parse)
+                .transpose()
+                .map_err(DotnetBuildpackConfigurationError::PublishMode)?,
+            publish_strategy: env
+                .get_string_lossy("PUBLISH_STRATEGY")
+                .as_deref()
+                .or_else(|| project_toml_config?.publish_strategy.as_deref())
+                .map_or(Ok(PublishStrategy::Solution), str::parse)
+                .map_err(DotnetBuildpackConfigurationError::PublishStrategy)?,
+            publish_concurrency: match env.get_string_lossy("PUBLISH_CONCURRENCY") {
+                Some(value) => value
+                    .trim()
+                    .parse()
+                    .map_err(|_| DotnetBuildpackConfigurationError::PublishConcurrency(value))?,
+                None => project_toml_config
+                    .and_then(|config| config.publish_concurrency)
+                    .unwrap_or(NonZeroUsize::MIN),
+            },
+            publish_ready_to_run: parse_bool_env(env, "PUBLISH_READY_TO_RUN")?
+                .or_else(|| project_toml_config?.publish_ready_to_run)
+                .unwrap_or(false),
+            publish_fail_on_trim_warnings: parse_bool_env(env, "PUBLISH_FAIL_ON_TRIM_WARNINGS")?
+                .or_else(|| project_toml_config?.publish_fail_on_trim_warnings)
+                .unwrap_or(false),
+            run_image_libraries: configured_run_image_libraries(env, project_toml_config),
+            runtime_options: RuntimeOptions {
+                tiered_pgo: parse_bool_env(env, "RUNTIME_TIERED_PGO")?
+                    .or_else(|| runtime_config?.tiered_pgo),
+                tiered_compilation: parse_bool_env(env, "RUNTIME_TIERED_COMPILATION")?
+                    .or_else(|| runtime_config?.tiered_compilation),
+                ready_to_run: parse_bool_env(env, "RUNTIME_READY_TO_RUN")?
+                    .or_else(|| runtime_config?.ready_to_run),
+            },
+            sdk_artifact_source: configured_sdk_artifact_source(env, sdk_config)?,
             solution_file,
+            workload_restore: configured_workload_restore(env, project_toml_config)?,
+            write_xor_execute: configured_write_xor_execute_options(env, project_toml_config)?,
+        })
+    }
+}
+
+/// Returns the configured SDK artifact source. Like other settings, the environment variables take
+/// precedence over `project.toml`, and a local artifact directory takes precedence over a mirror
+/// URL configured in the same place.
+fn configured_sdk_artifact_source(
+    env: &libcnb::Env,
+    sdk_config: Option<&SdkConfig>,
+) -> Result<SdkArtifactSource, DotnetBuildpackConfigurationError> {
+    let sources = [
+        (
+            env.get_string_lossy("SDK_ARTIFACT_DIR").map(PathBuf::from),
+            env.get_string_lossy("SDK_MIRROR_URL"),
+        ),
+        (
+            sdk_config.and_then(|config| config.artifact_dir.clone()),
+            sdk_config.and_then(|config| config.mirror_url.clone()),
+        ),
+    ];
+
+    for (artifact_dir, mirror_url) in sources {
+        if let Some(artifact_dir) = artifact_dir {
+            return Ok(SdkArtifactSource::LocalDirectory(artifact_dir));
+        }
+        if let Some(mirror_url) = mirror_url {
+            return mirror_url
+                .parse()
+                .map_err(DotnetBuildpackConfigurationError::SdkMirrorUrl);
+        }
+    }
+    Ok(SdkArtifactSource::Inventory)
+}
+
+/// Returns the configured solution file, which must be a file name with a solution extension
+fn configured_solution_file(
+    env: &libcnb::Env,
+    project_toml_config: Option<&DotnetConfig>,
+) -> Result<Option<PathBuf>, DotnetBuildpackConfigurationError> {
+    let solution_file = env
+        .get_string_lossy("SOLUTION_FILE")
+        .map(PathBuf::from)
+        .or_else(|| project_toml_config.and_then(|config| config.solution_file.clone()));
+
+    if let Some(path) = solution_file.as_ref() {
+        if path.parent().is_some_and(|p| p != Path::new("")) {
+            Err(DotnetBuildpackConfigurationError::SolutionFileContainsPath(
+                path.clone(),
+            ))?;
+        }
+
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        if !extension.is_some_and(|ext| SOLUTION_EXTENSIONS.contains(&ext)) {
+            Err(DotnetBuildpackConfigurationError::SolutionFileInvalidExtension(path.clone()))?;
+        }
+    }
+
+    Ok(solution_file)
+}
+
+fn configured_globalization_options(
+    env: &libcnb::Env,
+    project_toml_config: Option<&DotnetConfig>,
+) -> Result<GlobalizationOptions, DotnetBuildpackConfigurationError> {
+    let globalization_config = project_toml_config.and_then(|config| config.globalization.as_ref());
+
+    Ok(GlobalizationOptions {
+        invariant: parse_bool_env(env, "GLOBALIZATION_INVARIANT")?
+            .or_else(|| globalization_config?.invariant),
+        app_local_icu: env
+            .get_string_lossy("GLOBALIZATION_APP_LOCAL_ICU")
+            .or_else(|| globalization_config?.app_local_icu.clone()),
+    })
+}
+
+/// Returns the configured Entity Framework Core migrations options. The connection environment
+/// variable is referenced in the release process command, so it must be a valid variable name.
+fn configured_ef_migrations_options(
+    env: &libcnb::Env,
+    project_toml_config: Option<&DotnetConfig>,
+) -> Result<EfMigrationsOptions, DotnetBuildpackConfigurationError> {
+    let ef_migrations_config = project_toml_config.and_then(|config| config.ef_migrations.as_ref());
+
+    let connection_env_var = env
+        .get_string_lossy("EF_MIGRATIONS_CONNECTION_ENV_VAR")
+        .or_else(|| ef_migrations_config?.connection_env_var.clone());
+    if let Some(name) = &connection_env_var
+        && !is_valid_env_var_name(name)
+    {
+        Err(DotnetBuildpackConfigurationError::EfMigrationsConnectionEnvVar(name.clone()))?;
+    }
+
+    Ok(EfMigrationsOptions {
+        enabled: parse_bool_env(env, "EF_MIGRATIONS_ENABLED")?
+            .or_else(|| ef_migrations_config?.enabled)
+            .unwrap_or_default(),
+        project: env
+            .get_string_lossy("EF_MIGRATIONS_PROJECT")
+            .or_else(|| ef_migrations_config?.project.clone()),
+        context: env
+            .get_string_lossy("EF_MIGRATIONS_CONTEXT")
+            .or_else(|| ef_migrations_config?.context.clone()),
+        connection_env_var,
+    })
+}
+
+fn is_valid_env_var_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn configured_workload_restore(
+    env: &libcnb::Env,
+    project_toml_config: Option<&DotnetConfig>,
+) -> Result<Option<bool>, DotnetBuildpackConfigurationError> {
+    Ok(parse_bool_env(env, "WORKLOAD_RESTORE")?.or_else(|| project_toml_config?.workload_restore))
+}
+
+/// Returns the configured W^X options: the SDK setting applies during the build, and the runtime
+/// setting at launch.
+fn configured_write_xor_execute_options(
+    env: &libcnb::Env,
+    project_toml_config: Option<&DotnetConfig>,
+) -> Result<WriteXorExecuteOptions, DotnetBuildpackConfigurationError> {
+    Ok(WriteXorExecuteOptions {
+        build: parse_bool_env(env, "SDK_WRITE_XOR_EXECUTE")?
+            .or_else(|| project_toml_config?.sdk.as_ref()?.write_xor_execute),
+        launch: parse_bool_env(env, "RUNTIME_WRITE_XOR_EXECUTE")?
+            .or_else(|| project_toml_config?.runtime.as_ref()?.write_xor_execute),
+    })
+}
+
+/// Returns the configured run image libraries. The environment variable is a list of library
+/// names separated by commas, colons or whitespace.
+fn configured_run_image_libraries(
+    env: &libcnb::Env,
+    project_toml_config: Option<&DotnetConfig>,
+) -> Vec<String> {
+    env.get_string_lossy("RUN_IMAGE_LIBRARIES").map_or_else(
+        || {
+            project_toml_config
+                .and_then(|config| config.run_image_libraries.clone())
+                .unwrap_or_default()
+        },
+        |value| {
+            value
+                .split([',', ':'])
+                .flat_map(str::split_whitespace)
+                .map(str::to_string)
+                .collect()
+        },
+    )
+}
+
+fn parse_bool_env(
+    env: &libcnb::Env,
+    key: &'static str,
+) -> Result<Option<bool>, DotnetBuildpackConfigurationError> {
+    env.get_string_lossy(key)
+        .map(|value| match value.to_lowercase().as_str() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(DotnetBuildpackConfigurationError::InvalidBoolean(
+                key, value,
+            )),
         })
+        .transpose()
+}
+
+/// .NET runtime settings that affect startup performance, which are set as environment variables
+/// at launch. Unset options use the .NET runtime defaults.
+/// See <https://learn.microsoft.com/en-us/dotnet/core/runtime-config/compilation>
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct RuntimeOptions {
+    pub(crate) tiered_pgo: Option<bool>,
+    pub(crate) tiered_compilation: Option<bool>,
+    pub(crate) ready_to_run: Option<bool>,
+}
+
+impl RuntimeOptions {
+    /// Returns the environment variables for the configured options
+    pub(crate) fn environment_variables(&self) -> Vec<(&'static str, &'static str)> {
+        [
+            ("DOTNET_TieredPGO", self.tiered_pgo),
+            ("DOTNET_TieredCompilation", self.tiered_compilation),
+            ("DOTNET_ReadyToRun", self.ready_to_run),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|enabled| (name, if enabled { "1" } else { "0" })))
+        .collect()
+    }
+}
+
+/// .NET globalization settings, which are set as environment variables at launch. Unset options
+/// use the app's own settings (from its `*.runtimeconfig.json` file).
+/// See <https://learn.microsoft.com/en-us/dotnet/core/runtime-config/globalization>
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct GlobalizationOptions {
+    /// Whether to run apps in globalization-invariant mode, which doesn't need ICU
+    pub(crate) invariant: Option<bool>,
+    /// The version of the ICU libraries bundled with apps (e.g. `72.1.0.3`), which are loaded
+    /// instead of the run image's ICU libraries
+    pub(crate) app_local_icu: Option<String>,
+}
+
+impl GlobalizationOptions {
+    /// Returns the environment variables for the configured options
+    pub(crate) fn environment_variables(&self) -> Vec<(&'static str, String)> {
+        self.invariant
+            .map(|invariant| {
+                (
+                    "DOTNET_SYSTEM_GLOBALIZATION_INVARIANT",
+                    invariant.to_string(),
+                )
+            })
+            .into_iter()
+            .chain(
+                self.app_local_icu
+                    .clone()
+                    .map(|version| ("DOTNET_SYSTEM_GLOBALIZATION_APPLOCALICU", version)),
+            )
+            .collect()
+    }
+}
+
+/// Settings for the Entity Framework Core migrations bundle, which is run by the `release` process.
+/// Unset project and context options are detected from the app (or use the `dotnet ef` defaults).
+/// See <https://learn.microsoft.com/en-us/ef/core/managing-schemas/migrations/applying#bundles>
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct EfMigrationsOptions {
+    /// Whether to create the bundle and register the `release` process, which applies migrations
+    /// to the database on every release. Disabled by default.
+    pub(crate) enabled: bool,
+    /// The name of the project containing the migrations (its file name, with or without the
+    /// extension)
+    pub(crate) project: Option<String>,
+    /// The name of the `DbContext` class to run the migrations of, required when the project has
+    /// more than one
+    pub(crate) context: Option<String>,
+    /// The environment variable containing the database connection string at release time.
+    /// Without it, the bundle uses the connection string configured in the app.
+    pub(crate) connection_env_var: Option<String>,
+}
+
+/// Whether the .NET runtime's W^X (write-xor-execute) hardening is enabled during the build (for
+/// the SDK) and at launch, set with the `DOTNET_EnableWriteXorExecute` environment variable.
+/// Unset options use the .NET runtime default (enabled).
+/// See <https://learn.microsoft.com/en-us/dotnet/core/runtime-config/debugging-profiling#write-xor-execute>
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct WriteXorExecuteOptions {
+    pub(crate) build: Option<bool>,
+    pub(crate) launch: Option<bool>,
+}
+
+impl WriteXorExecuteOptions {
+    /// Returns the options to apply, disabling W^X during the build (when unset) if the buildpack
+    /// runs under emulation (e.g. an amd64 builder image on an Apple Silicon Mac), where W^X causes
+    /// .NET processes to crash. The launch option is left unchanged, as the host the image runs on
+    /// is only known at launch (see the `configure_write_xor_execute` exec.d program).
+    pub(crate) fn with_emulation_defaults(self, emulated: bool) -> Self {
+        Self {
+            build: self.build.or(emulated.then_some(false)),
+            launch: self.launch,
+        }
+    }
+}
+
+/// Where the .NET SDK archive is fetched from. Archives are always verified against the checksum
+/// listed in the inventory, regardless of their source.
+#[derive(Debug, PartialEq)]
+pub(crate) enum SdkArtifactSource {
+    /// Download the archive from the URL listed in the inventory.
+    Inventory,
+    /// Download the archive from a mirror, keeping the path of the inventory URL.
+    Mirror(Url),
+    /// Read the archive from a local directory, using the file name of the inventory URL.
+    LocalDirectory(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParseSdkMirrorUrlError(pub(crate) String);
+
+impl FromStr for SdkArtifactSource {
+    type Err = ParseSdkMirrorUrlError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let url = Url::parse(value).map_err(|_| ParseSdkMirrorUrlError(value.to_string()))?;
+        match url.scheme() {
+            "http" | "https" if url.has_host() => Ok(SdkArtifactSource::Mirror(url)),
+            "file" => url
+                .to_file_path()
+                .map(SdkArtifactSource::LocalDirectory)
+                .map_err(|()| ParseSdkMirrorUrlError(value.to_string())),
+            _ => Err(ParseSdkMirrorUrlError(value.to_string())),
+        }
     }
 }
 
@@ -133,10 +509,155 @@ impl fmt::Display for VerbosityLevel {
     }
 }
 
+/// How apps are published, which determines whether the .NET runtime is installed in a separate
+/// layer or bundled with the published app.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PublishMode {
+    /// The app is published without the runtime, and runs on the shared frameworks installed
+    /// by the buildpack.
+    FrameworkDependent,
+    /// The app is published with the runtime and all its dependencies in the publish directory.
+    SelfContained,
+    /// The app is published with the runtime as a single executable.
+    SingleFile,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParsePublishModeError(pub(crate) String);
+
+impl FromStr for PublishMode {
+    type Err = ParsePublishModeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "framework-dependent" => Ok(PublishMode::FrameworkDependent),
+            "self-contained" => Ok(PublishMode::SelfContained),
+            "single-file" => Ok(PublishMode::SingleFile),
+            _ => Err(ParsePublishModeError(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for PublishMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublishMode::FrameworkDependent => write!(f, "framework-dependent"),
+            PublishMode::SelfContained => write!(f, "self-contained"),
+            PublishMode::SingleFile => write!(f, "single-file"),
+        }
+    }
+}
+
+/// How launch processes start the published app.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum LaunchProcessMode {
+    /// The app is executed directly from its publish directory, without a shell.
+    Exec,
+    /// The app is started with a `bash -c` command, as in earlier versions of the buildpack.
+    Shell,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParseLaunchProcessModeError(pub(crate) String);
+
+impl FromStr for LaunchProcessMode {
+    type Err = ParseLaunchProcessModeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "exec" => Ok(LaunchProcessMode::Exec),
+            "shell" => Ok(LaunchProcessMode::Shell),
+            _ => Err(ParseLaunchProcessModeError(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for LaunchProcessMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchProcessMode::Exec => write!(f, "exec"),
+            LaunchProcessMode::Shell => write!(f, "shell"),
+        }
+    }
+}
+
+/// How detected processes are registered when the app has a `Procfile`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ProcfileMode {
+    /// Detected processes are registered, unless the Procfile defines a process of the same type.
+    Merge,
+    /// No detected processes are registered.
+    Skip,
+    /// All detected processes are registered, as if there was no Procfile.
+    Override,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParseProcfileModeError(pub(crate) String);
+
+impl FromStr for ProcfileMode {
+    type Err = ParseProcfileModeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "merge" => Ok(ProcfileMode::Merge),
+            "skip" => Ok(ProcfileMode::Skip),
+            "override" => Ok(ProcfileMode::Override),
+            _ => Err(ParseProcfileModeError(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ProcfileMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcfileMode::Merge => write!(f, "merge"),
+            ProcfileMode::Skip => write!(f, "skip"),
+            ProcfileMode::Override => write!(f, "override"),
+        }
+    }
+}
+
+/// What `dotnet publish` is run for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PublishStrategy {
+    /// The solution (or project) is published with a single command.
+    Solution,
+    /// Each launchable project is published with its own command, skipping test projects and
+    /// libraries that aren't referenced by a launchable project.
+    Projects,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParsePublishStrategyError(pub(crate) String);
+
+impl FromStr for PublishStrategy {
+    type Err = ParsePublishStrategyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "solution" => Ok(PublishStrategy::Solution),
+            "projects" => Ok(PublishStrategy::Projects),
+            _ => Err(ParsePublishStrategyError(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for PublishStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublishStrategy::Solution => write!(f, "solution"),
+            PublishStrategy::Projects => write!(f, "projects"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::project_toml::MsbuildConfig;
+    use crate::project_toml::{
+        EfMigrationsConfig, GlobalizationConfig, MsbuildConfig, RuntimeOptionsConfig, SdkConfig,
+    };
     use libcnb::Env;
     use libcnb_test::assert_matches;
 
@@ -158,9 +679,26 @@ mod tests {
             result,
             DotnetBuildpackConfiguration {
                 build_configuration: None,
+                ef_migrations: EfMigrationsOptions::default(),
                 execution_environment: ExecutionEnvironment::Production,
+                globalization_options: GlobalizationOptions::default(),
+                launch_process_mode: LaunchProcessMode::Exec,
+                launch_profile: None,
+                launch_smoke_check: false,
                 msbuild_verbosity_level: None,
-                solution_file: None
+                msbuild_binary_log: false,
+                procfile_mode: ProcfileMode::Skip,
+                publish_mode: None,
+                publish_strategy: PublishStrategy::Solution,
+                publish_concurrency: NonZeroUsize::MIN,
+                publish_ready_to_run: false,
+                publish_fail_on_trim_warnings: false,
+                run_image_libraries: vec![],
+                runtime_options: RuntimeOptions::default(),
+                sdk_artifact_source: SdkArtifactSource::Inventory,
+                solution_file: None,
+                workload_restore: None,
+                write_xor_execute: WriteXorExecuteOptions::default(),
             }
         );
     }
@@ -168,11 +706,44 @@ mod tests {
     #[test]
     fn test_project_toml_overrides_default_config() {
         let project_toml_config = DotnetConfig {
+            ef_migrations: Some(EfMigrationsConfig {
+                enabled: Some(true),
+                project: Some("Data".to_string()),
+                context: Some("BloggingContext".to_string()),
+                connection_env_var: Some("DATABASE_CONNECTION_STRING".to_string()),
+            }),
+            globalization: Some(GlobalizationConfig {
+                invariant: Some(true),
+                app_local_icu: None,
+            }),
+            launch_process_mode: Some("shell".to_string()),
+            launch_profile: Some("toml-profile".to_string()),
+            launch_smoke_check: Some(true),
             msbuild: Some(MsbuildConfig {
                 configuration: Some("Debug".to_string()),
                 verbosity: Some("Detailed".to_string()),
+                binary_log: Some(true),
+            }),
+            procfile_mode: Some("merge".to_string()),
+            publish_mode: Some("self-contained".to_string()),
+            publish_strategy: Some("projects".to_string()),
+            publish_concurrency: NonZeroUsize::new(4),
+            publish_ready_to_run: Some(true),
+            publish_fail_on_trim_warnings: Some(true),
+            run_image_libraries: Some(vec!["libgdiplus.so.0".to_string()]),
+            runtime: Some(RuntimeOptionsConfig {
+                tiered_pgo: Some(false),
+                tiered_compilation: None,
+                ready_to_run: Some(true),
+                write_xor_execute: Some(true),
+            }),
+            sdk: Some(SdkConfig {
+                mirror_url: None,
+                artifact_dir: None,
+                write_xor_execute: Some(false),
             }),
             solution_file: Some(PathBuf::from("foo.sln")),
+            workload_restore: Some(true),
         };
         let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(
             &create_env(&[]),
@@ -182,6 +753,49 @@ mod tests {
 
         assert_eq!(result.solution_file, Some(PathBuf::from("foo.sln")));
         assert_eq!(result.build_configuration, Some("Debug".to_string()));
+        assert_eq!(
+            result.ef_migrations,
+            EfMigrationsOptions {
+                enabled: true,
+                project: Some("Data".to_string()),
+                context: Some("BloggingContext".to_string()),
+                connection_env_var: Some("DATABASE_CONNECTION_STRING".to_string()),
+            }
+        );
+        assert_eq!(
+            result.globalization_options,
+            GlobalizationOptions {
+                invariant: Some(true),
+                app_local_icu: None,
+            }
+        );
+        assert_eq!(result.launch_process_mode, LaunchProcessMode::Shell);
+        assert_eq!(result.launch_profile, Some("toml-profile".to_string()));
+        assert!(result.launch_smoke_check);
+        assert_eq!(result.procfile_mode, ProcfileMode::Merge);
+        assert_eq!(result.publish_mode, Some(PublishMode::SelfContained));
+        assert_eq!(result.publish_strategy, PublishStrategy::Projects);
+        assert_eq!(result.publish_concurrency, NonZeroUsize::new(4).unwrap());
+        assert!(result.msbuild_binary_log);
+        assert!(result.publish_ready_to_run);
+        assert!(result.publish_fail_on_trim_warnings);
+        assert_eq!(result.run_image_libraries, vec!["libgdiplus.so.0"]);
+        assert_eq!(result.workload_restore, Some(true));
+        assert_eq!(
+            result.write_xor_execute,
+            WriteXorExecuteOptions {
+                build: Some(false),
+                launch: Some(true),
+            }
+        );
+        assert_eq!(
+            result.runtime_options,
+            RuntimeOptions {
+                tiered_pgo: Some(false),
+                tiered_compilation: None,
+                ready_to_run: Some(true),
+            }
+        );
         assert_eq!(
             result.msbuild_verbosity_level,
             Some(VerbosityLevel::Detailed)
@@ -193,14 +807,58 @@ mod tests {
         let env = create_env(&[
             ("BUILD_CONFIGURATION", "Release"),
             ("MSBUILD_VERBOSITY_LEVEL", "Detailed"),
+            ("GLOBALIZATION_INVARIANT", "false"),
+            ("GLOBALIZATION_APP_LOCAL_ICU", "72.1.0.3"),
+            ("LAUNCH_PROCESS_MODE", "Exec"),
+            ("LAUNCH_PROFILE", "env-profile"),
+            ("LAUNCH_SMOKE_CHECK", "false"),
+            ("PROCFILE_MODE", "Skip"),
+            ("PUBLISH_MODE", "single-file"),
+            ("PUBLISH_STRATEGY", "Solution"),
+            ("PUBLISH_CONCURRENCY", "2"),
+            ("MSBUILD_BINARY_LOG", "false"),
+            ("PUBLISH_FAIL_ON_TRIM_WARNINGS", "FALSE"),
+            ("PUBLISH_READY_TO_RUN", "false"),
+            ("RUNTIME_TIERED_PGO", "0"),
+            ("RUNTIME_WRITE_XOR_EXECUTE", "true"),
+            ("SDK_WRITE_XOR_EXECUTE", "false"),
+            ("WORKLOAD_RESTORE", "false"),
+            (
+                "RUN_IMAGE_LIBRARIES",
+                "libfoo.so.1, libbar.so.2:libbaz.so.3",
+            ),
             ("SOLUTION_FILE", "env-solution.sln"),
         ]);
         let project_toml_config = DotnetConfig {
+            ef_migrations: None,
+            globalization: Some(GlobalizationConfig {
+                invariant: Some(true),
+                app_local_icu: Some("68.2.0.9".to_string()),
+            }),
+            launch_process_mode: Some("shell".to_string()),
+            launch_profile: Some("toml-profile".to_string()),
+            launch_smoke_check: Some(true),
             msbuild: Some(MsbuildConfig {
                 configuration: Some("Debug".to_string()),
                 verbosity: Some("Quiet".to_string()),
+                binary_log: Some(true),
+            }),
+            procfile_mode: Some("merge".to_string()),
+            publish_mode: Some("framework-dependent".to_string()),
+            publish_strategy: Some("projects".to_string()),
+            publish_concurrency: NonZeroUsize::new(4),
+            publish_ready_to_run: Some(true),
+            publish_fail_on_trim_warnings: Some(true),
+            run_image_libraries: Some(vec!["libgdiplus.so.0".to_string()]),
+            runtime: Some(RuntimeOptionsConfig {
+                tiered_pgo: Some(true),
+                tiered_compilation: None,
+                ready_to_run: None,
+                write_xor_execute: Some(false),
             }),
+            sdk: None,
             solution_file: Some(PathBuf::from("toml-solution.sln")),
+            workload_restore: Some(true),
         };
         let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(
             &env,
@@ -209,16 +867,94 @@ mod tests {
         .unwrap();
 
         assert_eq!(result.build_configuration, Some("Release".to_string()));
+        assert_eq!(
+            result.globalization_options,
+            GlobalizationOptions {
+                invariant: Some(false),
+                app_local_icu: Some("72.1.0.3".to_string()),
+            }
+        );
+        assert_eq!(result.launch_process_mode, LaunchProcessMode::Exec);
+        assert_eq!(result.launch_profile, Some("env-profile".to_string()));
+        assert!(!result.launch_smoke_check);
         assert_eq!(
             result.msbuild_verbosity_level,
             Some(VerbosityLevel::Detailed)
         );
+        assert_eq!(result.procfile_mode, ProcfileMode::Skip);
+        assert_eq!(result.publish_mode, Some(PublishMode::SingleFile));
+        assert_eq!(result.publish_strategy, PublishStrategy::Solution);
+        assert_eq!(result.publish_concurrency, NonZeroUsize::new(2).unwrap());
+        assert!(!result.msbuild_binary_log);
+        assert!(!result.publish_ready_to_run);
+        assert!(!result.publish_fail_on_trim_warnings);
+        assert_eq!(result.runtime_options.tiered_pgo, Some(false));
+        assert_eq!(result.workload_restore, Some(false));
+        assert_eq!(
+            result.write_xor_execute,
+            WriteXorExecuteOptions {
+                build: Some(false),
+                launch: Some(true),
+            }
+        );
+        assert_eq!(
+            result.run_image_libraries,
+            vec!["libfoo.so.1", "libbar.so.2", "libbaz.so.3"]
+        );
         assert_eq!(
             result.solution_file,
             Some(PathBuf::from("env-solution.sln"))
         );
     }
 
+    #[test]
+    fn test_ef_migrations_env_overrides_project_toml() {
+        let env = create_env(&[
+            ("EF_MIGRATIONS_ENABLED", "false"),
+            ("EF_MIGRATIONS_PROJECT", "Infrastructure.csproj"),
+            ("EF_MIGRATIONS_CONNECTION_ENV_VAR", "DB_CONNECTION"),
+        ]);
+        let project_toml_config = DotnetConfig {
+            ef_migrations: Some(EfMigrationsConfig {
+                enabled: Some(true),
+                project: Some("Data".to_string()),
+                context: Some("BloggingContext".to_string()),
+                connection_env_var: Some("DATABASE_CONNECTION_STRING".to_string()),
+            }),
+            globalization: None,
+            launch_process_mode: None,
+            launch_profile: None,
+            launch_smoke_check: None,
+            msbuild: None,
+            procfile_mode: None,
+            publish_mode: None,
+            publish_strategy: None,
+            publish_concurrency: None,
+            publish_ready_to_run: None,
+            publish_fail_on_trim_warnings: None,
+            run_image_libraries: None,
+            runtime: None,
+            sdk: None,
+            solution_file: None,
+            workload_restore: None,
+        };
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(
+            &env,
+            Some(&project_toml_config),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result.ef_migrations,
+            EfMigrationsOptions {
+                enabled: false,
+                project: Some("Infrastructure.csproj".to_string()),
+                context: Some("BloggingContext".to_string()),
+                connection_env_var: Some("DB_CONNECTION".to_string()),
+            }
+        );
+    }
+
     #[test]
     fn test_env_vars_override_default_config() {
         let env = create_env(&[
@@ -307,6 +1043,357 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_publish_mode() {
+        assert_eq!(
+            "framework-dependent".parse(),
+            Ok(PublishMode::FrameworkDependent)
+        );
+        assert_eq!("Self-Contained".parse(), Ok(PublishMode::SelfContained));
+        assert_eq!("single-file".parse(), Ok(PublishMode::SingleFile));
+        assert_eq!(
+            "native".parse::<PublishMode>(),
+            Err(ParsePublishModeError("native".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_publish_mode_error() {
+        let env = create_env(&[("PUBLISH_MODE", "invalid")]);
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(&env, None);
+
+        assert_eq!(
+            result,
+            Err(DotnetBuildpackConfigurationError::PublishMode(
+                ParsePublishModeError("invalid".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_launch_process_mode() {
+        assert_eq!("exec".parse(), Ok(LaunchProcessMode::Exec));
+        assert_eq!("Shell".parse(), Ok(LaunchProcessMode::Shell));
+        assert_eq!(
+            "bash".parse::<LaunchProcessMode>(),
+            Err(ParseLaunchProcessModeError("bash".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_launch_process_mode_error() {
+        let env = create_env(&[("LAUNCH_PROCESS_MODE", "invalid")]);
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(&env, None);
+
+        assert_eq!(
+            result,
+            Err(DotnetBuildpackConfigurationError::LaunchProcessMode(
+                ParseLaunchProcessModeError("invalid".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_ef_migrations_connection_env_var_error() {
+        for name in ["DATABASE-URL", "1DATABASE", "$(rm -rf /)", ""] {
+            let env = create_env(&[("EF_MIGRATIONS_CONNECTION_ENV_VAR", name)]);
+            let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(&env, None);
+
+            assert_eq!(
+                result,
+                Err(
+                    DotnetBuildpackConfigurationError::EfMigrationsConnectionEnvVar(
+                        name.to_string()
+                    )
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_procfile_mode() {
+        assert_eq!("merge".parse(), Ok(ProcfileMode::Merge));
+        assert_eq!("Skip".parse(), Ok(ProcfileMode::Skip));
+        assert_eq!("OVERRIDE".parse(), Ok(ProcfileMode::Override));
+        assert_eq!(
+            "replace".parse::<ProcfileMode>(),
+            Err(ParseProcfileModeError("replace".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_procfile_mode_error() {
+        let env = create_env(&[("PROCFILE_MODE", "invalid")]);
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(&env, None);
+
+        assert_eq!(
+            result,
+            Err(DotnetBuildpackConfigurationError::ProcfileMode(
+                ParseProcfileModeError("invalid".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_publish_strategy() {
+        assert_eq!("solution".parse(), Ok(PublishStrategy::Solution));
+        assert_eq!("Projects".parse(), Ok(PublishStrategy::Projects));
+        assert_eq!(
+            "parallel".parse::<PublishStrategy>(),
+            Err(ParsePublishStrategyError("parallel".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_publish_strategy_error() {
+        let env = create_env(&[("PUBLISH_STRATEGY", "invalid")]);
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(&env, None);
+
+        assert_eq!(
+            result,
+            Err(DotnetBuildpackConfigurationError::PublishStrategy(
+                ParsePublishStrategyError("invalid".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_publish_concurrency_error() {
+        for value in ["0", "-1", "many"] {
+            let env = create_env(&[("PUBLISH_CONCURRENCY", value)]);
+            let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(&env, None);
+
+            assert_eq!(
+                result,
+                Err(DotnetBuildpackConfigurationError::PublishConcurrency(
+                    value.to_string()
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_boolean_error() {
+        let env = create_env(&[("RUNTIME_TIERED_PGO", "yes")]);
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(&env, None);
+
+        assert_eq!(
+            result,
+            Err(DotnetBuildpackConfigurationError::InvalidBoolean(
+                "RUNTIME_TIERED_PGO",
+                "yes".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_runtime_options_environment_variables() {
+        assert!(RuntimeOptions::default().environment_variables().is_empty());
+        assert_eq!(
+            RuntimeOptions {
+                tiered_pgo: Some(true),
+                tiered_compilation: Some(false),
+                ready_to_run: None,
+            }
+            .environment_variables(),
+            vec![("DOTNET_TieredPGO", "1"), ("DOTNET_TieredCompilation", "0")]
+        );
+    }
+
+    #[test]
+    fn test_globalization_options_environment_variables() {
+        assert!(
+            GlobalizationOptions::default()
+                .environment_variables()
+                .is_empty()
+        );
+        assert_eq!(
+            GlobalizationOptions {
+                invariant: Some(false),
+                app_local_icu: Some("72.1.0.3".to_string()),
+            }
+            .environment_variables(),
+            vec![
+                ("DOTNET_SYSTEM_GLOBALIZATION_INVARIANT", "false".to_string()),
+                (
+                    "DOTNET_SYSTEM_GLOBALIZATION_APPLOCALICU",
+                    "72.1.0.3".to_string()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_xor_execute_options_with_emulation_defaults() {
+        assert_eq!(
+            WriteXorExecuteOptions::default().with_emulation_defaults(false),
+            WriteXorExecuteOptions::default()
+        );
+        assert_eq!(
+            WriteXorExecuteOptions::default().with_emulation_defaults(true),
+            WriteXorExecuteOptions {
+                build: Some(false),
+                launch: None,
+            }
+        );
+        assert_eq!(
+            WriteXorExecuteOptions {
+                build: None,
+                launch: Some(true),
+            }
+            .with_emulation_defaults(true),
+            WriteXorExecuteOptions {
+                build: Some(false),
+                launch: Some(true),
+            }
+        );
+    }
+
+    #[test]
+    fn test_sdk_artifact_source_from_env() {
+        let cases = [
+            (
+                vec![("SDK_MIRROR_URL", "https://artifactory.example.com/dotnet")],
+                SdkArtifactSource::Mirror(
+                    Url::parse("https://artifactory.example.com/dotnet").unwrap(),
+                ),
+            ),
+            (
+                vec![("SDK_MIRROR_URL", "file:///mnt/dotnet-sdks")],
+                SdkArtifactSource::LocalDirectory(PathBuf::from("/mnt/dotnet-sdks")),
+            ),
+            (
+                vec![("SDK_ARTIFACT_DIR", "vendor/dotnet")],
+                SdkArtifactSource::LocalDirectory(PathBuf::from("vendor/dotnet")),
+            ),
+            (
+                vec![
+                    ("SDK_ARTIFACT_DIR", "vendor/dotnet"),
+                    ("SDK_MIRROR_URL", "https://artifactory.example.com/dotnet"),
+                ],
+                SdkArtifactSource::LocalDirectory(PathBuf::from("vendor/dotnet")),
+            ),
+        ];
+
+        for (variables, expected) in cases {
+            let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(
+                &create_env(&variables),
+                None,
+            )
+            .unwrap();
+            assert_eq!(result.sdk_artifact_source, expected);
+        }
+    }
+
+    #[test]
+    fn test_sdk_artifact_source_env_overrides_project_toml() {
+        let project_toml_config = DotnetConfig {
+            ef_migrations: None,
+            globalization: None,
+            launch_process_mode: None,
+            launch_profile: None,
+            launch_smoke_check: None,
+            msbuild: None,
+            procfile_mode: None,
+            publish_mode: None,
+            publish_strategy: None,
+            publish_concurrency: None,
+            publish_ready_to_run: None,
+            publish_fail_on_trim_warnings: None,
+            run_image_libraries: None,
+            runtime: None,
+            sdk: Some(SdkConfig {
+                mirror_url: Some("https://toml.example.com".to_string()),
+                artifact_dir: None,
+                write_xor_execute: None,
+            }),
+            solution_file: None,
+            workload_restore: None,
+        };
+
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(
+            &create_env(&[]),
+            Some(&project_toml_config),
+        )
+        .unwrap();
+        assert_eq!(
+            result.sdk_artifact_source,
+            SdkArtifactSource::Mirror(Url::parse("https://toml.example.com").unwrap())
+        );
+
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(
+            &create_env(&[("SDK_MIRROR_URL", "https://env.example.com")]),
+            Some(&project_toml_config),
+        )
+        .unwrap();
+        assert_eq!(
+            result.sdk_artifact_source,
+            SdkArtifactSource::Mirror(Url::parse("https://env.example.com").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_sdk_mirror_url_env_overrides_project_toml_artifact_dir() {
+        let project_toml_config = DotnetConfig {
+            ef_migrations: None,
+            globalization: None,
+            launch_process_mode: None,
+            launch_profile: None,
+            launch_smoke_check: None,
+            msbuild: None,
+            procfile_mode: None,
+            publish_mode: None,
+            publish_strategy: None,
+            publish_concurrency: None,
+            publish_ready_to_run: None,
+            publish_fail_on_trim_warnings: None,
+            run_image_libraries: None,
+            runtime: None,
+            sdk: Some(SdkConfig {
+                mirror_url: None,
+                artifact_dir: Some(PathBuf::from("vendor/dotnet")),
+                write_xor_execute: None,
+            }),
+            solution_file: None,
+            workload_restore: None,
+        };
+
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(
+            &create_env(&[]),
+            Some(&project_toml_config),
+        )
+        .unwrap();
+        assert_eq!(
+            result.sdk_artifact_source,
+            SdkArtifactSource::LocalDirectory(PathBuf::from("vendor/dotnet"))
+        );
+
+        let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(
+            &create_env(&[("SDK_MIRROR_URL", "https://env.example.com")]),
+            Some(&project_toml_config),
+        )
+        .unwrap();
+        assert_eq!(
+            result.sdk_artifact_source,
+            SdkArtifactSource::Mirror(Url::parse("https://env.example.com").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_invalid_sdk_mirror_url() {
+        for value in ["artifactory.example.com", "ftp://example.com", "https://"] {
+            let env = create_env(&[("SDK_MIRROR_URL", value)]);
+            let result = DotnetBuildpackConfiguration::try_from_env_and_project_toml(&env, None);
+
+            assert_eq!(
+                result,
+                Err(DotnetBuildpackConfigurationError::SdkMirrorUrl(
+                    ParseSdkMirrorUrlError(value.to_string())
+                ))
+            );
+        }
+    }
+
     #[test]
     fn test_invalid_solution_file_extension() {
         let env = create_env(&[("SOLUTION_FILE", "MyApp.txt")]);
@@ -1,9 +1,19 @@
+use crate::dotnet_buildpack_configuration::{
+    GlobalizationOptions, RuntimeOptions, WriteXorExecuteOptions,
+};
+use libcnb::data::launch::ProcessType;
 use libcnb::layer_env::{LayerEnv, ModificationBehavior, Scope};
 use std::path::Path;
 
 /// Helper function to generate a base `LayerEnv` for .NET layers that include the .NET runtime (e.g. SDK and runtime layers).
-pub(crate) fn generate_layer_env(layer_path: &Path, scope: &Scope) -> LayerEnv {
-    LayerEnv::new()
+/// The configured globalization options are only set at launch, so they don't affect the SDK during the build.
+pub(crate) fn generate_layer_env(
+    layer_path: &Path,
+    scope: &Scope,
+    globalization_options: &GlobalizationOptions,
+    write_xor_execute: WriteXorExecuteOptions,
+) -> LayerEnv {
+    let layer_env = LayerEnv::new()
         .chainable_insert(scope.clone(), ModificationBehavior::Delimiter, "PATH", ":")
         .chainable_insert(
             scope.clone(),
@@ -18,17 +28,6 @@ pub(crate) fn generate_layer_env(layer_path: &Path, scope: &Scope) -> LayerEnv {
             "DOTNET_CLI_TELEMETRY_OPTOUT",
             "true",
         )
-        // Using the buildpack on ARM64 Macs causes failures due to an incompatibility executing on emulated amd64 Docker images (such as builder/heroku:24).
-        // This feature is disabled when executing dotnet directly on Apple Silicon (see <https://github.com/dotnet/runtime/pull/70912>).
-        // The feature was opt-in for .NET 6.0, but enabled by default in later versions <https://devblogs.microsoft.com/dotnet/announcing-net-6-preview-7/#runtime-wx-write-xor-execute-support-for-all-platforms-and-architectures>.
-        // This environment variable disables W^X support.
-        // TODO: Investigate performance implications on platforms where this feature is supported.
-        .chainable_insert(
-            scope.clone(),
-            ModificationBehavior::Override,
-            "DOTNET_EnableWriteXorExecute",
-            "0",
-        )
         // Mute .NET welcome and telemetry messages: https://learn.microsoft.com/en-us/dotnet/core/tools/dotnet-environment-variables#dotnet_nologo
         .chainable_insert(
             scope.clone(),
@@ -51,24 +50,89 @@ pub(crate) fn generate_layer_env(layer_path: &Path, scope: &Scope) -> LayerEnv {
             ModificationBehavior::Override,
             "DOTNET_RUNNING_IN_CONTAINER",
             "true",
-        )
+        );
+
+    // W^X is enabled by default since .NET 7: https://devblogs.microsoft.com/dotnet/announcing-net-6-preview-7/#runtime-wx-write-xor-execute-support-for-all-platforms-and-architectures
+    // It causes failures on emulated amd64 Docker images (such as `heroku/builder:24` on ARM64 Macs), so it's
+    // disabled when emulation is detected: during the build by `WriteXorExecuteOptions::with_emulation_defaults`,
+    // and at launch by the `configure_write_xor_execute` exec.d program.
+    let scoped_write_xor_execute = match scope {
+        Scope::All => vec![
+            (Scope::Build, write_xor_execute.build),
+            (Scope::Launch, write_xor_execute.launch),
+        ],
+        Scope::Build => vec![(Scope::Build, write_xor_execute.build)],
+        Scope::Launch | Scope::Process(_) => vec![(scope.clone(), write_xor_execute.launch)],
+    };
+    let layer_env =
+        scoped_write_xor_execute
+            .into_iter()
+            .fold(layer_env, |layer_env, (scope, enabled)| match enabled {
+                Some(enabled) => layer_env.chainable_insert(
+                    scope,
+                    ModificationBehavior::Override,
+                    "DOTNET_EnableWriteXorExecute",
+                    if enabled { "1" } else { "0" },
+                ),
+                None => layer_env,
+            });
+
+    // Configure how the runtime loads ICU: https://learn.microsoft.com/en-us/dotnet/core/runtime-config/globalization
+    globalization_options
+        .environment_variables()
+        .into_iter()
+        .fold(layer_env, |layer_env, (name, value)| {
+            layer_env.chainable_insert(Scope::Launch, ModificationBehavior::Override, name, value)
+        })
+}
+
+/// Generates a launch `LayerEnv` with the configured .NET runtime startup options.
+pub(crate) fn generate_runtime_options_env(runtime_options: &RuntimeOptions) -> LayerEnv {
+    runtime_options.environment_variables().into_iter().fold(
+        LayerEnv::new(),
+        |layer_env, (name, value)| {
+            layer_env.chainable_insert(Scope::Launch, ModificationBehavior::Override, name, value)
+        },
+    )
+}
+
+/// Generates a launch `LayerEnv` for ASP.NET Core web apps running behind the Heroku router,
+/// scoped to the given web process types.
+pub(crate) fn generate_kestrel_env(process_types: &[ProcessType]) -> LayerEnv {
+    // Apply the `X-Forwarded-For` and `X-Forwarded-Proto` headers set by the router, so apps see
+    // the client's IP address and the original request scheme: https://learn.microsoft.com/en-us/aspnet/core/host-and-deploy/proxy-load-balancer#forward-the-scheme-for-linux-and-non-iis-reverse-proxies
+    process_types
+        .iter()
+        .fold(LayerEnv::new(), |layer_env, process_type| {
+            layer_env.chainable_insert(
+                Scope::Process(process_type.to_string()),
+                ModificationBehavior::Default,
+                "ASPNETCORE_FORWARDEDHEADERS_ENABLED",
+                "true",
+            )
+        })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utils;
+    use libcnb::data::process_type;
 
     #[test]
     fn test_generate_dotnet_layer_env() {
         for scope in [Scope::All, Scope::Build, Scope::Launch] {
-            let layer_env = generate_layer_env(Path::new("/layers/sdk"), &scope);
+            let layer_env = generate_layer_env(
+                Path::new("/layers/sdk"),
+                &scope,
+                &GlobalizationOptions::default(),
+                WriteXorExecuteOptions::default(),
+            );
 
             assert_eq!(
                 utils::environment_as_sorted_vector(&layer_env.apply_to_empty(scope)),
                 [
                     ("DOTNET_CLI_TELEMETRY_OPTOUT", "true"),
-                    ("DOTNET_EnableWriteXorExecute", "0"),
                     ("DOTNET_NOLOGO", "true"),
                     ("DOTNET_ROOT", "/layers/sdk"),
                     ("DOTNET_RUNNING_IN_CONTAINER", "true"),
@@ -77,4 +141,135 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_generate_dotnet_layer_env_with_globalization_options() {
+        let layer_env = generate_layer_env(
+            Path::new("/layers/runtime"),
+            &Scope::Launch,
+            &GlobalizationOptions {
+                invariant: Some(true),
+                app_local_icu: Some("72.1.0.3".to_string()),
+            },
+            WriteXorExecuteOptions::default(),
+        );
+
+        let launch_env = layer_env.apply_to_empty(Scope::Launch);
+        assert_eq!(
+            launch_env.get("DOTNET_SYSTEM_GLOBALIZATION_INVARIANT"),
+            Some(&"true".into())
+        );
+        assert_eq!(
+            launch_env.get("DOTNET_SYSTEM_GLOBALIZATION_APPLOCALICU"),
+            Some(&"72.1.0.3".into())
+        );
+
+        let build_env = generate_layer_env(
+            Path::new("/layers/sdk"),
+            &Scope::All,
+            &GlobalizationOptions {
+                invariant: Some(true),
+                app_local_icu: None,
+            },
+            WriteXorExecuteOptions::default(),
+        )
+        .apply_to_empty(Scope::Build);
+        assert!(!build_env.contains_key("DOTNET_SYSTEM_GLOBALIZATION_INVARIANT"));
+    }
+
+    #[test]
+    fn test_generate_dotnet_layer_env_with_write_xor_execute_options() {
+        let write_xor_execute = WriteXorExecuteOptions {
+            build: Some(false),
+            launch: Some(true),
+        };
+        let layer_env = generate_layer_env(
+            Path::new("/layers/sdk"),
+            &Scope::All,
+            &GlobalizationOptions::default(),
+            write_xor_execute,
+        );
+        assert_eq!(
+            layer_env
+                .apply_to_empty(Scope::Build)
+                .get("DOTNET_EnableWriteXorExecute"),
+            Some(&"0".into())
+        );
+        assert_eq!(
+            layer_env
+                .apply_to_empty(Scope::Launch)
+                .get("DOTNET_EnableWriteXorExecute"),
+            Some(&"1".into())
+        );
+
+        let layer_env = generate_layer_env(
+            Path::new("/layers/sdk"),
+            &Scope::Build,
+            &GlobalizationOptions::default(),
+            write_xor_execute,
+        );
+        assert!(
+            !layer_env
+                .apply_to_empty(Scope::Launch)
+                .contains_key("DOTNET_EnableWriteXorExecute")
+        );
+
+        let layer_env = generate_layer_env(
+            Path::new("/layers/runtime"),
+            &Scope::Launch,
+            &GlobalizationOptions::default(),
+            WriteXorExecuteOptions {
+                build: Some(false),
+                launch: None,
+            },
+        );
+        assert!(
+            !layer_env
+                .apply_to_empty(Scope::Launch)
+                .contains_key("DOTNET_EnableWriteXorExecute")
+        );
+    }
+
+    #[test]
+    fn test_generate_runtime_options_env() {
+        let layer_env = generate_runtime_options_env(&RuntimeOptions {
+            tiered_pgo: Some(false),
+            tiered_compilation: None,
+            ready_to_run: Some(true),
+        });
+
+        assert_eq!(
+            utils::environment_as_sorted_vector(&layer_env.apply_to_empty(Scope::Launch)),
+            [("DOTNET_ReadyToRun", "1"), ("DOTNET_TieredPGO", "0")]
+        );
+        assert!(
+            !layer_env
+                .apply_to_empty(Scope::Build)
+                .contains_key("DOTNET_TieredPGO")
+        );
+    }
+
+    #[test]
+    fn test_generate_kestrel_env() {
+        let layer_env = generate_kestrel_env(&[process_type!("web"), process_type!("api")]);
+
+        for process_type in ["web", "api"] {
+            let scope = Scope::Process(process_type.to_string());
+            assert_eq!(
+                utils::environment_as_sorted_vector(&layer_env.apply_to_empty(scope.clone())),
+                [("ASPNETCORE_FORWARDEDHEADERS_ENABLED", "true")]
+            );
+            let mut env = libcnb::Env::new();
+            env.insert("ASPNETCORE_FORWARDEDHEADERS_ENABLED", "false");
+            assert_eq!(
+                utils::environment_as_sorted_vector(&layer_env.apply(scope, &env)),
+                [("ASPNETCORE_FORWARDEDHEADERS_ENABLED", "false")]
+            );
+        }
+        assert!(
+            !layer_env
+                .apply_to_empty(Scope::Process(String::from("worker")))
+                .contains_key("ASPNETCORE_FORWARDEDHEADERS_ENABLED")
+        );
+    }
 }
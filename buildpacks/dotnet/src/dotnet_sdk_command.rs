@@ -1,8 +1,7 @@
 use crate::dotnet::runtime_identifier::RuntimeIdentifier;
-use crate::dotnet_buildpack_configuration::VerbosityLevel;
+use crate::dotnet_buildpack_configuration::{PublishMode, VerbosityLevel};
 use libcnb::data::launch::{Process, ProcessBuilder};
 use libcnb::data::process_type;
-use std::env::temp_dir;
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -10,7 +9,13 @@ pub(crate) struct DotnetPublishCommand {
     pub(crate) path: PathBuf,
     pub(crate) runtime_identifier: RuntimeIdentifier,
     pub(crate) configuration: Option<String>,
+    pub(crate) publish_mode: Option<PublishMode>,
+    pub(crate) ready_to_run: bool,
     pub(crate) verbosity_level: Option<VerbosityLevel>,
+    pub(crate) binary_log_path: Option<PathBuf>,
+    /// The directory that intermediate and output files of the build are written to (instead of
+    /// each project's `obj` and `bin` directories)
+    pub(crate) artifacts_path: PathBuf,
 }
 
 impl From<DotnetPublishCommand> for Command {
@@ -23,15 +28,76 @@ impl From<DotnetPublishCommand> for Command {
             &value.runtime_identifier.to_string(),
             "-p:PublishDir=bin/publish",
             "--artifacts-path",
-            &temp_dir().join("build_artifacts").to_string_lossy(),
+            &value.artifacts_path.to_string_lossy(),
         ]);
 
         if let Some(configuration) = value.configuration {
             command.args(["--configuration", &configuration]);
         }
+        // Without a configured publish mode, each project's own `SelfContained` and
+        // `PublishSingleFile` properties are respected.
+        match value.publish_mode {
+            Some(PublishMode::FrameworkDependent) => {
+                command.args(["--self-contained", "false"]);
+            }
+            Some(PublishMode::SelfContained) => {
+                command.args(["--self-contained", "true"]);
+            }
+            Some(PublishMode::SingleFile) => {
+                command.args(["--self-contained", "true", "-p:PublishSingleFile=true"]);
+            }
+            None => {}
+        }
+        if value.ready_to_run {
+            command.arg("-p:PublishReadyToRun=true");
+        }
         if let Some(verbosity_level) = value.verbosity_level {
             command.args(["--verbosity", &verbosity_level.to_string()]);
         }
+        // The implicit restore is run by the same `MSBuild` invocation, so it's included in the log.
+        if let Some(binary_log_path) = value.binary_log_path {
+            command.arg(format!("-bl:{}", binary_log_path.to_string_lossy()));
+        }
+        command
+    }
+}
+
+pub(crate) struct DotnetEfMigrationsBundleCommand {
+    pub(crate) project_path: PathBuf,
+    pub(crate) startup_project_path: PathBuf,
+    pub(crate) output_path: PathBuf,
+    pub(crate) runtime_identifier: RuntimeIdentifier,
+    pub(crate) configuration: Option<String>,
+    pub(crate) context: Option<String>,
+}
+
+impl From<DotnetEfMigrationsBundleCommand> for Command {
+    fn from(value: DotnetEfMigrationsBundleCommand) -> Self {
+        let mut command = Command::new("dotnet");
+        // The bundle is self-contained, so it runs without the .NET runtime (or the app) at
+        // release time.
+        command.args([
+            "ef",
+            "migrations",
+            "bundle",
+            "--project",
+            &value.project_path.to_string_lossy(),
+            "--startup-project",
+            &value.startup_project_path.to_string_lossy(),
+            "--output",
+            &value.output_path.to_string_lossy(),
+            "--runtime",
+            &value.runtime_identifier.to_string(),
+            "--self-contained",
+            "--force",
+        ]);
+
+        if let Some(configuration) = value.configuration {
+            command.args(["--configuration", &configuration]);
+        }
+        if let Some(context) = value.context {
+            command.args(["--context", &context]);
+        }
         command
     }
 }
@@ -139,7 +205,7 @@ mod tests {
     fn test_command_from_dotnet_publish_command() {
         let publish_command = base_publish_command();
         let command = Command::from(publish_command);
-        assert_publish_command_args(&command, &base_publish_command_args());
+        assert_command_args(&command, &base_publish_command_args());
     }
 
     #[test]
@@ -156,10 +222,118 @@ mod tests {
             "--verbosity".to_string(),
             "normal".to_string(),
         ]);
-        assert_publish_command_args(&command, &expected_args);
+        assert_command_args(&command, &expected_args);
+    }
+
+    #[test]
+    fn test_command_from_dotnet_publish_command_with_publish_mode() {
+        let cases = [
+            (
+                PublishMode::FrameworkDependent,
+                vec!["--self-contained", "false"],
+            ),
+            (PublishMode::SelfContained, vec!["--self-contained", "true"]),
+            (
+                PublishMode::SingleFile,
+                vec!["--self-contained", "true", "-p:PublishSingleFile=true"],
+            ),
+        ];
+
+        for (publish_mode, args) in cases {
+            let mut publish_command = base_publish_command();
+            publish_command.publish_mode = Some(publish_mode);
+
+            let command = Command::from(publish_command);
+            let mut expected_args = base_publish_command_args();
+            expected_args.extend(args.into_iter().map(ToString::to_string));
+            assert_command_args(&command, &expected_args);
+        }
+    }
+
+    #[test]
+    fn test_command_from_dotnet_publish_command_with_ready_to_run() {
+        let mut publish_command = base_publish_command();
+        publish_command.ready_to_run = true;
+
+        let command = Command::from(publish_command);
+        let mut expected_args = base_publish_command_args();
+        expected_args.push("-p:PublishReadyToRun=true".to_string());
+        assert_command_args(&command, &expected_args);
+    }
+
+    #[test]
+    fn test_command_from_dotnet_publish_command_with_binary_log_path() {
+        let mut publish_command = base_publish_command();
+        publish_command.verbosity_level = Some(VerbosityLevel::Minimal);
+        publish_command.binary_log_path =
+            Some(PathBuf::from("/layers/msbuild-logs/publish.binlog"));
+
+        let command = Command::from(publish_command);
+        let mut expected_args = base_publish_command_args();
+        expected_args.extend([
+            "--verbosity".to_string(),
+            "minimal".to_string(),
+            "-bl:/layers/msbuild-logs/publish.binlog".to_string(),
+        ]);
+        assert_command_args(&command, &expected_args);
+    }
+
+    #[test]
+    fn test_command_from_dotnet_ef_migrations_bundle_command() {
+        let bundle_command = base_ef_migrations_bundle_command();
+        let command = Command::from(bundle_command);
+        assert_command_args(&command, &base_ef_migrations_bundle_command_args());
     }
 
-    fn assert_publish_command_args(command: &Command, expected_args: &[String]) {
+    #[test]
+    fn test_command_from_dotnet_ef_migrations_bundle_command_with_configuration_and_context() {
+        let mut bundle_command = base_ef_migrations_bundle_command();
+        bundle_command.configuration = Some("Release".to_string());
+        bundle_command.context = Some("BloggingContext".to_string());
+
+        let command = Command::from(bundle_command);
+        let mut expected_args = base_ef_migrations_bundle_command_args();
+        expected_args.extend(vec![
+            "--configuration".to_string(),
+            "Release".to_string(),
+            "--context".to_string(),
+            "BloggingContext".to_string(),
+        ]);
+        assert_command_args(&command, &expected_args);
+    }
+
+    fn base_ef_migrations_bundle_command() -> DotnetEfMigrationsBundleCommand {
+        DotnetEfMigrationsBundleCommand {
+            project_path: PathBuf::from("/foo/Data/Data.csproj"),
+            startup_project_path: PathBuf::from("/foo/Web/Web.csproj"),
+            output_path: PathBuf::from("/layers/ef-migrations/efbundle"),
+            runtime_identifier: RuntimeIdentifier::LinuxX64,
+            configuration: None,
+            context: None,
+        }
+    }
+
+    fn base_ef_migrations_bundle_command_args() -> Vec<String> {
+        [
+            "ef",
+            "migrations",
+            "bundle",
+            "--project",
+            "/foo/Data/Data.csproj",
+            "--startup-project",
+            "/foo/Web/Web.csproj",
+            "--output",
+            "/layers/ef-migrations/efbundle",
+            "--runtime",
+            "linux-x64",
+            "--self-contained",
+            "--force",
+        ]
+        .map(ToString::to_string)
+        .to_vec()
+    }
+
+    fn assert_command_args(command: &Command, expected_args: &[String]) {
         assert_eq!(command.get_program(), "dotnet");
         let args: Vec<String> = command
             .get_args()
@@ -173,7 +347,11 @@ mod tests {
             path: PathBuf::from("/foo/bar.sln"),
             runtime_identifier: RuntimeIdentifier::LinuxX64,
             configuration: None,
+            publish_mode: None,
+            ready_to_run: false,
             verbosity_level: None,
+            binary_log_path: None,
+            artifacts_path: PathBuf::from("/tmp/build_artifacts"),
         }
     }
 
@@ -185,10 +363,7 @@ mod tests {
             "linux-x64".to_string(),
             "-p:PublishDir=bin/publish".to_string(),
             "--artifacts-path".to_string(),
-            temp_dir()
-                .join("build_artifacts")
-                .to_string_lossy()
-                .to_string(),
+            "/tmp/build_artifacts".to_string(),
         ]
     }
 }
@@ -0,0 +1,294 @@
+use crate::detect;
+use crate::dotnet::project::Project;
+use crate::dotnet::solution::Solution;
+use libcnb::data::launch::{Process, ProcessBuilder};
+use libcnb::data::process_type;
+use std::path::Path;
+
+/// The package that adds the design-time services used by the `dotnet ef` tool, which must be
+/// referenced by the startup project.
+pub(crate) const EF_CORE_DESIGN_PACKAGE: &str = "Microsoft.EntityFrameworkCore.Design";
+
+/// The `dotnet ef` tool's package ID in tool manifests
+pub(crate) const DOTNET_EF_TOOL: &str = "dotnet-ef";
+
+/// The projects used to create an Entity Framework Core migrations bundle
+#[derive(Debug)]
+pub(crate) struct MigrationsProjects<'a> {
+    /// The project containing the migrations (and the `DbContext`)
+    pub(crate) project: &'a Project,
+    /// The project that's built and run to create the `DbContext` at design time, which references
+    /// the `Microsoft.EntityFrameworkCore.Design` package
+    pub(crate) startup_project: &'a Project,
+}
+
+#[derive(Debug)]
+pub(crate) enum MigrationsProjectsError {
+    ProjectNotFound(String),
+    MissingDesignPackageReference(String),
+    MultipleStartupProjects(Vec<String>),
+}
+
+/// Returns whether the app's tool manifest (`.config/dotnet-tools.json`) includes the `dotnet ef`
+/// tool. Manifests that can't be read or parsed are ignored here, as `dotnet tool restore` reports
+/// those errors.
+pub(crate) fn has_dotnet_ef_tool(app_dir: &Path) -> bool {
+    detect::dotnet_tools_manifest_file(app_dir)
+        .and_then(|path| fs_err::read_to_string(path).ok())
+        .is_some_and(|contents| tool_manifest_has_dotnet_ef(&contents))
+}
+
+fn tool_manifest_has_dotnet_ef(contents: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(contents)
+        .ok()
+        .as_ref()
+        .and_then(|manifest| manifest.get("tools")?.as_object())
+        .is_some_and(|tools| {
+            tools
+                .keys()
+                .any(|package_id| package_id.eq_ignore_ascii_case(DOTNET_EF_TOOL))
+        })
+}
+
+/// Returns the projects to create a migrations bundle with. The startup project is the project
+/// that references the `Microsoft.EntityFrameworkCore.Design` package, and the migrations project
+/// is the configured project (matched by file name, with or without the extension), or the startup
+/// project. Returns `None` when no project references the package, and no project is configured.
+pub(crate) fn migrations_projects<'a>(
+    solution: &'a Solution,
+    configured_project: Option<&str>,
+) -> Result<Option<MigrationsProjects<'a>>, MigrationsProjectsError> {
+    let project = configured_project
+        .map(|name| {
+            solution
+                .all_projects()
+                .find(|project| {
+                    project
+                        .path
+                        .file_name()
+                        .is_some_and(|file_name| file_name == name)
+                        || project
+                            .path
+                            .file_stem()
+                            .is_some_and(|file_stem| file_stem == name)
+                })
+                .ok_or_else(|| MigrationsProjectsError::ProjectNotFound(name.to_string()))
+        })
+        .transpose()?;
+
+    // The migrations project is used as the startup project if it references the package itself.
+    if let Some(project) = project
+        && references_design_package(project)
+    {
+        return Ok(Some(MigrationsProjects {
+            project,
+            startup_project: project,
+        }));
+    }
+
+    let startup_projects = solution
+        .all_projects()
+        .filter(|project| references_design_package(project))
+        .collect::<Vec<_>>();
+
+    match (startup_projects.as_slice(), project) {
+        ([], None) => Ok(None),
+        ([], Some(project)) => Err(MigrationsProjectsError::MissingDesignPackageReference(
+            project.assembly_name.clone(),
+        )),
+        ([startup_project], project) => Ok(Some(MigrationsProjects {
+            project: project.unwrap_or(startup_project),
+            startup_project,
+        })),
+        (startup_projects, _) => Err(MigrationsProjectsError::MultipleStartupProjects(
+            startup_projects
+                .iter()
+                .map(|project| project.assembly_name.clone())
+                .collect(),
+        )),
+    }
+}
+
+fn references_design_package(project: &Project) -> bool {
+    project
+        .package_references
+        .iter()
+        .any(|package_id| package_id.eq_ignore_ascii_case(EF_CORE_DESIGN_PACKAGE))
+}
+
+/// Returns the `release` process that runs the migrations bundle. The bundle is run directly
+/// (without a shell), and the CNB launcher expands the `$(VAR)` reference to the connection string
+/// environment variable at release time.
+pub(crate) fn release_process(bundle_path: &Path, connection_env_var: Option<&str>) -> Process {
+    let mut process_builder = ProcessBuilder::new(
+        process_type!("release"),
+        [bundle_path.to_string_lossy().to_string()],
+    );
+    if let Some(connection_env_var) = connection_env_var {
+        process_builder.args([
+            "--connection".to_string(),
+            format!("$({connection_env_var})"),
+        ]);
+    }
+    process_builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dotnet::project::ProjectBuilder;
+    use libcnb::data::launch::WorkingDirectory;
+    use libcnb_test::assert_matches;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn create_test_solution(projects: Vec<Project>) -> Solution {
+        Solution {
+            path: PathBuf::from("/tmp/app/app.sln"),
+            projects,
+            referenced_projects: vec![],
+        }
+    }
+
+    #[test]
+    fn test_tool_manifest_has_dotnet_ef() {
+        assert!(tool_manifest_has_dotnet_ef(
+            r#"{ "version": 1, "isRoot": true, "tools": { "dotnet-ef": { "version": "9.0.0", "commands": ["dotnet-ef"] } } }"#
+        ));
+        assert!(tool_manifest_has_dotnet_ef(
+            r#"{ "version": 1, "tools": { "Dotnet-EF": { "version": "9.0.0" } } }"#
+        ));
+        assert!(!tool_manifest_has_dotnet_ef(
+            r#"{ "version": 1, "tools": { "dotnet-format": { "version": "5.1.250801" } } }"#
+        ));
+        assert!(!tool_manifest_has_dotnet_ef(r#"{ "version": 1 }"#));
+        assert!(!tool_manifest_has_dotnet_ef("not json"));
+    }
+
+    #[test]
+    fn test_has_dotnet_ef_tool() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(!has_dotnet_ef_tool(temp_dir.path()));
+
+        fs::create_dir(temp_dir.path().join(".config")).unwrap();
+        fs::write(
+            temp_dir.path().join(".config/dotnet-tools.json"),
+            r#"{ "version": 1, "tools": { "dotnet-ef": { "version": "9.0.0" } } }"#,
+        )
+        .unwrap();
+        assert!(has_dotnet_ef_tool(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_migrations_projects_without_design_package() {
+        let solution = create_test_solution(vec![
+            ProjectBuilder::new("/tmp/app/Web/Web.csproj")
+                .package_references(&["Microsoft.EntityFrameworkCore.Sqlite"])
+                .build(),
+        ]);
+
+        assert_matches!(migrations_projects(&solution, None), Ok(None));
+    }
+
+    #[test]
+    fn test_migrations_projects_detected_from_design_package() {
+        let solution = create_test_solution(vec![
+            ProjectBuilder::new("/tmp/app/Web/Web.csproj")
+                .package_references(&["microsoft.entityframeworkcore.design"])
+                .build(),
+            ProjectBuilder::new("/tmp/app/Data/Data.csproj").build(),
+        ]);
+
+        let projects = migrations_projects(&solution, None).unwrap().unwrap();
+        assert_eq!(projects.project.assembly_name, "Web");
+        assert_eq!(projects.startup_project.assembly_name, "Web");
+    }
+
+    #[test]
+    fn test_migrations_projects_configured_project() {
+        let solution = create_test_solution(vec![
+            ProjectBuilder::new("/tmp/app/Web/Web.csproj")
+                .package_references(&[EF_CORE_DESIGN_PACKAGE])
+                .build(),
+            ProjectBuilder::new("/tmp/app/Data/Data.csproj").build(),
+        ]);
+
+        for name in ["Data", "Data.csproj"] {
+            let projects = migrations_projects(&solution, Some(name)).unwrap().unwrap();
+            assert_eq!(projects.project.assembly_name, "Data");
+            assert_eq!(projects.startup_project.assembly_name, "Web");
+        }
+    }
+
+    #[test]
+    fn test_migrations_projects_configured_project_with_design_package() {
+        let solution = create_test_solution(vec![
+            ProjectBuilder::new("/tmp/app/Web/Web.csproj")
+                .package_references(&[EF_CORE_DESIGN_PACKAGE])
+                .build(),
+            ProjectBuilder::new("/tmp/app/Admin/Admin.csproj")
+                .package_references(&[EF_CORE_DESIGN_PACKAGE])
+                .build(),
+        ]);
+
+        let projects = migrations_projects(&solution, Some("Admin"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(projects.project.assembly_name, "Admin");
+        assert_eq!(projects.startup_project.assembly_name, "Admin");
+    }
+
+    #[test]
+    fn test_migrations_projects_errors() {
+        let solution = create_test_solution(vec![
+            ProjectBuilder::new("/tmp/app/Web/Web.csproj")
+                .package_references(&[EF_CORE_DESIGN_PACKAGE])
+                .build(),
+            ProjectBuilder::new("/tmp/app/Admin/Admin.csproj")
+                .package_references(&[EF_CORE_DESIGN_PACKAGE])
+                .build(),
+            ProjectBuilder::new("/tmp/app/Data/Data.csproj").build(),
+        ]);
+
+        assert_matches!(
+            migrations_projects(&solution, Some("Missing")),
+            Err(MigrationsProjectsError::ProjectNotFound(name)) if name == "Missing"
+        );
+        assert_matches!(
+            migrations_projects(&solution, None),
+            Err(MigrationsProjectsError::MultipleStartupProjects(names)) if names == ["Web", "Admin"]
+        );
+        assert_matches!(
+            migrations_projects(
+                &create_test_solution(vec![ProjectBuilder::new("/tmp/app/Data/Data.csproj").build()]),
+                Some("Data")
+            ),
+            Err(MigrationsProjectsError::MissingDesignPackageReference(name)) if name == "Data"
+        );
+    }
+
+    #[test]
+    fn test_release_process() {
+        let bundle_path = Path::new("/layers/heroku_dotnet/ef-migrations/efbundle");
+
+        let process = release_process(bundle_path, None);
+        assert_eq!(process.r#type, process_type!("release"));
+        assert_eq!(
+            process.command,
+            vec!["/layers/heroku_dotnet/ef-migrations/efbundle"]
+        );
+        assert!(process.args.is_empty());
+        assert!(!process.default);
+        assert_eq!(process.working_directory, WorkingDirectory::App);
+
+        let process = release_process(bundle_path, Some("DATABASE_CONNECTION_STRING"));
+        assert_eq!(
+            process.command,
+            vec!["/layers/heroku_dotnet/ef-migrations/efbundle"]
+        );
+        assert_eq!(
+            process.args,
+            vec!["--connection", "$(DATABASE_CONNECTION_STRING)"]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/// Returns whether the buildpack runs under emulation of its architecture, such as an amd64 builder
+/// image running on an Apple Silicon Mac (with Rosetta or QEMU). The emulated process still sees
+/// the host CPU in `/proc/cpuinfo` (or, with Rosetta, a `VirtualApple` vendor).
+pub(crate) fn is_emulated() -> bool {
+    fs_err::read_to_string("/proc/cpuinfo")
+        .is_ok_and(|cpuinfo| cpuinfo_indicates_emulation(&cpuinfo, std::env::consts::ARCH))
+}
+
+fn cpuinfo_indicates_emulation(cpuinfo: &str, arch: &str) -> bool {
+    let fields = cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect::<Vec<_>>();
+    let has_field = |field_name: &str| fields.iter().any(|(name, _)| *name == field_name);
+
+    let x86_cpu = has_field("vendor_id");
+    let arm_cpu = has_field("CPU implementer");
+    let rosetta = fields
+        .iter()
+        .any(|(name, value)| *name == "vendor_id" && *value == "VirtualApple");
+
+    match arch {
+        "x86_64" => rosetta || (arm_cpu && !x86_cpu),
+        "aarch64" => x86_cpu && !arm_cpu,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X86_CPUINFO: &str =
+        "processor\t: 0\nvendor_id\t: GenuineIntel\ncpu family\t: 6\nflags\t\t: fpu vme de pse\n";
+    const ARM_CPUINFO: &str = "processor\t: 0\nBogoMIPS\t: 48.00\nFeatures\t: fp asimd evtstrm\nCPU implementer\t: 0x61\nCPU architecture: 8\n";
+    const ROSETTA_CPUINFO: &str =
+        "processor\t: 0\nvendor_id\t: VirtualApple\nmodel name\t: VirtualApple @ 2.50GHz\n";
+
+    #[test]
+    fn test_native_cpuinfo() {
+        assert!(!cpuinfo_indicates_emulation(X86_CPUINFO, "x86_64"));
+        assert!(!cpuinfo_indicates_emulation(ARM_CPUINFO, "aarch64"));
+    }
+
+    #[test]
+    fn test_emulated_cpuinfo() {
+        assert!(cpuinfo_indicates_emulation(ARM_CPUINFO, "x86_64"));
+        assert!(cpuinfo_indicates_emulation(ROSETTA_CPUINFO, "x86_64"));
+        assert!(cpuinfo_indicates_emulation(X86_CPUINFO, "aarch64"));
+    }
+
+    #[test]
+    fn test_unknown_cpuinfo() {
+        assert!(!cpuinfo_indicates_emulation("", "x86_64"));
+        assert!(!cpuinfo_indicates_emulation(ARM_CPUINFO, "riscv64"));
+    }
+}
@@ -1,12 +1,19 @@
 use crate::DotnetBuildpackError;
 use crate::app_source::{self, DiscoveryError};
 use crate::dotnet::global_json::SdkConfigError;
+use crate::dotnet::msbuild_diagnostics::{self, Diagnostic, Severity};
 use crate::dotnet::target_framework_moniker::ParseTargetFrameworkError;
 use crate::dotnet::{project, solution};
 use crate::dotnet_buildpack_configuration::{
-    DotnetBuildpackConfigurationError, ExecutionEnvironmentError, ParseVerbosityLevelError,
+    DotnetBuildpackConfigurationError, ExecutionEnvironmentError, ParseLaunchProcessModeError,
+    ParseProcfileModeError, ParsePublishModeError, ParsePublishStrategyError,
+    ParseSdkMirrorUrlError, ParseVerbosityLevelError,
 };
+use crate::ef_migrations::MigrationsProjectsError;
+use crate::launch_process::LaunchProfileError;
+use crate::launch_smoke_check::{LaunchSmokeCheck, LaunchSmokeCheckError};
 use crate::layers::sdk::SdkLayerError;
+use crate::publish_scheduler::PublishProjectsError;
 use bullet_stream::{Print, fun_run, style};
 use indoc::formatdoc;
 use std::io::{self, Write};
@@ -46,6 +53,12 @@ fn on_buildpack_error_with_writer(error: &DotnetBuildpackError, mut writer: impl
             "determining if we must run the Heroku .NET buildpack for this application",
             io_error,
         ),
+        DotnetBuildpackError::ReadProcfile(io_error) => log_io_error_to(
+            &mut writer,
+            "Error reading `Procfile`",
+            "reading the `Procfile` to determine which process types to register",
+            io_error,
+        ),
         DotnetBuildpackError::ReadProjectTomlFile(io_error) => log_io_error_to(
             &mut writer,
             "Error reading `project.toml` file",
@@ -385,14 +398,6 @@ fn on_buildpack_error_with_writer(error: &DotnetBuildpackError, mut writer: impl
                 "},
                 Some(error.to_string()),
             ),
-            SdkLayerError::ReadArchive(io_error) => {
-                log_io_error_to(
-                    &mut writer,
-                    "Error reading downloaded SDK archive",
-                    "calculating checksum for the downloaded .NET SDK archive",
-                    io_error,
-                );
-            }
             SdkLayerError::VerifyArchiveChecksum { expected, actual } => log_error_to(
                 &mut writer,
                 "Corrupted .NET SDK download",
@@ -409,20 +414,52 @@ fn on_buildpack_error_with_writer(error: &DotnetBuildpackError, mut writer: impl
                 ", expected = hex::encode(expected), actual = hex::encode(actual) },
                 None,
             ),
-            SdkLayerError::OpenArchive(io_error) => {
-                log_io_error_to(
-                    &mut writer,
-                    "Error reading downloaded SDK archive",
-                    "decompressing downloaded .NET SDK archive",
-                    io_error,
-                );
-            }
+            SdkLayerError::InvalidArtifactUrl(url) => log_error_to(
+                &mut writer,
+                "Invalid .NET SDK artifact URL",
+                formatdoc! {"
+                    The .NET SDK artifact URL listed in the inventory ({url}) isn't a valid URL
+                    ending with a file name, so the SDK can't be fetched from the configured mirror
+                    or local artifact directory.
+
+                    This is an unexpected error. Please file an issue here:
+                    https://github.com/heroku/buildpacks-dotnet/issues/new
+                "},
+                None,
+            ),
+            SdkLayerError::PrepareStagingDirectory(io_error) => log_io_error_to(
+                &mut writer,
+                "Failed to prepare .NET SDK installation",
+                "preparing to download the .NET SDK",
+                io_error,
+            ),
+            SdkLayerError::OpenLocalArchive(io_error) => log_error_to(
+                &mut writer,
+                "Error reading local .NET SDK archive",
+                formatdoc! {"
+                    We can’t read the .NET SDK archive from the configured local artifact directory.
+
+                    The directory configured with the `SDK_ARTIFACT_DIR` environment variable or in
+                    `project.toml` must contain the SDK archive using the file name listed in the
+                    inventory (e.g. `dotnet-sdk-8.0.101-linux-x64.tar.gz`).
+
+                    For a complete inventory of supported .NET SDK versions and platforms, see:
+                    https://github.com/heroku/buildpacks-dotnet/blob/main/buildpacks/dotnet/inventory.toml
+                "},
+                Some(io_error.to_string()),
+            ),
             SdkLayerError::DecompressArchive(io_error) => log_io_error_to(
                 &mut writer,
                 "Failed to decompress .NET SDK",
                 "extracting .NET SDK archive contents",
                 io_error,
             ),
+            SdkLayerError::InstallStagedSdk(io_error) => log_io_error_to(
+                &mut writer,
+                "Failed to install .NET SDK",
+                "installing the verified .NET SDK files",
+                io_error,
+            ),
         },
         DotnetBuildpackError::ParseBuildpackConfiguration(error) => match error {
             DotnetBuildpackConfigurationError::VerbosityLevel(ParseVerbosityLevelError(
@@ -449,6 +486,125 @@ fn on_buildpack_error_with_writer(error: &DotnetBuildpackError, mut writer: impl
                     None,
                 );
             }
+            DotnetBuildpackConfigurationError::PublishMode(ParsePublishModeError(publish_mode)) => {
+                log_error_to(
+                    &mut writer,
+                    "Invalid publish mode",
+                    formatdoc! {"
+                        The configured publish mode (`{publish_mode}`) is invalid. Did you mean
+                        one of the following supported values?
+
+                        framework-dependent
+                        self-contained
+                        single-file
+
+                        For more information, see:
+                        https://github.com/heroku/buildpacks-dotnet#publish-mode
+                    "},
+                    None,
+                );
+            }
+            DotnetBuildpackConfigurationError::LaunchProcessMode(ParseLaunchProcessModeError(
+                launch_process_mode,
+            )) => {
+                log_error_to(
+                    &mut writer,
+                    "Invalid launch process mode",
+                    formatdoc! {"
+                        The configured launch process mode (`{launch_process_mode}`) is invalid. Did
+                        you mean one of the following supported values?
+
+                        exec
+                        shell
+
+                        For more information, see:
+                        https://github.com/heroku/buildpacks-dotnet#launch-process-mode
+                    "},
+                    None,
+                );
+            }
+            DotnetBuildpackConfigurationError::ProcfileMode(ParseProcfileModeError(
+                procfile_mode,
+            )) => {
+                log_error_to(
+                    &mut writer,
+                    "Invalid Procfile mode",
+                    formatdoc! {"
+                        The configured Procfile mode (`{procfile_mode}`) is invalid. Did you mean
+                        one of the following supported values?
+
+                        merge
+                        skip
+                        override
+
+                        For more information, see:
+                        https://github.com/heroku/buildpacks-dotnet#procfile
+                    "},
+                    None,
+                );
+            }
+            DotnetBuildpackConfigurationError::PublishStrategy(ParsePublishStrategyError(
+                publish_strategy,
+            )) => {
+                log_error_to(
+                    &mut writer,
+                    "Invalid publish strategy",
+                    formatdoc! {"
+                        The configured publish strategy (`{publish_strategy}`) is invalid. Did you
+                        mean one of the following supported values?
+
+                        solution
+                        projects
+
+                        For more information, see:
+                        https://github.com/heroku/buildpacks-dotnet#publish-strategy
+                    "},
+                    None,
+                );
+            }
+            DotnetBuildpackConfigurationError::PublishConcurrency(value) => {
+                log_error_to(
+                    &mut writer,
+                    "Invalid publish concurrency",
+                    formatdoc! {"
+                        The `PUBLISH_CONCURRENCY` environment variable value (`{value}`) is invalid.
+                        Use the maximum number of projects to publish at the same time, which must
+                        be a positive whole number (e.g. `4`).
+
+                        For more information, see:
+                        https://github.com/heroku/buildpacks-dotnet#publish-strategy
+                    "},
+                    None,
+                );
+            }
+            DotnetBuildpackConfigurationError::InvalidBoolean(name, value) => {
+                log_error_to(
+                    &mut writer,
+                    "Invalid boolean setting",
+                    formatdoc! {"
+                        The `{name}` environment variable value (`{value}`) is invalid. Use `true`
+                        or `false` instead.
+                    "},
+                    None,
+                );
+            }
+            DotnetBuildpackConfigurationError::SdkMirrorUrl(ParseSdkMirrorUrlError(url)) => {
+                log_error_to(
+                    &mut writer,
+                    "Invalid .NET SDK mirror URL",
+                    formatdoc! {"
+                        The configured .NET SDK mirror URL (`{url}`) is invalid.
+
+                        The `SDK_MIRROR_URL` environment variable or `project.toml` value must be an
+                        absolute `http://` or `https://` URL, or a `file://` URL pointing at a local
+                        directory containing .NET SDK archives.
+
+                        For more information, see:
+                        https://github.com/heroku/buildpacks-dotnet#sdk-download-source
+                    "},
+                    None,
+                );
+            }
             DotnetBuildpackConfigurationError::ExecutionEnvironment(error) => match error {
                 ExecutionEnvironmentError::UnsupportedExecutionEnvironment(
                     execution_environment,
@@ -481,6 +637,26 @@ fn on_buildpack_error_with_writer(error: &DotnetBuildpackError, mut writer: impl
                     None,
                 );
             }
+            DotnetBuildpackConfigurationError::EfMigrationsConnectionEnvVar(name) => {
+                log_error_to(
+                    &mut writer,
+                    "Invalid migrations connection environment variable",
+                    formatdoc! {"
+                        The configured Entity Framework Core migrations connection environment
+                        variable (`{name}`) isn’t a valid environment variable name.
+
+                        Use a name with only letters, digits and underscores, that doesn’t start
+                        with a digit (e.g. `DATABASE_CONNECTION_STRING`). Check the
+                        `EF_MIGRATIONS_CONNECTION_ENV_VAR` environment variable or
+                        `ef_migrations.connection_env_var` setting in `project.toml` and retry your
+                        build.
+
+                        For more information, see:
+                        https://github.com/heroku/buildpacks-dotnet#entity-framework-core-migrations
+                    "},
+                    None,
+                );
+            }
             DotnetBuildpackConfigurationError::SolutionFileContainsPath(solution_file) => {
                 log_error_to(
                     &mut writer,
@@ -524,28 +700,256 @@ fn on_buildpack_error_with_writer(error: &DotnetBuildpackError, mut writer: impl
                 None,
             ),
         },
-        DotnetBuildpackError::PublishCommand(error) => match error {
+        DotnetBuildpackError::ConfigureWorkloads(io_error) => log_io_error_to(
+            &mut writer,
+            "Unable to configure .NET workload installation",
+            "configuring the .NET SDK to install workloads in the workloads layer",
+            io_error,
+        ),
+        DotnetBuildpackError::ConfigureKestrel(io_error) => log_io_error_to(
+            &mut writer,
+            "Unable to configure Kestrel",
+            "copying the program that configures Kestrel at launch to the kestrel layer",
+            io_error,
+        ),
+        DotnetBuildpackError::RestoreWorkloadsCommand(error) => match error {
             fun_run::CmdError::SystemError(_message, io_error) => log_io_error_to(
                 &mut writer,
-                "Unable to publish",
-                "running the command to publish the .NET solution/project",
+                "Unable to restore .NET workloads",
+                "running the command to restore .NET workloads",
                 io_error,
             ),
             fun_run::CmdError::NonZeroExitNotStreamed(output)
             | fun_run::CmdError::NonZeroExitAlreadyStreamed(output) => log_error_to(
                 &mut writer,
-                "Unable to publish",
+                "Unable to restore .NET workloads",
                 formatdoc! {"
-                    The `dotnet publish` command failed ({exit_status}).
+                    The `dotnet workload restore` command failed ({exit_status}).
 
-                    The most common cause is a compilation error. Review the command output above
-                    to find and fix the issue.
+                    The most common cause is a workload that isn't available for the .NET SDK
+                    version used to build your app. Review the command output above to find and
+                    fix the issue.
 
                     The failure may also be temporary due to a network or service outage. Retrying
                     your build often resolves this.
 
-                    If the log suggests a NuGet issue, check the service status before retrying:
-                    https://status.nuget.org
+                    For more information, see:
+                    https://github.com/heroku/buildpacks-dotnet#workloads
+                ", exit_status = output.status()},
+                None,
+            ),
+        },
+        DotnetBuildpackError::MissingNativeAotPrerequisites(prerequisites) => {
+            let missing_prerequisites = prerequisites
+                .iter()
+                .map(|prerequisite| {
+                    format!(
+                        "- {prerequisite} (Ubuntu package: `{}`)",
+                        prerequisite.package_name()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            log_error_to(
+                &mut writer,
+                "Missing Native AOT prerequisites",
+                formatdoc! {"
+                    One or more projects are configured to publish as Native AOT apps
+                    (`PublishAot`), but the following native toolchain prerequisites weren't found
+                    in the build environment:
+
+                    {missing_prerequisites}
+
+                    Install the missing packages before this buildpack runs (e.g. using a buildpack
+                    that installs Ubuntu packages), or disable Native AOT compilation by setting the
+                    `PublishAot` property to `false`.
+
+                    For more information, see:
+                    https://learn.microsoft.com/en-us/dotnet/core/deploying/native-aot/#prerequisites
+                "},
+                None,
+            );
+        }
+        DotnetBuildpackError::PublishCommand(error) => match error {
+            fun_run::CmdError::SystemError(_message, io_error) => log_io_error_to(
+                &mut writer,
+                "Unable to publish",
+                "running the command to publish the .NET solution/project",
+                io_error,
+            ),
+            fun_run::CmdError::NonZeroExitNotStreamed(output)
+            | fun_run::CmdError::NonZeroExitAlreadyStreamed(output) => {
+                let errors = msbuild_diagnostics::parse_diagnostics(&output.stdout_lossy())
+                    .into_iter()
+                    .filter(|diagnostic| diagnostic.severity == Severity::Error)
+                    .collect::<Vec<_>>();
+                if errors.is_empty() {
+                    log_error_to(
+                        &mut writer,
+                        "Unable to publish",
+                        formatdoc! {"
+                            The `dotnet publish` command failed ({exit_status}).
+
+                            The most common cause is a compilation error. Review the command output above
+                            to find and fix the issue.
+
+                            The failure may also be temporary due to a network or service outage. Retrying
+                            your build often resolves this.
+
+                            If the log suggests a NuGet issue, check the service status before retrying:
+                            https://status.nuget.org
+                        ", exit_status = output.status()},
+                        None,
+                    );
+                } else {
+                    log_error_to(
+                        &mut writer,
+                        "Unable to publish",
+                        formatdoc! {"
+                            The `dotnet publish` command failed ({exit_status}) with the following
+                            {count} error(s):
+
+                            {summary}
+
+                            Fix these errors and retry your build. The command output above has more
+                            details.
+                        ", exit_status = output.status(), count = errors.len(), summary = format_diagnostics_summary(&errors)},
+                        None,
+                    );
+                }
+            }
+        },
+        DotnetBuildpackError::PublishProjects(PublishProjectsError { failed, skipped }) => {
+            let mut sections = Vec::new();
+            if !failed.is_empty() {
+                sections.push(format!(
+                    "The `dotnet publish` command failed for {count} project(s):\n\n{summary}",
+                    count = failed.len(),
+                    summary = failed
+                        .iter()
+                        .map(|(name, error)| format_publish_project_failure(name, error))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ));
+            }
+            if !skipped.is_empty() {
+                sections.push(format!(
+                    "The following project(s) weren't published, because a project they reference\ncouldn't be published: {}",
+                    skipped
+                        .iter()
+                        .map(|name| format!("`{name}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            log_error_to(
+                &mut writer,
+                "Unable to publish projects",
+                formatdoc! {"
+                    {sections}
+
+                    Fix these errors and retry your build. The command output above has more
+                    details.
+                ", sections = sections.join("\n\n")},
+                None,
+            );
+        }
+        DotnetBuildpackError::TrimAnalysisWarnings(warnings) => {
+            let mut codes = warnings
+                .iter()
+                .map(|warning| warning.code.as_str())
+                .collect::<Vec<_>>();
+            codes.sort_unstable();
+            codes.dedup();
+            log_error_to(
+                &mut writer,
+                "Trim analysis warnings found",
+                formatdoc! {"
+                    Publishing produced {count} trim or Native AOT analysis warning(s) ({codes}),
+                    and the build is configured to fail when such warnings are found.
+
+                    These warnings indicate code that may not work correctly once the app is
+                    trimmed. Review the warnings listed above and fix them, or disable this check by
+                    setting `PUBLISH_FAIL_ON_TRIM_WARNINGS` (or `publish_fail_on_trim_warnings`
+                    in `project.toml`) to `false`.
+
+                    For more information, see:
+                    https://learn.microsoft.com/dotnet/core/deploying/trimming/fixing-warnings
+                ", count = warnings.len(), codes = codes.join(", ")},
+                None,
+            );
+        }
+        DotnetBuildpackError::EfMigrationsProjects(error) => match error {
+            MigrationsProjectsError::ProjectNotFound(name) => log_error_to(
+                &mut writer,
+                "Migrations project not found",
+                formatdoc! {"
+                    The configured Entity Framework Core migrations project `{name}` isn’t part
+                    of the solution, or referenced by its projects.
+
+                    Check the `EF_MIGRATIONS_PROJECT` environment variable or
+                    `ef_migrations.project` setting in `project.toml` (which must be the project’s
+                    file name, with or without the extension) and retry your build.
+
+                    For more information, see:
+                    https://github.com/heroku/buildpacks-dotnet#entity-framework-core-migrations
+                "},
+                None,
+            ),
+            MigrationsProjectsError::MissingDesignPackageReference(name) => log_error_to(
+                &mut writer,
+                "Missing Entity Framework Core design package",
+                formatdoc! {"
+                    The `{name}` project is configured as the Entity Framework Core migrations
+                    project, but no project references the `Microsoft.EntityFrameworkCore.Design`
+                    package, which the `dotnet ef` tool needs to create a migrations bundle.
+
+                    Add a `PackageReference` to the package in your startup project (or the
+                    migrations project) and retry your build.
+
+                    For more information, see:
+                    https://github.com/heroku/buildpacks-dotnet#entity-framework-core-migrations
+                "},
+                None,
+            ),
+            MigrationsProjectsError::MultipleStartupProjects(names) => log_error_to(
+                &mut writer,
+                "Multiple Entity Framework Core startup projects",
+                formatdoc! {"
+                    The following projects reference the `Microsoft.EntityFrameworkCore.Design`
+                    package: {names}.
+
+                    The buildpack can’t tell which project to create the migrations bundle with.
+                    Set the `EF_MIGRATIONS_PROJECT` environment variable (or
+                    `ef_migrations.project` in `project.toml`) to the project that contains the
+                    migrations, and retry your build.
+
+                    For more information, see:
+                    https://github.com/heroku/buildpacks-dotnet#entity-framework-core-migrations
+                ", names = names.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ")},
+                None,
+            ),
+        },
+        DotnetBuildpackError::EfMigrationsBundleCommand(error) => match error {
+            fun_run::CmdError::SystemError(_message, io_error) => log_io_error_to(
+                &mut writer,
+                "Unable to create migrations bundle",
+                "running the command to create the Entity Framework Core migrations bundle",
+                io_error,
+            ),
+            fun_run::CmdError::NonZeroExitNotStreamed(output)
+            | fun_run::CmdError::NonZeroExitAlreadyStreamed(output) => log_error_to(
+                &mut writer,
+                "Unable to create migrations bundle",
+                formatdoc! {"
+                    The `dotnet ef migrations bundle` command failed ({exit_status}).
+
+                    The most common causes are a `DbContext` that can’t be created at design time,
+                    or a project with more than one `DbContext` (set `EF_MIGRATIONS_CONTEXT` to
+                    choose one). Review the command output above to find and fix the issue.
+
+                    For more information, see:
+                    https://learn.microsoft.com/ef/core/cli/dbcontext-creation
                 ", exit_status = output.status()},
                 None,
             ),
@@ -556,6 +960,155 @@ fn on_buildpack_error_with_writer(error: &DotnetBuildpackError, mut writer: impl
             "copying .NET runtime files from the SDK layer to the runtime layer",
             io_error,
         ),
+        DotnetBuildpackError::ReadRuntimeConfig(io_error) => log_io_error_to(
+            &mut writer,
+            "Error reading published runtime configuration",
+            "detecting the shared frameworks required by the published app",
+            io_error,
+        ),
+        DotnetBuildpackError::ParseRuntimeConfig(path, error) => log_error_to(
+            &mut writer,
+            "Invalid published runtime configuration",
+            formatdoc! {"
+                We can’t parse the runtime configuration file written by `dotnet publish`
+                because it contains invalid JSON:
+                `{}`
+
+                The buildpack uses this file to detect which .NET shared frameworks to include
+                in the app image.
+
+                Use the debug information above to troubleshoot and retry your build.
+            ", path.to_string_lossy()},
+            Some(error.to_string()),
+        ),
+        DotnetBuildpackError::LaunchProfile(error) => match error {
+            LaunchProfileError::ReadLaunchSettings(io_error) => log_io_error_to(
+                &mut writer,
+                "Error reading launch settings",
+                "reading the launch settings used to configure launch processes",
+                io_error,
+            ),
+            LaunchProfileError::ParseLaunchSettings(path, error) => log_error_to(
+                &mut writer,
+                "Invalid launch settings",
+                formatdoc! {"
+                    We can’t parse the launch settings file because it contains invalid JSON:
+                    `{}`
+
+                    The buildpack uses the launch profile in this file to configure the arguments
+                    and environment variables of the project’s launch process.
+
+                    Use the debug information above to troubleshoot and retry your build.
+                ", path.to_string_lossy()},
+                Some(error.to_string()),
+            ),
+            LaunchProfileError::ProfileNotFound(name) => log_error_to(
+                &mut writer,
+                "Launch profile not found",
+                formatdoc! {"
+                    The configured launch profile `{name}` isn’t defined in the launch settings
+                    of any launchable project.
+
+                    Launch profiles are read from each project’s `Properties/launchSettings.json`
+                    file (or `<app>.run.json` for file-based apps).
+
+                    Check the `LAUNCH_PROFILE` environment variable or `launch_profile` setting in
+                    `project.toml` and retry your build.
+
+                    For more information, see:
+                    https://github.com/heroku/buildpacks-dotnet#launch-profile
+                "},
+                None,
+            ),
+            LaunchProfileError::ParseCommandLineArgs(path, name, error) => log_error_to(
+                &mut writer,
+                "Invalid launch profile arguments",
+                formatdoc! {"
+                    We can’t parse the `commandLineArgs` of the `{name}` launch profile in:
+                    `{path}`
+
+                    Arguments are split like a shell command line. Check that all quotes in the
+                    value are closed, and retry your build.
+                ", path = path.to_string_lossy()},
+                Some(error.to_string()),
+            ),
+        },
+        DotnetBuildpackError::CheckNativeDependencies(io_error) => log_io_error_to(
+            &mut writer,
+            "Error checking native library dependencies",
+            "reading the native libraries published with the app",
+            io_error,
+        ),
+        DotnetBuildpackError::LaunchSmokeCheck(name, error) => match error {
+            LaunchSmokeCheckError::Io(io_error) => log_io_error_to(
+                &mut writer,
+                "Error running launch smoke check",
+                &format!("starting `{name}` to check that it launches"),
+                io_error,
+            ),
+            LaunchSmokeCheckError::Exited {
+                check,
+                status,
+                output,
+            } => {
+                let failure = match check {
+                    LaunchSmokeCheck::WebApp => formatdoc! {"
+                        The published `{name}` web app exited before accepting connections on the
+                        dummy `PORT` it was started with ({status}).
+                    "},
+                    LaunchSmokeCheck::Help => formatdoc! {"
+                        The published `{name}` app exited unsuccessfully when run with `--help`
+                        ({status}).
+                    "},
+                };
+                log_error_to(
+                    &mut writer,
+                    "Launch smoke check failed",
+                    formatdoc! {"
+                        {failure}
+                        This usually means the app will crash at launch, for instance because a
+                        native library it depends on isn’t installed, or its configuration is
+                        invalid. The app’s output is included in the debug information above.
+
+                        {LAUNCH_SMOKE_CHECK_HELP}
+                    "},
+                    Some(output_or_placeholder(output)),
+                );
+            }
+            LaunchSmokeCheckError::NotListening {
+                port,
+                timeout,
+                output,
+            } => log_error_to(
+                &mut writer,
+                "Launch smoke check failed",
+                formatdoc! {"
+                    The published `{name}` web app didn’t accept connections on port {port} within
+                    {timeout} seconds of starting.
+
+                    Check that the app listens on the port set by the `PORT` (or `ASPNETCORE_URLS`)
+                    environment variable, and that it doesn’t wait for unavailable services before
+                    starting the web server. The app’s output is included in the debug information
+                    above.
+
+                    {LAUNCH_SMOKE_CHECK_HELP}
+                ", timeout = timeout.as_secs()},
+                Some(output_or_placeholder(output)),
+            ),
+        },
+    }
+}
+
+const LAUNCH_SMOKE_CHECK_HELP: &str = "\
+    To disable this check, set `LAUNCH_SMOKE_CHECK` (or `launch_smoke_check` in\n\
+    `project.toml`) to `false`. For more information, see:\n\
+    https://github.com/heroku/buildpacks-dotnet#launch-smoke-check";
+
+fn output_or_placeholder(output: &str) -> String {
+    if output.is_empty() {
+        "(no output)".to_string()
+    } else {
+        output.to_string()
     }
 }
 
@@ -631,6 +1184,57 @@ fn on_load_dotnet_project_error_with_writer(
     }
 }
 
+// The maximum number of diagnostics listed in an error message summary.
+const MAX_SUMMARIZED_DIAGNOSTICS: usize = 10;
+
+fn format_publish_project_failure(name: &str, error: &fun_run::CmdError) -> String {
+    match error {
+        fun_run::CmdError::SystemError(_message, io_error) => {
+            format!("- `{name}`: unable to run the command ({io_error})")
+        }
+        fun_run::CmdError::NonZeroExitNotStreamed(output)
+        | fun_run::CmdError::NonZeroExitAlreadyStreamed(output) => {
+            let errors = msbuild_diagnostics::parse_diagnostics(&output.stdout_lossy())
+                .into_iter()
+                .filter(|diagnostic| diagnostic.severity == Severity::Error)
+                .collect::<Vec<_>>();
+            let mut lines = vec![format!(
+                "- `{name}`: failed ({exit_status}) with {count} error(s)",
+                exit_status = output.status(),
+                count = errors.len()
+            )];
+            if !errors.is_empty() {
+                lines.extend(
+                    format_diagnostics_summary(&errors)
+                        .lines()
+                        .map(|line| format!("  {line}")),
+                );
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+fn format_diagnostics_summary(diagnostics: &[Diagnostic]) -> String {
+    let mut lines = Vec::new();
+    for diagnostic in diagnostics.iter().take(MAX_SUMMARIZED_DIAGNOSTICS) {
+        lines.push(match &diagnostic.project {
+            Some(project) => format!("- {diagnostic} (project: {project})"),
+            None => format!("- {diagnostic}"),
+        });
+        if let Some(url) = diagnostic.documentation_url() {
+            lines.push(format!("  {url}"));
+        }
+    }
+    if diagnostics.len() > MAX_SUMMARIZED_DIAGNOSTICS {
+        lines.push(format!(
+            "- ...and {} more",
+            diagnostics.len() - MAX_SUMMARIZED_DIAGNOSTICS
+        ));
+    }
+    lines.join("\n")
+}
+
 fn log_io_error_to(
     mut writer: impl Write,
     header: &str,
@@ -673,6 +1277,8 @@ fn log_error_to(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::native_aot::NativeAotPrerequisite;
+    use indoc::indoc;
     use insta::{assert_snapshot, with_settings};
     use std::path::PathBuf;
 
@@ -698,6 +1304,11 @@ mod tests {
         assert_error_snapshot(DotnetBuildpackError::ReadProjectTomlFile(create_io_error()));
     }
 
+    #[test]
+    fn test_read_procfile_error() {
+        assert_error_snapshot(DotnetBuildpackError::ReadProcfile(create_io_error()));
+    }
+
     #[test]
     fn test_parse_project_toml_error() {
         assert_error_snapshot(DotnetBuildpackError::ParseProjectToml(
@@ -976,6 +1587,20 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_sdk_layer_invalid_artifact_url_error() {
+        assert_error_snapshot(DotnetBuildpackError::SdkLayer(
+            SdkLayerError::InvalidArtifactUrl("not a url".to_string()),
+        ));
+    }
+
+    #[test]
+    fn test_sdk_layer_open_local_archive_error() {
+        assert_error_snapshot(DotnetBuildpackError::SdkLayer(
+            SdkLayerError::OpenLocalArchive(create_io_error()),
+        ));
+    }
+
     #[test]
     fn test_sdk_layer_decompress_archive_error() {
         assert_error_snapshot(DotnetBuildpackError::SdkLayer(
@@ -994,17 +1619,17 @@ mod tests {
     }
 
     #[test]
-    fn test_sdk_layer_open_archive_error() {
-        assert_error_snapshot(DotnetBuildpackError::SdkLayer(SdkLayerError::OpenArchive(
-            create_io_error(),
-        )));
+    fn test_sdk_layer_prepare_staging_directory_error() {
+        assert_error_snapshot(DotnetBuildpackError::SdkLayer(
+            SdkLayerError::PrepareStagingDirectory(create_io_error()),
+        ));
     }
 
     #[test]
-    fn test_sdk_layer_read_archive_error() {
-        assert_error_snapshot(DotnetBuildpackError::SdkLayer(SdkLayerError::ReadArchive(
-            create_io_error(),
-        )));
+    fn test_sdk_layer_install_staged_sdk_error() {
+        assert_error_snapshot(DotnetBuildpackError::SdkLayer(
+            SdkLayerError::InstallStagedSdk(create_io_error()),
+        ));
     }
 
     #[test]
@@ -1016,6 +1641,61 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_publish_mode_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
+            DotnetBuildpackConfigurationError::PublishMode(ParsePublishModeError(
+                "native".to_string(),
+            )),
+        ));
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_launch_process_mode_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
+            DotnetBuildpackConfigurationError::LaunchProcessMode(ParseLaunchProcessModeError(
+                "bash".to_string(),
+            )),
+        ));
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_procfile_mode_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
+            DotnetBuildpackConfigurationError::ProcfileMode(ParseProcfileModeError(
+                "replace".to_string(),
+            )),
+        ));
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_publish_strategy_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
+            DotnetBuildpackConfigurationError::PublishStrategy(ParsePublishStrategyError(
+                "parallel".to_string(),
+            )),
+        ));
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_boolean_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
+            DotnetBuildpackConfigurationError::InvalidBoolean(
+                "PUBLISH_READY_TO_RUN",
+                "yes".to_string(),
+            ),
+        ));
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_sdk_mirror_url_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
+            DotnetBuildpackConfigurationError::SdkMirrorUrl(ParseSdkMirrorUrlError(
+                "ftp://example.com".to_string(),
+            )),
+        ));
+    }
+
     #[test]
     fn test_parse_buildpack_configuration_unsupported_execution_environment_error() {
         assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
@@ -1043,6 +1723,56 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_buildpack_configuration_ef_migrations_connection_env_var_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
+            DotnetBuildpackConfigurationError::EfMigrationsConnectionEnvVar(
+                "DATABASE-URL".to_string(),
+            ),
+        ));
+    }
+
+    #[test]
+    fn test_ef_migrations_projects_project_not_found_error() {
+        assert_error_snapshot(DotnetBuildpackError::EfMigrationsProjects(
+            MigrationsProjectsError::ProjectNotFound("Data".to_string()),
+        ));
+    }
+
+    #[test]
+    fn test_ef_migrations_projects_missing_design_package_reference_error() {
+        assert_error_snapshot(DotnetBuildpackError::EfMigrationsProjects(
+            MigrationsProjectsError::MissingDesignPackageReference("Data".to_string()),
+        ));
+    }
+
+    #[test]
+    fn test_ef_migrations_projects_multiple_startup_projects_error() {
+        assert_error_snapshot(DotnetBuildpackError::EfMigrationsProjects(
+            MigrationsProjectsError::MultipleStartupProjects(vec![
+                "Web".to_string(),
+                "Admin".to_string(),
+            ]),
+        ));
+    }
+
+    #[test]
+    fn test_ef_migrations_bundle_command_system_error() {
+        assert_error_snapshot(DotnetBuildpackError::EfMigrationsBundleCommand(
+            fun_run::CmdError::SystemError(
+                "Failed to start process".to_string(),
+                create_io_error(),
+            ),
+        ));
+    }
+
+    #[test]
+    fn test_ef_migrations_bundle_command_non_zero_exit_not_streamed_error() {
+        assert_error_snapshot(DotnetBuildpackError::EfMigrationsBundleCommand(
+            create_fun_run_cmd_error_captured_output(),
+        ));
+    }
+
     #[test]
     fn test_restore_dotnet_tools_command_system_error() {
         assert_error_snapshot(DotnetBuildpackError::RestoreDotnetToolsCommand(
@@ -1067,6 +1797,52 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_configure_workloads_error() {
+        assert_error_snapshot(DotnetBuildpackError::ConfigureWorkloads(create_io_error()));
+    }
+
+    #[test]
+    fn test_configure_kestrel_error() {
+        assert_error_snapshot(DotnetBuildpackError::ConfigureKestrel(create_io_error()));
+    }
+
+    #[test]
+    fn test_restore_workloads_command_system_error() {
+        assert_error_snapshot(DotnetBuildpackError::RestoreWorkloadsCommand(
+            fun_run::CmdError::SystemError(
+                "Failed to start process".to_string(),
+                create_io_error(),
+            ),
+        ));
+    }
+
+    #[test]
+    fn test_restore_workloads_command_non_zero_exit_not_streamed_error() {
+        assert_error_snapshot(DotnetBuildpackError::RestoreWorkloadsCommand(
+            create_fun_run_cmd_error_captured_output(),
+        ));
+    }
+
+    #[test]
+    fn test_missing_native_aot_prerequisites_error() {
+        assert_error_snapshot(DotnetBuildpackError::MissingNativeAotPrerequisites(vec![
+            NativeAotPrerequisite::Clang,
+            NativeAotPrerequisite::Zlib,
+        ]));
+    }
+
+    #[test]
+    fn test_trim_analysis_warnings_error() {
+        assert_error_snapshot(DotnetBuildpackError::TrimAnalysisWarnings(
+            msbuild_diagnostics::parse_diagnostics(indoc! {"
+                /workspace/Program.cs(5,1): warning IL2026: Using member 'Foo' can break functionality when trimming application code. [/workspace/foo.csproj]
+                /workspace/Program.cs(9,1): warning IL3050: Calling 'Bar' might require dynamic code. [/workspace/foo.csproj]
+                /workspace/Program.cs(12,1): warning IL2026: Using member 'Baz' can break functionality when trimming application code. [/workspace/foo.csproj]
+            "}),
+        ));
+    }
+
     #[test]
     fn test_publish_command_system_error() {
         assert_error_snapshot(DotnetBuildpackError::PublishCommand(
@@ -1093,6 +1869,108 @@ mod tests {
         assert_error_snapshot(DotnetBuildpackError::CopyRuntimeFiles(create_io_error()));
     }
 
+    #[test]
+    fn test_read_runtime_config_error() {
+        assert_error_snapshot(DotnetBuildpackError::ReadRuntimeConfig(create_io_error()));
+    }
+
+    #[test]
+    fn test_parse_runtime_config_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseRuntimeConfig(
+            PathBuf::from("/workspace/bin/publish/foo.runtimeconfig.json"),
+            serde_json::from_str::<serde_json::Value>("invalid json").unwrap_err(),
+        ));
+    }
+
+    #[test]
+    fn test_read_launch_settings_error() {
+        assert_error_snapshot(DotnetBuildpackError::LaunchProfile(
+            LaunchProfileError::ReadLaunchSettings(create_io_error()),
+        ));
+    }
+
+    #[test]
+    fn test_parse_launch_settings_error() {
+        assert_error_snapshot(DotnetBuildpackError::LaunchProfile(
+            LaunchProfileError::ParseLaunchSettings(
+                PathBuf::from("/workspace/foo/Properties/launchSettings.json"),
+                serde_json::from_str::<serde_json::Value>("invalid json").unwrap_err(),
+            ),
+        ));
+    }
+
+    #[test]
+    fn test_launch_profile_not_found_error() {
+        assert_error_snapshot(DotnetBuildpackError::LaunchProfile(
+            LaunchProfileError::ProfileNotFound("production".to_string()),
+        ));
+    }
+
+    #[test]
+    fn test_parse_launch_profile_command_line_args_error() {
+        assert_error_snapshot(DotnetBuildpackError::LaunchProfile(
+            LaunchProfileError::ParseCommandLineArgs(
+                PathBuf::from("/workspace/foo/Properties/launchSettings.json"),
+                "http".to_string(),
+                shell_words::split("--name 'unterminated").unwrap_err(),
+            ),
+        ));
+    }
+
+    #[test]
+    fn test_check_native_dependencies_error() {
+        assert_error_snapshot(DotnetBuildpackError::CheckNativeDependencies(
+            create_io_error(),
+        ));
+    }
+
+    #[test]
+    fn test_launch_smoke_check_io_error() {
+        assert_error_snapshot(DotnetBuildpackError::LaunchSmokeCheck(
+            "foo".to_string(),
+            LaunchSmokeCheckError::Io(create_io_error()),
+        ));
+    }
+
+    #[test]
+    fn test_launch_smoke_check_exited_error() {
+        assert_error_snapshot(DotnetBuildpackError::LaunchSmokeCheck(
+            "foo".to_string(),
+            LaunchSmokeCheckError::Exited {
+                check: LaunchSmokeCheck::Help,
+                status: std::os::unix::process::ExitStatusExt::from_raw(127 << 8),
+                output: "./foo: error while loading shared libraries: libgdiplus.so: cannot open shared object file: No such file or directory".to_string(),
+            },
+        ));
+    }
+
+    #[test]
+    fn test_launch_smoke_check_web_app_exited_error() {
+        assert_error_snapshot(DotnetBuildpackError::LaunchSmokeCheck(
+            "foo".to_string(),
+            LaunchSmokeCheckError::Exited {
+                check: LaunchSmokeCheck::WebApp,
+                status: std::os::unix::process::ExitStatusExt::from_raw(134),
+                output: indoc! {"
+                    Unhandled exception. System.InvalidOperationException: No connection string named 'Default' could be found.
+                       at Program.<Main>$(String[] args) in /workspace/foo/Program.cs:line 12"}
+                .to_string(),
+            },
+        ));
+    }
+
+    #[test]
+    fn test_launch_smoke_check_not_listening_error() {
+        assert_error_snapshot(DotnetBuildpackError::LaunchSmokeCheck(
+            "foo".to_string(),
+            LaunchSmokeCheckError::NotListening {
+                port: 41234,
+                timeout: std::time::Duration::from_secs(30),
+                output: String::new(),
+            },
+        ));
+    }
+
     fn assert_error_snapshot(error: DotnetBuildpackError) {
         assert_writer_snapshot(|writer| {
             on_error_with_writer(libcnb::Error::BuildpackError(error), writer);
@@ -1133,6 +2011,88 @@ mod tests {
         quick_xml::de::DeError::Custom("XML parsing error".to_string())
     }
 
+    #[test]
+    fn test_publish_command_non_zero_exit_with_msbuild_errors_error() {
+        let stdout = indoc! {"
+              Determining projects to restore...
+              Restored /workspace/consoleapp.csproj (in 1.01 sec).
+            /workspace/Program.cs(1,9): error CS0117: 'Console' does not contain a definition for 'WriteFoo' [/workspace/consoleapp.csproj]
+            /workspace/Program.cs(2,1): error CS0103: The name 'foo' does not exist in the current context [/workspace/consoleapp.csproj]
+            /workspace/Program.cs(3,5): warning CS0168: The variable 'bar' is declared but never used [/workspace/consoleapp.csproj]
+            CSC : error FS0039: The value or constructor 'baz' is not defined. [/workspace/consoleapp.csproj]
+
+            Build FAILED.
+
+            /workspace/Program.cs(1,9): error CS0117: 'Console' does not contain a definition for 'WriteFoo' [/workspace/consoleapp.csproj]
+            /workspace/Program.cs(2,1): error CS0103: The name 'foo' does not exist in the current context [/workspace/consoleapp.csproj]
+                1 Warning(s)
+                3 Error(s)
+        "};
+        assert_error_snapshot(DotnetBuildpackError::PublishCommand(
+            fun_run::nonzero_streamed(
+                "foo".to_string(),
+                std::process::Output {
+                    status: std::os::unix::process::ExitStatusExt::from_raw(256),
+                    stdout: stdout.as_bytes().to_vec(),
+                    stderr: vec![],
+                },
+            )
+            .unwrap_err(),
+        ));
+    }
+
+    #[test]
+    fn test_publish_projects_error() {
+        let stdout = indoc! {"
+            /workspace/api/Program.cs(4,1): error CS0103: The name 'foo' does not exist in the current context [/workspace/api/api.csproj]
+        "};
+        assert_error_snapshot(DotnetBuildpackError::PublishProjects(
+            PublishProjectsError {
+                failed: vec![
+                    (
+                        "api".to_string(),
+                        fun_run::nonzero_streamed(
+                            "foo".to_string(),
+                            std::process::Output {
+                                status: std::os::unix::process::ExitStatusExt::from_raw(256),
+                                stdout: stdout.as_bytes().to_vec(),
+                                stderr: vec![],
+                            },
+                        )
+                        .unwrap_err(),
+                    ),
+                    (
+                        "worker".to_string(),
+                        fun_run::CmdError::SystemError(
+                            "Cannot find executable".to_string(),
+                            create_io_error(),
+                        ),
+                    ),
+                ],
+                skipped: vec!["web".to_string()],
+            },
+        ));
+    }
+
+    #[test]
+    fn test_parse_buildpack_configuration_invalid_publish_concurrency_error() {
+        assert_error_snapshot(DotnetBuildpackError::ParseBuildpackConfiguration(
+            DotnetBuildpackConfigurationError::PublishConcurrency("0".to_string()),
+        ));
+    }
+
+    #[test]
+    fn test_format_diagnostics_summary_truncates_diagnostics() {
+        let stdout = (1..=12)
+            .map(|line| format!("/workspace/Program.cs({line},1): error CS0103: The name 'foo' does not exist in the current context"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let summary = format_diagnostics_summary(&msbuild_diagnostics::parse_diagnostics(&stdout));
+
+        assert_eq!(summary.lines().count(), 21);
+        assert!(summary.ends_with("- ...and 2 more"));
+    }
+
     fn create_fun_run_cmd_error_captured_output() -> fun_run::CmdError {
         fun_run::nonzero_captured(
             "foo".to_string(),
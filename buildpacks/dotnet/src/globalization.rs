@@ -0,0 +1,225 @@
+use crate::dotnet::project::Project;
+use crate::dotnet::runtime_config::RuntimeConfig;
+use crate::dotnet_buildpack_configuration::GlobalizationOptions;
+use libcnb::Target;
+use std::fmt;
+
+/// The Ubuntu versions of the Heroku run images, which all have ICU installed. ICU sonames include
+/// the major version (e.g. `libicuuc.so.74` on Ubuntu 24.04), which differs between distribution
+/// releases, so ICU isn't listed with the other run image libraries.
+const HEROKU_RUN_IMAGE_DISTRO_VERSIONS: &[&str] = &["22.04", "24.04", "26.04"];
+
+/// How a published app handles culture-specific data (e.g. for formatting and string
+/// comparisons) at launch.
+/// See <https://learn.microsoft.com/en-us/dotnet/core/extensions/globalization-icu>
+#[derive(Debug, PartialEq)]
+pub(crate) enum GlobalizationMode {
+    /// The app uses invariant culture data, and doesn't load ICU
+    Invariant,
+    /// The app loads the ICU libraries (of the given version) bundled with the app
+    AppLocalIcu(String),
+    /// The app loads the ICU libraries installed on the run image, and fails to start if they're
+    /// missing
+    Icu,
+}
+
+impl fmt::Display for GlobalizationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobalizationMode::Invariant => write!(f, "globalization-invariant mode"),
+            GlobalizationMode::AppLocalIcu(version) => write!(f, "app-local ICU {version}"),
+            GlobalizationMode::Icu => write!(f, "ICU"),
+        }
+    }
+}
+
+/// Resolves the globalization mode of a project's published app. Settings in the app's
+/// `*.runtimeconfig.json` file take precedence over the configured options (which are set as
+/// environment variables at launch), as they do in the .NET runtime. The project properties are
+/// used when the runtime config file can't be read.
+pub(crate) fn project_globalization_mode(
+    project: &Project,
+    runtime_config: Option<&RuntimeConfig>,
+    globalization_options: Option<&GlobalizationOptions>,
+) -> GlobalizationMode {
+    let invariant = runtime_config
+        .and_then(RuntimeConfig::globalization_invariant)
+        .or_else(|| globalization_options?.invariant)
+        .unwrap_or(project.invariant_globalization);
+    if invariant {
+        return GlobalizationMode::Invariant;
+    }
+
+    runtime_config
+        .and_then(RuntimeConfig::app_local_icu)
+        .or_else(|| globalization_options?.app_local_icu.as_deref())
+        .map_or(GlobalizationMode::Icu, |version| {
+            GlobalizationMode::AppLocalIcu(version.to_string())
+        })
+}
+
+/// Returns whether ICU is known to be available on the run image: either the target is a Heroku
+/// run image with ICU installed, or ICU is listed in the configured run image libraries.
+pub(crate) fn run_image_has_icu(target: &Target, run_image_libraries: &[String]) -> bool {
+    (target.distro_name == "ubuntu"
+        && HEROKU_RUN_IMAGE_DISTRO_VERSIONS.contains(&target.distro_version.as_str()))
+        || run_image_libraries
+            .iter()
+            .any(|library| library.starts_with("libicuuc.so"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dotnet::project::{ProjectBuilder, ProjectType};
+
+    fn create_runtime_config(config_properties: &str) -> RuntimeConfig {
+        format!(r#"{{ "runtimeOptions": {{ "configProperties": {config_properties} }} }}"#)
+            .parse()
+            .unwrap()
+    }
+
+    fn create_target(distro_name: &str, distro_version: &str) -> Target {
+        Target {
+            os: "linux".to_string(),
+            arch: "amd64".to_string(),
+            arch_variant: None,
+            distro_name: distro_name.to_string(),
+            distro_version: distro_version.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_project_globalization_mode_defaults_to_icu() {
+        assert_eq!(
+            project_globalization_mode(
+                &ProjectBuilder::new("/tmp/project/app.csproj")
+                    .project_type(ProjectType::ConsoleApplication)
+                    .build(),
+                Some(&create_runtime_config("{}")),
+                Some(&GlobalizationOptions::default())
+            ),
+            GlobalizationMode::Icu
+        );
+    }
+
+    #[test]
+    fn test_project_globalization_mode_from_project_property() {
+        assert_eq!(
+            project_globalization_mode(
+                &ProjectBuilder::new("/tmp/project/app.csproj")
+                    .project_type(ProjectType::ConsoleApplication)
+                    .invariant_globalization()
+                    .build(),
+                None,
+                None
+            ),
+            GlobalizationMode::Invariant
+        );
+    }
+
+    #[test]
+    fn test_project_globalization_mode_from_runtime_config() {
+        let project = ProjectBuilder::new("/tmp/project/app.csproj")
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+
+        assert_eq!(
+            project_globalization_mode(
+                &project,
+                Some(&create_runtime_config(
+                    r#"{ "System.Globalization.Invariant": true }"#
+                )),
+                None
+            ),
+            GlobalizationMode::Invariant
+        );
+        assert_eq!(
+            project_globalization_mode(
+                &project,
+                Some(&create_runtime_config(
+                    r#"{ "System.Globalization.AppLocalIcu": "72.1.0.3" }"#
+                )),
+                None
+            ),
+            GlobalizationMode::AppLocalIcu("72.1.0.3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_project_globalization_mode_runtime_config_takes_precedence() {
+        assert_eq!(
+            project_globalization_mode(
+                &ProjectBuilder::new("/tmp/project/app.csproj")
+                    .project_type(ProjectType::ConsoleApplication)
+                    .build(),
+                Some(&create_runtime_config(
+                    r#"{ "System.Globalization.Invariant": false }"#
+                )),
+                Some(&GlobalizationOptions {
+                    invariant: Some(true),
+                    app_local_icu: None,
+                })
+            ),
+            GlobalizationMode::Icu
+        );
+    }
+
+    #[test]
+    fn test_project_globalization_mode_from_globalization_options() {
+        let project = ProjectBuilder::new("/tmp/project/app.csproj")
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        let runtime_config = create_runtime_config("{}");
+
+        assert_eq!(
+            project_globalization_mode(
+                &project,
+                Some(&runtime_config),
+                Some(&GlobalizationOptions {
+                    invariant: Some(true),
+                    app_local_icu: Some("72.1.0.3".to_string()),
+                })
+            ),
+            GlobalizationMode::Invariant
+        );
+        assert_eq!(
+            project_globalization_mode(
+                &project,
+                Some(&runtime_config),
+                Some(&GlobalizationOptions {
+                    invariant: None,
+                    app_local_icu: Some("72.1.0.3".to_string()),
+                })
+            ),
+            GlobalizationMode::AppLocalIcu("72.1.0.3".to_string())
+        );
+        assert_eq!(
+            project_globalization_mode(
+                &ProjectBuilder::new("/tmp/project/app.csproj")
+                    .project_type(ProjectType::ConsoleApplication)
+                    .invariant_globalization()
+                    .build(),
+                Some(&runtime_config),
+                Some(&GlobalizationOptions {
+                    invariant: Some(false),
+                    app_local_icu: None,
+                })
+            ),
+            GlobalizationMode::Icu
+        );
+    }
+
+    #[test]
+    fn test_run_image_has_icu() {
+        assert!(run_image_has_icu(&create_target("ubuntu", "22.04"), &[]));
+        assert!(run_image_has_icu(&create_target("ubuntu", "24.04"), &[]));
+        assert!(run_image_has_icu(&create_target("ubuntu", "26.04"), &[]));
+        assert!(!run_image_has_icu(&create_target("ubuntu", "20.04"), &[]));
+        assert!(!run_image_has_icu(&create_target("debian", "12"), &[]));
+        assert!(run_image_has_icu(
+            &create_target("debian", "12"),
+            &["libgdiplus.so.0".to_string(), "libicuuc.so.72".to_string()]
+        ));
+    }
+}
@@ -1,38 +1,57 @@
+use crate::dotnet::launch_settings::LaunchSettings;
 use crate::dotnet::project::ProjectType;
+use crate::dotnet::runtime_config::RuntimeConfig;
 use crate::dotnet::solution::Solution;
+use crate::dotnet_buildpack_configuration::{LaunchProcessMode, PublishMode};
 use crate::{Project, utils};
-use libcnb::data::launch::{Process, ProcessBuilder, ProcessType};
+use libcnb::data::launch::{Process, ProcessBuilder, ProcessType, WorkingDirectory};
 use libcnb::data::process_type;
+use libcnb::layer_env::{LayerEnv, ModificationBehavior, Scope};
+use std::collections::HashMap;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use tracing::instrument;
 
+/// Launch profile environment variables that aren't carried over to launch processes: web server
+/// URLs and ports (the `$PORT` assigned by the platform takes precedence), and the hosting
+/// environment name (which is set to `Development` by most launch profiles).
+const IGNORED_LAUNCH_PROFILE_ENVIRONMENT_VARIABLES: [&str; 6] = [
+    "ASPNETCORE_URLS",
+    "ASPNETCORE_HTTP_PORTS",
+    "ASPNETCORE_HTTPS_PORTS",
+    "DOTNET_URLS",
+    "ASPNETCORE_ENVIRONMENT",
+    "DOTNET_ENVIRONMENT",
+];
+
+/// Launch profile arguments that aren't carried over to launch processes, since they would take
+/// precedence over the `$PORT` configured at launch.
+const IGNORED_LAUNCH_PROFILE_ARGUMENTS: [&str; 1] = ["--urls"];
+
 /// Detects processes in a solution's projects
-pub(crate) fn detect_solution_processes(app_dir: &Path, solution: &Solution) -> Vec<Process> {
-    // Check if the solution contains exactly one web application.
-    let has_single_web_app = solution
-        .projects
-        .iter()
-        .filter(|p| p.project_type == ProjectType::WebApplication)
-        .count()
-        == 1;
-
-    solution
-        .projects
-        .iter()
-        .filter(|project| {
-            matches!(
-                project.project_type,
-                ProjectType::ConsoleApplication
-                    | ProjectType::WebApplication
-                    | ProjectType::WorkerService
-            )
-        })
+pub(crate) fn detect_solution_processes(
+    app_dir: &Path,
+    solution: &Solution,
+    publish_mode: Option<PublishMode>,
+    launch_profiles: &HashMap<PathBuf, ProjectLaunchProfile>,
+    launch_process_mode: LaunchProcessMode,
+) -> Vec<Process> {
+    let has_single_web_app = has_single_web_app(solution);
+
+    launchable_projects(solution)
         .filter_map(|project| {
-            let mut process = project_launch_process(app_dir, project).ok()?;
+            let mut process = project_launch_process(
+                app_dir,
+                project,
+                project_publish_mode(project, publish_mode),
+                launch_profiles.get(&project.path),
+                launch_process_mode,
+            )
+            .ok()?;
 
             // If it's a web app and the only one, override its type and make it default.
-            if has_single_web_app && project.project_type == ProjectType::WebApplication {
+            if has_single_web_app && is_web_app(project) {
                 process.r#type = process_type!("web");
                 process.default = true;
             }
@@ -42,51 +61,432 @@ pub(crate) fn detect_solution_processes(app_dir: &Path, solution: &Solution) ->
         .collect()
 }
 
+/// Returns the process types that serve web traffic: the processes of the solution's web apps, and
+/// the `web` process (which can also be declared in a Procfile). Empty if the solution doesn't
+/// contain a web app.
+pub(crate) fn web_process_types(solution: &Solution) -> Vec<ProcessType> {
+    let mut process_types = launchable_projects(solution)
+        .filter(|project| is_web_app(project))
+        .map(|project| launch_process_type(solution, project))
+        .collect::<Vec<_>>();
+    if !process_types.is_empty() && !process_types.contains(&process_type!("web")) {
+        process_types.push(process_type!("web"));
+    }
+    process_types
+}
+
+/// Checks if the solution contains exactly one web application
+fn has_single_web_app(solution: &Solution) -> bool {
+    launchable_projects(solution)
+        .filter(|project| is_web_app(project))
+        .count()
+        == 1
+}
+
+/// Returns whether a project is a web app: either a web project, or a console project that uses
+/// the ASP.NET Core shared framework (according to its published `*.runtimeconfig.json` file).
+pub(crate) fn is_web_app(project: &Project) -> bool {
+    match project.project_type {
+        ProjectType::WebApplication => true,
+        ProjectType::ConsoleApplication => {
+            fs_err::read_to_string(project_runtime_config_path(project))
+                .ok()
+                .and_then(|contents| contents.parse::<RuntimeConfig>().ok())
+                .is_some_and(|runtime_config| {
+                    runtime_config.uses_framework("Microsoft.AspNetCore.App")
+                })
+        }
+        _ => false,
+    }
+}
+
+/// The launch profile settings applied to a project's launch process
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ProjectLaunchProfile {
+    pub(crate) name: String,
+    pub(crate) launch_settings_path: PathBuf,
+    arguments: Vec<String>,
+    environment_variables: Vec<(String, String)>,
+    pub(crate) ignored_arguments: Vec<String>,
+    pub(crate) ignored_environment_variables: Vec<String>,
+}
+
+#[derive(Debug)]
+pub(crate) enum LaunchProfileError {
+    ReadLaunchSettings(io::Error),
+    ParseLaunchSettings(PathBuf, serde_json::Error),
+    ProfileNotFound(String),
+    ParseCommandLineArgs(PathBuf, String, shell_words::ParseError),
+}
+
+/// Loads the launch profiles of a solution's launchable projects, keyed by project path.
+///
+/// Each project uses the configured profile if its launch settings define it, or the first
+/// profile that runs the project (like `dotnet run`) when no profile is configured.
+pub(crate) fn load_launch_profiles(
+    solution: &Solution,
+    configured_profile: Option<&str>,
+) -> Result<HashMap<PathBuf, ProjectLaunchProfile>, LaunchProfileError> {
+    let mut launch_profiles = HashMap::new();
+
+    for project in launchable_projects(solution) {
+        let launch_settings_path = project_launch_settings_path(project);
+        if !launch_settings_path.is_file() {
+            continue;
+        }
+        let launch_settings = fs_err::read_to_string(&launch_settings_path)
+            .map_err(LaunchProfileError::ReadLaunchSettings)?
+            .parse::<LaunchSettings>()
+            .map_err(|error| {
+                LaunchProfileError::ParseLaunchSettings(launch_settings_path.clone(), error)
+            })?;
+
+        let Some((name, profile)) = (match configured_profile {
+            Some(name) => launch_settings.profile(name).map(|profile| (name, profile)),
+            None => launch_settings.default_profile(),
+        }) else {
+            continue;
+        };
+
+        let (arguments, ignored_arguments) = split_ignored_arguments(
+            profile
+                .command_line_args
+                .as_deref()
+                .map(shell_words::split)
+                .transpose()
+                .map_err(|error| {
+                    LaunchProfileError::ParseCommandLineArgs(
+                        launch_settings_path.clone(),
+                        name.to_string(),
+                        error,
+                    )
+                })?
+                .unwrap_or_default(),
+        );
+
+        let (ignored_environment_variables, environment_variables): (Vec<_>, Vec<_>) = profile
+            .environment_variables
+            .clone()
+            .into_iter()
+            .partition(|(name, _)| {
+                IGNORED_LAUNCH_PROFILE_ENVIRONMENT_VARIABLES
+                    .iter()
+                    .any(|ignored| ignored.eq_ignore_ascii_case(name))
+            });
+
+        launch_profiles.insert(
+            project.path.clone(),
+            ProjectLaunchProfile {
+                name: name.to_string(),
+                launch_settings_path,
+                arguments,
+                environment_variables,
+                ignored_arguments,
+                ignored_environment_variables: ignored_environment_variables
+                    .into_iter()
+                    .map(|(name, _)| name)
+                    .collect(),
+            },
+        );
+    }
+
+    match configured_profile {
+        Some(name) if launch_profiles.is_empty() => {
+            Err(LaunchProfileError::ProfileNotFound(name.to_string()))
+        }
+        _ => Ok(launch_profiles),
+    }
+}
+
+/// Separates the ignored options (and their values) from launch profile arguments. Options can be
+/// written as `--urls value` or `--urls=value`.
+fn split_ignored_arguments(arguments: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let mut kept = Vec::new();
+    let mut ignored = Vec::new();
+    let mut arguments = arguments.into_iter();
+
+    while let Some(argument) = arguments.next() {
+        let option = argument
+            .split_once('=')
+            .map_or(argument.as_str(), |(option, _)| option);
+        if IGNORED_LAUNCH_PROFILE_ARGUMENTS
+            .iter()
+            .any(|ignored| ignored.eq_ignore_ascii_case(option))
+        {
+            let has_inline_value = option.len() < argument.len();
+            ignored.push(argument);
+            if !has_inline_value {
+                ignored.extend(arguments.next());
+            }
+        } else {
+            kept.push(argument);
+        }
+    }
+    (kept, ignored)
+}
+
+/// Returns the type of the process detected for a launchable project: `web` for the solution's
+/// only web app, or a type derived from the project's assembly name otherwise.
+pub(crate) fn launch_process_type(solution: &Solution, project: &Project) -> ProcessType {
+    if is_web_app(project) && has_single_web_app(solution) {
+        process_type!("web")
+    } else {
+        project_process_type(project)
+    }
+}
+
+/// Generates a launch `LayerEnv` with the environment variables of each project's launch profile,
+/// scoped to the project's process. Variables that are already set (e.g. as config vars) take
+/// precedence.
+pub(crate) fn generate_launch_profiles_env(
+    solution: &Solution,
+    launch_profiles: &HashMap<PathBuf, ProjectLaunchProfile>,
+) -> LayerEnv {
+    launchable_projects(solution)
+        .filter_map(|project| Some((project, launch_profiles.get(&project.path)?)))
+        .fold(LayerEnv::new(), |layer_env, (project, launch_profile)| {
+            let process_type = launch_process_type(solution, project);
+            launch_profile.environment_variables.iter().fold(
+                layer_env,
+                |layer_env, (name, value)| {
+                    layer_env.chainable_insert(
+                        Scope::Process(process_type.to_string()),
+                        ModificationBehavior::Default,
+                        name,
+                        value,
+                    )
+                },
+            )
+        })
+}
+
+/// Returns the (expected) absolute path to the project's launch settings: `Properties/launchSettings.json`
+/// for project files, or `<name>.run.json` for file-based apps.
+fn project_launch_settings_path(project: &Project) -> PathBuf {
+    let project_dir = project
+        .path
+        .parent()
+        .expect("Project file should always have a parent directory");
+
+    if project.path.extension().is_some_and(|ext| ext == "cs") {
+        project.path.with_extension("run.json")
+    } else {
+        project_dir.join("Properties").join("launchSettings.json")
+    }
+}
+
+/// Returns the projects in a solution that are published as launchable apps
+pub(crate) fn launchable_projects(solution: &Solution) -> impl Iterator<Item = &Project> {
+    solution.projects.iter().filter(|project| {
+        matches!(
+            project.project_type,
+            ProjectType::ConsoleApplication
+                | ProjectType::WebApplication
+                | ProjectType::WorkerService
+        )
+    })
+}
+
+/// Returns the publish mode of a project: either the configured publish mode, or the mode implied
+/// by the project's own `SelfContained` and `PublishSingleFile` properties.
+pub(crate) fn project_publish_mode(
+    project: &Project,
+    configured_publish_mode: Option<PublishMode>,
+) -> PublishMode {
+    // Native AOT apps are always compiled to a single native executable that doesn't need the
+    // shared runtime, regardless of the configured publish mode.
+    if project.publish_aot {
+        return PublishMode::SingleFile;
+    }
+
+    configured_publish_mode.unwrap_or(
+        match (project.self_contained, project.publish_single_file) {
+            (true, true) => PublishMode::SingleFile,
+            (true, false) => PublishMode::SelfContained,
+            // Framework-dependent single-file apps still require the shared runtime
+            (false, _) => PublishMode::FrameworkDependent,
+        },
+    )
+}
+
+/// Returns the publish mode to pass to `dotnet publish` for a project. Native AOT projects are
+/// published without publish mode arguments, since `PublishAot` already produces the single
+/// native executable that [`project_publish_mode`] reports for them.
+pub(crate) fn publish_command_mode(
+    project: &Project,
+    configured_publish_mode: Option<PublishMode>,
+) -> Option<PublishMode> {
+    configured_publish_mode.filter(|_| !project.publish_aot)
+}
+
+/// How a published project is started
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Launcher {
+    /// Run the project's executable directly
+    Executable,
+    /// Run the project's assembly with the `dotnet` host
+    DotnetHost,
+}
+
 #[instrument(skip(app_dir), err)]
-fn project_launch_process(app_dir: &Path, project: &Project) -> io::Result<Process> {
+fn project_launch_process(
+    app_dir: &Path,
+    project: &Project,
+    publish_mode: PublishMode,
+    launch_profile: Option<&ProjectLaunchProfile>,
+    launch_process_mode: LaunchProcessMode,
+) -> io::Result<Process> {
+    let (entrypoint_path, launcher) = project_entrypoint(project, publish_mode)?;
+
+    let relative_entrypoint_path = entrypoint_path
+        .strip_prefix(app_dir)
+        .expect("Entrypoint path should be inside the app directory")
+        .to_path_buf();
+
+    let arguments = launch_profile.map_or(&[][..], |launch_profile| &launch_profile.arguments);
+    let process_type = project_process_type(project);
+
+    Ok(match launch_process_mode {
+        LaunchProcessMode::Exec => {
+            build_exec_process(process_type, &relative_entrypoint_path, launcher, arguments)
+        }
+        LaunchProcessMode::Shell => {
+            let command = build_command(&relative_entrypoint_path, launcher, arguments);
+            ProcessBuilder::new(process_type, ["bash", "-c", &command]).build()
+        }
+    })
+}
+
+/// Returns a command that starts the project's published app from its publish directory, with
+/// the arguments and environment variables of its launch profile (like its launch process).
+pub(crate) fn project_launch_command(
+    project: &Project,
+    publish_mode: PublishMode,
+    launch_profile: Option<&ProjectLaunchProfile>,
+) -> io::Result<Command> {
+    let (entrypoint_path, launcher) = project_entrypoint(project, publish_mode)?;
+
+    let mut command = match launcher {
+        Launcher::Executable => Command::new(&entrypoint_path),
+        Launcher::DotnetHost => {
+            let mut command = Command::new("dotnet");
+            command.arg(
+                entrypoint_path
+                    .file_name()
+                    .expect("Entrypoint path should always have a file name"),
+            );
+            command
+        }
+    };
+    command.current_dir(
+        entrypoint_path
+            .parent()
+            .expect("Entrypoint path should always have a parent directory"),
+    );
+    if let Some(launch_profile) = launch_profile {
+        command
+            .args(&launch_profile.arguments)
+            .envs(launch_profile.environment_variables.iter().cloned());
+    }
+    Ok(command)
+}
+
+/// Returns the absolute path to the file that starts the project's published app, and how it's
+/// started.
+fn project_entrypoint(
+    project: &Project,
+    publish_mode: PublishMode,
+) -> io::Result<(PathBuf, Launcher)> {
     let executable_path = project_executable_path(project);
 
-    if !executable_path.exists() {
+    let (entrypoint_path, launcher) = match publish_mode {
+        // Framework-dependent apps published without an apphost (`UseAppHost=false`) only
+        // include the app's assembly, which is started with the `dotnet` host.
+        PublishMode::FrameworkDependent if !executable_path.exists() => {
+            (project_assembly_path(project), Launcher::DotnetHost)
+        }
+        // Self-contained and single-file apps bundle the runtime, and are always started with
+        // their own executable.
+        PublishMode::FrameworkDependent | PublishMode::SelfContained | PublishMode::SingleFile => {
+            (executable_path, Launcher::Executable)
+        }
+    };
+
+    if !entrypoint_path.exists() {
         return Err(io::Error::new(
             io::ErrorKind::NotFound,
-            format!("Executable not found: {}", executable_path.display()),
+            format!("Entrypoint not found: {}", entrypoint_path.display()),
         ));
     }
 
-    let relative_executable_path = executable_path
-        .strip_prefix(app_dir)
-        .expect("Executable path should be inside the app directory")
-        .to_path_buf();
+    Ok((entrypoint_path, launcher))
+}
 
-    let command = build_command(&relative_executable_path, project.project_type);
+/// Constructs a process that executes the app directly (without a shell), from its publish
+/// directory. The CNB launcher expands `$(VAR)` references in the process arguments, so arguments
+/// are escaped to pass them to the app as-is.
+fn build_exec_process(
+    process_type: ProcessType,
+    relative_entrypoint_path: &Path,
+    launcher: Launcher,
+    arguments: &[String],
+) -> Process {
+    let parent_dir = relative_entrypoint_path
+        .parent()
+        .expect("Entrypoint path should always have a parent directory");
 
-    let process_type = project_process_type(project);
+    let file_name = relative_entrypoint_path
+        .file_name()
+        .expect("Entrypoint path should always have a file name")
+        .to_str()
+        .expect("Path should be valid UTF-8");
+
+    let command = match launcher {
+        Launcher::Executable => vec![format!("./{file_name}")],
+        Launcher::DotnetHost => vec!["dotnet".to_string(), file_name.to_string()],
+    };
 
-    Ok(ProcessBuilder::new(process_type, ["bash", "-c", &command]).build())
+    ProcessBuilder::new(process_type, command)
+        .args(arguments.iter().map(|argument| argument.replace('$', "$$")))
+        .working_directory(WorkingDirectory::Directory(parent_dir.to_path_buf()))
+        .build()
 }
 
-/// Constructs the shell command for launching the process
-fn build_command(relative_executable_path: &Path, project_type: ProjectType) -> String {
-    let parent_dir = relative_executable_path
+/// Constructs the shell command for launching the process. Arguments are quoted, so they're passed
+/// to the app as-is.
+fn build_command(
+    relative_entrypoint_path: &Path,
+    launcher: Launcher,
+    arguments: &[String],
+) -> String {
+    let parent_dir = relative_entrypoint_path
         .parent()
-        .expect("Executable path should always have a parent directory")
+        .expect("Entrypoint path should always have a parent directory")
         .to_str()
         .expect("Path should be valid UTF-8");
 
-    let file_name = relative_executable_path
+    let file_name = relative_entrypoint_path
         .file_name()
-        .expect("Executable path should always have a file name")
+        .expect("Entrypoint path should always have a file name")
         .to_str()
         .expect("Path should be valid UTF-8");
 
-    let mut command = format!(
-        "cd {}; ./{}",
-        shell_words::quote(parent_dir),
-        shell_words::quote(file_name)
-    );
+    let mut command = match launcher {
+        Launcher::Executable => format!(
+            "cd {}; ./{}",
+            shell_words::quote(parent_dir),
+            shell_words::quote(file_name)
+        ),
+        Launcher::DotnetHost => format!(
+            "cd {}; dotnet {}",
+            shell_words::quote(parent_dir),
+            shell_words::quote(file_name)
+        ),
+    };
 
-    if project_type == ProjectType::WebApplication {
-        command.push_str(" --urls http://*:$PORT");
+    for argument in arguments {
+        command.push(' ');
+        command.push_str(&shell_words::quote(argument));
     }
 
     command
@@ -102,32 +502,40 @@ fn project_process_type(project: &Project) -> ProcessType {
 
 /// Returns the (expected) absolute path to the project's compiled executable
 fn project_executable_path(project: &Project) -> PathBuf {
+    project_publish_dir(project).join(&project.assembly_name)
+}
+
+/// Returns the (expected) absolute path to the project's compiled assembly
+fn project_assembly_path(project: &Project) -> PathBuf {
+    project_publish_dir(project).join(format!("{}.dll", project.assembly_name))
+}
+
+/// Returns the (expected) absolute path to the project's published `*.runtimeconfig.json` file
+pub(crate) fn project_runtime_config_path(project: &Project) -> PathBuf {
+    project_publish_dir(project).join(format!("{}.runtimeconfig.json", project.assembly_name))
+}
+
+/// Returns the (expected) absolute path to the project's publish directory
+pub(crate) fn project_publish_dir(project: &Project) -> PathBuf {
     project
         .path
         .parent()
         .expect("Project file should always have a parent directory")
         .join("bin")
         .join("publish")
-        .join(&project.assembly_name)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dotnet::project::ProjectBuilder;
     use libcnb::data::launch::{Process, WorkingDirectory};
     use libcnb::data::process_type;
+    use libcnb_test::assert_matches;
+    use std::ffi::OsStr;
     use std::fs;
     use std::path::PathBuf;
 
-    fn create_test_project(path: &str, assembly_name: &str, project_type: ProjectType) -> Project {
-        Project {
-            path: PathBuf::from(path),
-            target_framework: "net9.0".to_string(),
-            project_type,
-            assembly_name: assembly_name.to_string(),
-        }
-    }
-
     fn create_executable_for_project(project: &Project) {
         let executable_path = project_executable_path(project);
         fs::create_dir_all(executable_path.parent().unwrap()).unwrap();
@@ -141,30 +549,39 @@ mod tests {
 
         let solution = Solution {
             path: app_dir.join("foo.sln"),
-            projects: vec![create_test_project(
-                &format!("{}/bar/bar.csproj", app_dir.display()),
-                "bar",
-                ProjectType::WebApplication,
-            )],
+            projects: vec![
+                ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+                    .project_type(ProjectType::WebApplication)
+                    .build(),
+            ],
+            referenced_projects: vec![],
         };
 
-        assert!(detect_solution_processes(app_dir, &solution).is_empty());
+        assert!(
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Shell
+            )
+            .is_empty()
+        );
     }
 
     #[test]
     fn test_detect_solution_processes_single_web_app() {
         let temp_dir = tempfile::tempdir().unwrap();
         let app_dir = temp_dir.path();
-        let project = create_test_project(
-            &format!("{}/bar/bar.csproj", app_dir.display()),
-            "bar",
-            ProjectType::WebApplication,
-        );
+        let project = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::WebApplication)
+            .build();
         create_executable_for_project(&project);
 
         let solution = Solution {
             path: app_dir.join("foo.sln"),
             projects: vec![project],
+            referenced_projects: vec![],
         };
 
         let expected_processes = vec![Process {
@@ -172,7 +589,7 @@ mod tests {
             command: vec![
                 "bash".to_string(),
                 "-c".to_string(),
-                "cd bar/bin/publish; ./bar --urls http://*:$PORT".to_string(),
+                "cd bar/bin/publish; ./bar".to_string(),
             ],
             args: vec![],
             default: true,
@@ -180,7 +597,13 @@ mod tests {
         }];
 
         assert_eq!(
-            detect_solution_processes(app_dir, &solution),
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Shell
+            ),
             expected_processes
         );
     }
@@ -189,28 +612,31 @@ mod tests {
     fn test_detect_solution_processes_multiple_web_apps() {
         let temp_dir = tempfile::tempdir().unwrap();
         let app_dir = temp_dir.path();
-        let project1 = create_test_project(
-            &format!("{}/bar/bar.csproj", app_dir.display()),
-            "bar",
-            ProjectType::WebApplication,
-        );
-        let project2 = create_test_project(
-            &format!("{}/baz/baz.csproj", app_dir.display()),
-            "baz",
-            ProjectType::WebApplication,
-        );
+        let project1 = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::WebApplication)
+            .build();
+        let project2 = ProjectBuilder::new(format!("{}/baz/baz.csproj", app_dir.display()))
+            .project_type(ProjectType::WebApplication)
+            .build();
         create_executable_for_project(&project1);
         create_executable_for_project(&project2);
 
         let solution = Solution {
             path: app_dir.join("foo.sln"),
             projects: vec![project1, project2],
+            referenced_projects: vec![],
         };
         assert_eq!(
-            detect_solution_processes(app_dir, &solution)
-                .iter()
-                .map(|process| process.r#type.clone())
-                .collect::<Vec<ProcessType>>(),
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Shell
+            )
+            .iter()
+            .map(|process| process.r#type.clone())
+            .collect::<Vec<ProcessType>>(),
             vec![process_type!("bar"), process_type!("baz")]
         );
     }
@@ -219,54 +645,145 @@ mod tests {
     fn test_detect_solution_processes_single_web_app_and_console_app() {
         let temp_dir = tempfile::tempdir().unwrap();
         let app_dir = temp_dir.path();
-        let project1 = create_test_project(
-            &format!("{}/qux/qux.csproj", app_dir.display()),
-            "qux",
-            ProjectType::Unknown,
-        );
-        let project2 = create_test_project(
-            &format!("{}/bar/bar.csproj", app_dir.display()),
-            "bar",
-            ProjectType::WebApplication,
-        );
-        let project3 = create_test_project(
-            &format!("{}/baz/baz.csproj", app_dir.display()),
-            "baz",
-            ProjectType::ConsoleApplication,
-        );
+        let project1 = ProjectBuilder::new(format!("{}/qux/qux.csproj", app_dir.display()))
+            .project_type(ProjectType::Unknown)
+            .build();
+        let project2 = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::WebApplication)
+            .build();
+        let project3 = ProjectBuilder::new(format!("{}/baz/baz.csproj", app_dir.display()))
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
         create_executable_for_project(&project2);
         create_executable_for_project(&project3);
 
         let solution = Solution {
             path: app_dir.join("foo.sln"),
             projects: vec![project1, project2, project3],
+            referenced_projects: vec![],
         };
         assert_eq!(
-            detect_solution_processes(app_dir, &solution)
-                .iter()
-                .map(|process| process.r#type.clone())
-                .collect::<Vec<ProcessType>>(),
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Shell
+            )
+            .iter()
+            .map(|process| process.r#type.clone())
+            .collect::<Vec<ProcessType>>(),
             vec![process_type!("web"), process_type!("baz")]
         );
     }
 
+    #[test]
+    fn test_launch_process_type() {
+        let solution = Solution {
+            path: PathBuf::from("/tmp/foo.sln"),
+            projects: vec![
+                ProjectBuilder::new("/tmp/bar/bar.csproj")
+                    .project_type(ProjectType::WebApplication)
+                    .build(),
+                ProjectBuilder::new("/tmp/baz/baz.csproj")
+                    .project_type(ProjectType::ConsoleApplication)
+                    .build(),
+            ],
+            referenced_projects: vec![],
+        };
+        assert_eq!(
+            launch_process_type(&solution, &solution.projects[0]),
+            process_type!("web")
+        );
+        assert_eq!(
+            launch_process_type(&solution, &solution.projects[1]),
+            process_type!("baz")
+        );
+
+        let solution = Solution {
+            path: PathBuf::from("/tmp/foo.sln"),
+            projects: vec![
+                ProjectBuilder::new("/tmp/bar/bar.csproj")
+                    .project_type(ProjectType::WebApplication)
+                    .build(),
+                ProjectBuilder::new("/tmp/qux/qux.csproj")
+                    .project_type(ProjectType::WebApplication)
+                    .build(),
+            ],
+            referenced_projects: vec![],
+        };
+        assert_eq!(
+            launch_process_type(&solution, &solution.projects[0]),
+            process_type!("bar")
+        );
+    }
+
+    #[test]
+    fn test_web_process_types() {
+        let solution = Solution {
+            path: PathBuf::from("/tmp/foo.sln"),
+            projects: vec![
+                ProjectBuilder::new("/tmp/bar/bar.csproj")
+                    .project_type(ProjectType::WebApplication)
+                    .build(),
+                ProjectBuilder::new("/tmp/baz/baz.csproj")
+                    .project_type(ProjectType::ConsoleApplication)
+                    .build(),
+            ],
+            referenced_projects: vec![],
+        };
+        assert_eq!(web_process_types(&solution), vec![process_type!("web")]);
+
+        let solution = Solution {
+            path: PathBuf::from("/tmp/foo.sln"),
+            projects: vec![
+                ProjectBuilder::new("/tmp/bar/bar.csproj")
+                    .project_type(ProjectType::WebApplication)
+                    .build(),
+                ProjectBuilder::new("/tmp/qux/qux.csproj")
+                    .project_type(ProjectType::WebApplication)
+                    .build(),
+            ],
+            referenced_projects: vec![],
+        };
+        assert_eq!(
+            web_process_types(&solution),
+            vec![
+                process_type!("bar"),
+                process_type!("qux"),
+                process_type!("web")
+            ]
+        );
+
+        let solution = Solution {
+            path: PathBuf::from("/tmp/foo.sln"),
+            projects: vec![
+                ProjectBuilder::new("/tmp/baz/baz.csproj")
+                    .project_type(ProjectType::WorkerService)
+                    .build(),
+            ],
+            referenced_projects: vec![],
+        };
+        assert!(web_process_types(&solution).is_empty());
+    }
+
     #[test]
     fn test_detect_solution_processes_with_spaces() {
         let temp_dir = tempfile::tempdir().unwrap();
         let app_dir = temp_dir.path();
-        let project = create_test_project(
-            &format!(
-                "{}/My Project With Spaces/project.csproj",
-                app_dir.display()
-            ),
-            "My App",
-            ProjectType::ConsoleApplication,
-        );
+        let project = ProjectBuilder::new(format!(
+            "{}/My Project With Spaces/project.csproj",
+            app_dir.display()
+        ))
+        .assembly_name("My App")
+        .project_type(ProjectType::ConsoleApplication)
+        .build();
         create_executable_for_project(&project);
 
         let solution = Solution {
             path: app_dir.join("My Solution With Spaces.sln"),
             projects: vec![project],
+            referenced_projects: vec![],
         };
 
         let expected_processes = vec![Process {
@@ -282,19 +799,164 @@ mod tests {
         }];
 
         assert_eq!(
-            detect_solution_processes(app_dir, &solution),
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Shell
+            ),
             expected_processes
         );
     }
 
     #[test]
-    fn test_project_executable_path() {
-        let project = create_test_project(
-            "/tmp/project/project.csproj",
-            "TestApp",
-            ProjectType::ConsoleApplication,
+    fn test_detect_solution_processes_framework_dependent_without_apphost() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        let assembly_path = project_assembly_path(&project);
+        fs::create_dir_all(assembly_path.parent().unwrap()).unwrap();
+        fs::write(&assembly_path, "").unwrap();
+
+        let solution = Solution {
+            path: app_dir.join("foo.sln"),
+            projects: vec![project],
+            referenced_projects: vec![],
+        };
+
+        assert_eq!(
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Shell
+            )
+            .iter()
+            .map(|process| process.command.clone())
+            .collect::<Vec<_>>(),
+            vec![vec![
+                "bash".to_string(),
+                "-c".to_string(),
+                "cd bar/bin/publish; dotnet bar.dll".to_string(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_detect_solution_processes_self_contained_requires_executable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        let assembly_path = project_assembly_path(&project);
+        fs::create_dir_all(assembly_path.parent().unwrap()).unwrap();
+        fs::write(&assembly_path, "").unwrap();
+
+        let solution = Solution {
+            path: app_dir.join("foo.sln"),
+            projects: vec![project],
+            referenced_projects: vec![],
+        };
+
+        for publish_mode in [PublishMode::SelfContained, PublishMode::SingleFile] {
+            assert!(
+                detect_solution_processes(
+                    app_dir,
+                    &solution,
+                    Some(publish_mode),
+                    &HashMap::new(),
+                    LaunchProcessMode::Exec
+                )
+                .is_empty()
+            );
+        }
+    }
+
+    #[test]
+    fn test_project_publish_mode() {
+        let mut project = ProjectBuilder::new("/tmp/project/project.csproj")
+            .assembly_name("TestApp")
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        assert_eq!(
+            project_publish_mode(&project, None),
+            PublishMode::FrameworkDependent
         );
 
+        project.publish_single_file = true;
+        assert_eq!(
+            project_publish_mode(&project, None),
+            PublishMode::FrameworkDependent
+        );
+
+        project.self_contained = true;
+        assert_eq!(
+            project_publish_mode(&project, None),
+            PublishMode::SingleFile
+        );
+
+        project.publish_single_file = false;
+        assert_eq!(
+            project_publish_mode(&project, None),
+            PublishMode::SelfContained
+        );
+
+        assert_eq!(
+            project_publish_mode(&project, Some(PublishMode::FrameworkDependent)),
+            PublishMode::FrameworkDependent
+        );
+    }
+
+    #[test]
+    fn test_project_publish_mode_native_aot() {
+        let mut project = ProjectBuilder::new("/tmp/project/project.csproj")
+            .assembly_name("TestApp")
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        project.publish_aot = true;
+
+        assert_eq!(
+            project_publish_mode(&project, None),
+            PublishMode::SingleFile
+        );
+        assert_eq!(
+            project_publish_mode(&project, Some(PublishMode::FrameworkDependent)),
+            PublishMode::SingleFile
+        );
+    }
+
+    #[test]
+    fn test_publish_command_mode() {
+        let mut project = ProjectBuilder::new("/tmp/project/project.csproj")
+            .assembly_name("TestApp")
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        assert_eq!(publish_command_mode(&project, None), None);
+        assert_eq!(
+            publish_command_mode(&project, Some(PublishMode::FrameworkDependent)),
+            Some(PublishMode::FrameworkDependent)
+        );
+
+        project.publish_aot = true;
+        assert_eq!(publish_command_mode(&project, None), None);
+        assert_eq!(
+            publish_command_mode(&project, Some(PublishMode::FrameworkDependent)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_project_executable_path() {
+        let project = ProjectBuilder::new("/tmp/project/project.csproj")
+            .assembly_name("TestApp")
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+
         assert_eq!(
             project_executable_path(&project),
             PathBuf::from("/tmp/project/bin/publish/TestApp")
@@ -306,13 +968,18 @@ mod tests {
         let executable_path = PathBuf::from("some/project with spaces/bin/publish/My App");
 
         assert_eq!(
-            build_command(&executable_path, ProjectType::ConsoleApplication),
+            build_command(&executable_path, Launcher::Executable, &[]),
             "cd 'some/project with spaces/bin/publish'; ./'My App'"
         );
+    }
+
+    #[test]
+    fn test_build_command_with_dotnet_host() {
+        let assembly_path = PathBuf::from("some/project with spaces/bin/publish/My App.dll");
 
         assert_eq!(
-            build_command(&executable_path, ProjectType::WebApplication),
-            "cd 'some/project with spaces/bin/publish'; ./'My App' --urls http://*:$PORT"
+            build_command(&assembly_path, Launcher::DotnetHost, &[]),
+            "cd 'some/project with spaces/bin/publish'; dotnet 'My App.dll'"
         );
     }
 
@@ -322,7 +989,7 @@ mod tests {
             PathBuf::from("some/project with #special$chars/bin/publish/My-App+v1.2_Release!");
 
         assert_eq!(
-            build_command(&executable_path, ProjectType::ConsoleApplication),
+            build_command(&executable_path, Launcher::Executable, &[]),
             "cd 'some/project with #special$chars/bin/publish'; ./My-App+v1.2_Release!"
         );
     }
@@ -331,16 +998,17 @@ mod tests {
     fn test_detect_solution_processes_nested_solution() {
         let temp_dir = tempfile::tempdir().unwrap();
         let app_dir = temp_dir.path();
-        let project = create_test_project(
-            &format!("{}/src/MyApp/MyApp.csproj", app_dir.display()), // Project is also in src/ subdirectory
-            "MyApp",
-            ProjectType::WebApplication,
-        );
+        let project = ProjectBuilder::new(
+            format!("{}/src/MyApp/MyApp.csproj", app_dir.display()), // Project is also in src/ subdirectory
+        )
+        .project_type(ProjectType::WebApplication)
+        .build();
         create_executable_for_project(&project);
 
         let solution = Solution {
             path: app_dir.join("src/MyApp.sln"), // Solution is in src/ subdirectory
             projects: vec![project],
+            referenced_projects: vec![],
         };
 
         let expected_processes = vec![Process {
@@ -348,7 +1016,7 @@ mod tests {
             command: vec![
                 "bash".to_string(),
                 "-c".to_string(),
-                "cd src/MyApp/bin/publish; ./MyApp --urls http://*:$PORT".to_string(),
+                "cd src/MyApp/bin/publish; ./MyApp".to_string(),
             ],
             args: vec![],
             default: true,
@@ -356,8 +1024,423 @@ mod tests {
         }];
 
         assert_eq!(
-            detect_solution_processes(app_dir, &solution),
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Shell
+            ),
             expected_processes
         );
     }
+
+    fn write_launch_settings(project: &Project, contents: &str) {
+        let launch_settings_path = project_launch_settings_path(project);
+        fs::create_dir_all(launch_settings_path.parent().unwrap()).unwrap();
+        fs::write(&launch_settings_path, contents).unwrap();
+    }
+
+    const LAUNCH_SETTINGS: &str = r#"
+    {
+      "profiles": {
+        "IIS Express": {
+          "commandName": "IISExpress",
+          "commandLineArgs": "--iis"
+        },
+        "http": {
+          "commandName": "Project",
+          "commandLineArgs": "--seed 'demo data' --urls http://localhost:5000",
+          "applicationUrl": "http://localhost:5000",
+          "environmentVariables": {
+            "ASPNETCORE_ENVIRONMENT": "Development",
+            "ASPNETCORE_URLS": "http://localhost:5000",
+            "FEATURE_FLAGS": "beta $HOME"
+          }
+        },
+        "worker": {
+          "commandName": "Project",
+          "commandLineArgs": "--worker"
+        }
+      }
+    }
+    "#;
+
+    #[test]
+    fn test_load_launch_profiles_default_profile() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::WebApplication)
+            .build();
+        write_launch_settings(&project, LAUNCH_SETTINGS);
+        let solution = Solution {
+            path: app_dir.join("foo.sln"),
+            projects: vec![project],
+            referenced_projects: vec![],
+        };
+
+        assert_eq!(
+            load_launch_profiles(&solution, None).unwrap(),
+            HashMap::from([(
+                app_dir.join("bar/bar.csproj"),
+                ProjectLaunchProfile {
+                    name: "http".to_string(),
+                    launch_settings_path: app_dir.join("bar/Properties/launchSettings.json"),
+                    arguments: vec!["--seed".to_string(), "demo data".to_string()],
+                    environment_variables: vec![(
+                        "FEATURE_FLAGS".to_string(),
+                        "beta $HOME".to_string()
+                    )],
+                    ignored_arguments: vec![
+                        "--urls".to_string(),
+                        "http://localhost:5000".to_string()
+                    ],
+                    ignored_environment_variables: vec![
+                        "ASPNETCORE_ENVIRONMENT".to_string(),
+                        "ASPNETCORE_URLS".to_string(),
+                    ],
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn test_load_launch_profiles_configured_profile() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project1 = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::WorkerService)
+            .build();
+        let project2 = ProjectBuilder::new(format!("{}/baz/baz.csproj", app_dir.display()))
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        write_launch_settings(&project1, LAUNCH_SETTINGS);
+        write_launch_settings(&project2, r#"{ "profiles": {} }"#);
+        let solution = Solution {
+            path: app_dir.join("foo.sln"),
+            projects: vec![project1, project2],
+            referenced_projects: vec![],
+        };
+
+        let launch_profiles = load_launch_profiles(&solution, Some("worker")).unwrap();
+        assert_eq!(launch_profiles.len(), 1);
+        assert_eq!(
+            launch_profiles[&app_dir.join("bar/bar.csproj")].arguments,
+            vec!["--worker".to_string()]
+        );
+
+        assert_matches!(
+            load_launch_profiles(&solution, Some("missing")),
+            Err(LaunchProfileError::ProfileNotFound(name)) if name == "missing"
+        );
+    }
+
+    #[test]
+    fn test_load_launch_profiles_file_based_app() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project = ProjectBuilder::new(format!("{}/app.cs", app_dir.display()))
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        fs::write(app_dir.join("app.run.json"), LAUNCH_SETTINGS).unwrap();
+        let solution = Solution {
+            path: app_dir.join("app.cs"),
+            projects: vec![project],
+            referenced_projects: vec![],
+        };
+
+        assert_eq!(
+            load_launch_profiles(&solution, None).unwrap()[&app_dir.join("app.cs")]
+                .launch_settings_path,
+            app_dir.join("app.run.json")
+        );
+    }
+
+    #[test]
+    fn test_load_launch_profiles_errors() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        let launch_settings_path = project_launch_settings_path(&project);
+        fs::create_dir_all(launch_settings_path.parent().unwrap()).unwrap();
+        let solution = Solution {
+            path: app_dir.join("foo.sln"),
+            projects: vec![project],
+            referenced_projects: vec![],
+        };
+
+        assert!(load_launch_profiles(&solution, None).unwrap().is_empty());
+
+        fs::write(&launch_settings_path, "{ invalid").unwrap();
+        assert_matches!(
+            load_launch_profiles(&solution, None),
+            Err(LaunchProfileError::ParseLaunchSettings(_, _))
+        );
+
+        fs::write(
+            &launch_settings_path,
+            r#"{ "profiles": { "bar": { "commandName": "Project", "commandLineArgs": "--name 'unterminated" } } }"#,
+        )
+        .unwrap();
+        assert_matches!(
+            load_launch_profiles(&solution, None),
+            Err(LaunchProfileError::ParseCommandLineArgs(_, name, _)) if name == "bar"
+        );
+    }
+
+    #[test]
+    fn test_detect_solution_processes_with_launch_profile() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::WebApplication)
+            .build();
+        create_executable_for_project(&project);
+        write_launch_settings(&project, LAUNCH_SETTINGS);
+        let solution = Solution {
+            path: app_dir.join("foo.sln"),
+            projects: vec![project],
+            referenced_projects: vec![],
+        };
+        let launch_profiles = load_launch_profiles(&solution, None).unwrap();
+
+        assert_eq!(
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &launch_profiles,
+                LaunchProcessMode::Shell
+            )
+            .iter()
+            .map(|process| process.command.clone())
+            .collect::<Vec<_>>(),
+            vec![vec![
+                "bash".to_string(),
+                "-c".to_string(),
+                "cd bar/bin/publish; ./bar --seed 'demo data'".to_string(),
+            ]]
+        );
+
+        let layer_env = generate_launch_profiles_env(&solution, &launch_profiles);
+        assert_eq!(
+            utils::environment_as_sorted_vector(
+                &layer_env.apply_to_empty(Scope::Process("web".to_string()))
+            ),
+            vec![("FEATURE_FLAGS", "beta $HOME")]
+        );
+        let mut env = libcnb::Env::new();
+        env.insert("FEATURE_FLAGS", "config var");
+        assert_eq!(
+            utils::environment_as_sorted_vector(
+                &layer_env.apply(Scope::Process("web".to_string()), &env)
+            ),
+            vec![("FEATURE_FLAGS", "config var")]
+        );
+        assert!(
+            utils::environment_as_sorted_vector(&layer_env.apply_to_empty(Scope::Launch))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_detect_solution_processes_console_app_using_aspnetcore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::ConsoleApplication)
+            .build();
+        create_executable_for_project(&project);
+        fs::write(
+            project_runtime_config_path(&project),
+            r#"{ "runtimeOptions": { "frameworks": [{ "name": "Microsoft.AspNetCore.App", "version": "9.0.0" }] } }"#,
+        )
+        .unwrap();
+        let solution = Solution {
+            path: app_dir.join("foo.sln"),
+            projects: vec![project],
+            referenced_projects: vec![],
+        };
+
+        assert_eq!(
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Shell
+            ),
+            vec![Process {
+                r#type: process_type!("web"),
+                command: vec![
+                    "bash".to_string(),
+                    "-c".to_string(),
+                    "cd bar/bin/publish; ./bar".to_string(),
+                ],
+                args: vec![],
+                default: true,
+                working_directory: WorkingDirectory::App,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_build_command_with_arguments() {
+        let executable_path = PathBuf::from("bar/bin/publish/bar");
+
+        assert_eq!(
+            build_command(
+                &executable_path,
+                Launcher::Executable,
+                &[
+                    "--name".to_string(),
+                    "$(rm -rf /); echo".to_string(),
+                    "--flag".to_string()
+                ]
+            ),
+            "cd bar/bin/publish; ./bar --name '$(rm -rf /); echo' --flag"
+        );
+    }
+
+    #[test]
+    fn test_detect_solution_processes_exec_mode() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project1 = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::WebApplication)
+            .build();
+        let project2 =
+            ProjectBuilder::new(format!("{}/My Worker/worker.csproj", app_dir.display()))
+                .assembly_name("My Worker")
+                .project_type(ProjectType::WorkerService)
+                .build();
+        create_executable_for_project(&project1);
+        let assembly_path = project_assembly_path(&project2);
+        fs::create_dir_all(assembly_path.parent().unwrap()).unwrap();
+        fs::write(&assembly_path, "").unwrap();
+        let solution = Solution {
+            path: app_dir.join("foo.sln"),
+            projects: vec![project1, project2],
+            referenced_projects: vec![],
+        };
+
+        assert_eq!(
+            detect_solution_processes(
+                app_dir,
+                &solution,
+                None,
+                &HashMap::new(),
+                LaunchProcessMode::Exec
+            ),
+            vec![
+                Process {
+                    r#type: process_type!("web"),
+                    command: vec!["./bar".to_string()],
+                    args: vec![],
+                    default: true,
+                    working_directory: WorkingDirectory::Directory(PathBuf::from(
+                        "bar/bin/publish"
+                    )),
+                },
+                Process {
+                    r#type: process_type!("my-worker"),
+                    command: vec!["dotnet".to_string(), "My Worker.dll".to_string()],
+                    args: vec![],
+                    default: false,
+                    working_directory: WorkingDirectory::Directory(PathBuf::from(
+                        "My Worker/bin/publish"
+                    )),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_exec_process_with_arguments() {
+        let process = build_exec_process(
+            process_type!("web"),
+            Path::new("bar/bin/publish/bar"),
+            Launcher::Executable,
+            &["--greeting".to_string(), "$(HOME) costs $5".to_string()],
+        );
+
+        assert_eq!(
+            process.args,
+            vec!["--greeting".to_string(), "$$(HOME) costs $$5".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_split_ignored_arguments() {
+        let arguments = [
+            "--urls",
+            "http://localhost:5000",
+            "--seed",
+            "--URLS=http://+:80",
+            "--verbose",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(
+            split_ignored_arguments(arguments),
+            (
+                vec!["--seed".to_string(), "--verbose".to_string()],
+                vec![
+                    "--urls".to_string(),
+                    "http://localhost:5000".to_string(),
+                    "--URLS=http://+:80".to_string()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_project_launch_command() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let app_dir = temp_dir.path();
+        let project = ProjectBuilder::new(format!("{}/bar/bar.csproj", app_dir.display()))
+            .project_type(ProjectType::WebApplication)
+            .build();
+        let publish_dir = project_publish_dir(&project);
+
+        assert!(project_launch_command(&project, PublishMode::FrameworkDependent, None).is_err());
+
+        let assembly_path = project_assembly_path(&project);
+        fs::create_dir_all(&publish_dir).unwrap();
+        fs::write(&assembly_path, "").unwrap();
+        let command =
+            project_launch_command(&project, PublishMode::FrameworkDependent, None).unwrap();
+        assert_eq!(command.get_program(), "dotnet");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), vec!["bar.dll"]);
+        assert_eq!(command.get_current_dir(), Some(publish_dir.as_path()));
+
+        create_executable_for_project(&project);
+        let launch_profile = ProjectLaunchProfile {
+            name: "https".to_string(),
+            launch_settings_path: PathBuf::from("Properties/launchSettings.json"),
+            arguments: vec!["--seed".to_string(), "$HOME".to_string()],
+            environment_variables: vec![("FOO".to_string(), "bar".to_string())],
+            ignored_arguments: vec![],
+            ignored_environment_variables: vec![],
+        };
+        let command = project_launch_command(
+            &project,
+            PublishMode::FrameworkDependent,
+            Some(&launch_profile),
+        )
+        .unwrap();
+        assert_eq!(command.get_program(), project_executable_path(&project));
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["--seed", "$HOME"]
+        );
+        assert_eq!(
+            command.get_envs().collect::<Vec<_>>(),
+            vec![(OsStr::new("FOO"), Some(OsStr::new("bar")))]
+        );
+    }
 }
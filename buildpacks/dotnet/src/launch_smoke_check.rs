@@ -0,0 +1,260 @@
+use std::fs::File;
+use std::io::{self, Read, Seek};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait for a web app to accept connections
+pub(crate) const WEB_APP_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long to let other apps run, since apps that don't handle `--help` may keep running
+pub(crate) const EXECUTABLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// The number of trailing output lines included in errors
+const MAX_OUTPUT_LINES: usize = 50;
+
+/// How a published app is started to check that it launches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum LaunchSmokeCheck {
+    /// Start the app with a dummy `PORT`, and wait for it to accept connections on that port
+    WebApp,
+    /// Run the app with a `--help` argument
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum LaunchSmokeCheckOutcome {
+    /// The web app accepted connections on the port after the given time
+    Listening(u16, Duration),
+    /// The app exited successfully
+    Exited,
+    /// The app was still running when the timeout elapsed, and was stopped
+    StillRunning(Duration),
+}
+
+#[derive(Debug)]
+pub(crate) enum LaunchSmokeCheckError {
+    Io(io::Error),
+    /// The app exited unsuccessfully (or, for web apps, before accepting connections)
+    Exited {
+        check: LaunchSmokeCheck,
+        status: ExitStatus,
+        output: String,
+    },
+    /// The web app didn't accept connections before the timeout elapsed
+    NotListening {
+        port: u16,
+        timeout: Duration,
+        output: String,
+    },
+}
+
+/// Starts the app with the given command, and stops it once the check completes. The app's output
+/// (stdout and stderr) is captured and included in errors.
+pub(crate) fn run(
+    mut command: Command,
+    check: LaunchSmokeCheck,
+    timeout: Duration,
+) -> Result<LaunchSmokeCheckOutcome, LaunchSmokeCheckError> {
+    let mut output_file = tempfile::tempfile().map_err(LaunchSmokeCheckError::Io)?;
+    command
+        .stdin(Stdio::null())
+        .stdout(output_file.try_clone().map_err(LaunchSmokeCheckError::Io)?)
+        .stderr(output_file.try_clone().map_err(LaunchSmokeCheckError::Io)?);
+
+    let port = match check {
+        LaunchSmokeCheck::WebApp => {
+            let port = unused_port().map_err(LaunchSmokeCheckError::Io)?;
+            command
+                .env("PORT", port.to_string())
+                .env("ASPNETCORE_URLS", format!("http://*:{port}"));
+            Some(port)
+        }
+        LaunchSmokeCheck::Help => {
+            command.arg("--help");
+            None
+        }
+    };
+
+    let mut child = command.spawn().map_err(LaunchSmokeCheckError::Io)?;
+    let started = Instant::now();
+    let result = loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() && port.is_none() => {
+                break Ok(LaunchSmokeCheckOutcome::Exited);
+            }
+            Ok(Some(status)) => {
+                break Err(LaunchSmokeCheckError::Exited {
+                    check,
+                    status,
+                    output: read_output(&mut output_file),
+                });
+            }
+            Ok(None) => {}
+            Err(error) => break Err(LaunchSmokeCheckError::Io(error)),
+        }
+
+        if let Some(port) = port
+            && TcpStream::connect_timeout(
+                &SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+                POLL_INTERVAL,
+            )
+            .is_ok()
+        {
+            break Ok(LaunchSmokeCheckOutcome::Listening(port, started.elapsed()));
+        }
+
+        if started.elapsed() >= timeout {
+            break match port {
+                Some(port) => Err(LaunchSmokeCheckError::NotListening {
+                    port,
+                    timeout,
+                    output: read_output(&mut output_file),
+                }),
+                None => Ok(LaunchSmokeCheckOutcome::StillRunning(timeout)),
+            };
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    // The app may have already exited, in which case there's nothing to stop.
+    let _ = child.kill();
+    let _ = child.wait();
+    result
+}
+
+/// Returns a port that's currently unused on the loopback interface
+fn unused_port() -> io::Result<u16> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?
+        .local_addr()
+        .map(|address| address.port())
+}
+
+/// Returns the last lines of the captured output
+fn read_output(output_file: &mut File) -> String {
+    let mut output = Vec::new();
+    let _ = output_file
+        .rewind()
+        .and_then(|()| output_file.read_to_end(&mut output));
+    let output = String::from_utf8_lossy(&output);
+    let lines = output.trim_end().lines().collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(MAX_OUTPUT_LINES)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libcnb_test::assert_matches;
+    use std::io::Write;
+
+    fn shell_command(script: &str) -> Command {
+        let mut command = Command::new("bash");
+        command.args(["-c", script, "app"]);
+        command
+    }
+
+    #[test]
+    fn test_help_check_exited() {
+        assert_eq!(
+            run(
+                shell_command(r#"[ "$1" = "--help" ] && echo "Usage: app""#),
+                LaunchSmokeCheck::Help,
+                EXECUTABLE_TIMEOUT
+            )
+            .unwrap(),
+            LaunchSmokeCheckOutcome::Exited
+        );
+    }
+
+    #[test]
+    fn test_help_check_still_running() {
+        assert_eq!(
+            run(
+                shell_command("sleep 10"),
+                LaunchSmokeCheck::Help,
+                Duration::from_millis(200)
+            )
+            .unwrap(),
+            LaunchSmokeCheckOutcome::StillRunning(Duration::from_millis(200))
+        );
+    }
+
+    #[test]
+    fn test_help_check_crashed() {
+        let error = run(
+            shell_command(
+                "echo 'Starting'; echo 'error while loading shared libraries: libfoo.so' >&2; exit 127",
+            ),
+            LaunchSmokeCheck::Help,
+            EXECUTABLE_TIMEOUT,
+        )
+        .unwrap_err();
+
+        assert_matches!(
+            error,
+            LaunchSmokeCheckError::Exited {
+                check: LaunchSmokeCheck::Help,
+                status,
+                output
+            } if status.code() == Some(127)
+                && output == "Starting\nerror while loading shared libraries: libfoo.so"
+        );
+    }
+
+    #[test]
+    fn test_web_app_check_exited_before_listening() {
+        assert_matches!(
+            run(
+                shell_command(r#"echo "PORT=$PORT $ASPNETCORE_URLS""#),
+                LaunchSmokeCheck::WebApp,
+                WEB_APP_TIMEOUT
+            )
+            .unwrap_err(),
+            LaunchSmokeCheckError::Exited {
+                check: LaunchSmokeCheck::WebApp,
+                status,
+                output
+            } if status.success() && output.starts_with("PORT=")
+        );
+    }
+
+    #[test]
+    fn test_web_app_check_not_listening() {
+        assert_matches!(
+            run(
+                shell_command("echo 'Waiting'; sleep 10"),
+                LaunchSmokeCheck::WebApp,
+                Duration::from_millis(200)
+            )
+            .unwrap_err(),
+            LaunchSmokeCheckError::NotListening { output, .. } if output == "Waiting"
+        );
+    }
+
+    #[test]
+    fn test_spawn_error() {
+        assert_matches!(
+            run(
+                Command::new("/nonexistent/app"),
+                LaunchSmokeCheck::Help,
+                EXECUTABLE_TIMEOUT
+            )
+            .unwrap_err(),
+            LaunchSmokeCheckError::Io(_)
+        );
+    }
+
+    #[test]
+    fn test_read_output_keeps_last_lines() {
+        let mut output_file = tempfile::tempfile().unwrap();
+        for line in 1..=60 {
+            writeln!(output_file, "line {line}").unwrap();
+        }
+
+        let output = read_output(&mut output_file);
+        assert_eq!(output.lines().count(), MAX_OUTPUT_LINES);
+        assert!(output.starts_with("line 11\n"));
+        assert!(output.ends_with("line 60"));
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{DotnetBuildpack, DotnetBuildpackError, dotnet_layer_env};
+use libcnb::additional_buildpack_binary_path;
+use libcnb::build::BuildContext;
+use libcnb::data::launch::ProcessType;
+use libcnb::data::layer_name;
+use libcnb::layer::UncachedLayerDefinition;
+
+/// Creates the layer that configures Kestrel to listen on `$PORT` behind the router. The exec.d
+/// program and environment are scoped to the web process types, so other processes (e.g. workers
+/// or the `release` process) don't bind to `$PORT`.
+pub(crate) fn handle(
+    context: &BuildContext<DotnetBuildpack>,
+    process_types: &[ProcessType],
+) -> Result<(), libcnb::Error<DotnetBuildpackError>> {
+    let kestrel_layer = context.uncached_layer(
+        layer_name!("kestrel"),
+        UncachedLayerDefinition {
+            build: false,
+            launch: true,
+        },
+    )?;
+
+    // libcnb only writes exec.d programs that run for every process, so the program is copied to
+    // the process-specific `exec.d/<process>` directories instead.
+    for process_type in process_types {
+        let exec_d_dir = kestrel_layer.path().join("exec.d").join(&**process_type);
+        fs_err::create_dir_all(&exec_d_dir)
+            .and_then(|()| {
+                fs_err::copy(
+                    additional_buildpack_binary_path!("configure_kestrel"),
+                    exec_d_dir.join("configure_kestrel"),
+                )
+            })
+            .map_err(DotnetBuildpackError::ConfigureKestrel)?;
+    }
+    kestrel_layer.write_env(dotnet_layer_env::generate_kestrel_env(process_types))?;
+    Ok(())
+}
@@ -1,3 +1,6 @@
+pub(crate) mod kestrel;
+pub(crate) mod msbuild_logs;
 pub(crate) mod nuget_cache;
 pub(crate) mod runtime;
 pub(crate) mod sdk;
+pub(crate) mod workloads;
@@ -0,0 +1,79 @@
+use crate::{DotnetBuildpack, DotnetBuildpackError};
+use libcnb::build::BuildContext;
+use libcnb::data::layer_name;
+use libcnb::generic::GenericMetadata;
+use libcnb::layer::{CachedLayerDefinition, InvalidMetadataAction, RestoredLayerAction};
+use std::path::{Path, PathBuf};
+
+/// Creates the layer that `MSBuild` binary logs are written to. Binary logs can embed project files,
+/// properties and environment values, so the layer is kept in the build cache (with only the logs
+/// of the latest build) instead of being added to the app image.
+pub(crate) fn handle(
+    context: &BuildContext<DotnetBuildpack>,
+) -> Result<PathBuf, libcnb::Error<DotnetBuildpackError>> {
+    let msbuild_logs_layer = context.cached_layer(
+        layer_name!("msbuild-logs"),
+        CachedLayerDefinition {
+            build: false,
+            launch: false,
+            invalid_metadata_action: &|_| InvalidMetadataAction::DeleteLayer,
+            restored_layer_action: &|_: &GenericMetadata, _path| RestoredLayerAction::DeleteLayer,
+        },
+    )?;
+    Ok(msbuild_logs_layer.path())
+}
+
+/// Returns the binary log path for publishing `publish_path`. The solution is logged to
+/// `publish.binlog`, and projects published separately are logged to a path that mirrors the
+/// project file's path relative to the app directory (e.g. `publish/src/Web/Web.binlog`), so
+/// projects with the same name in different directories don't overwrite each other's logs.
+pub(crate) fn binary_log_path(
+    msbuild_logs_dir: &Path,
+    app_dir: &Path,
+    publish_path: &Path,
+    solution_path: &Path,
+) -> PathBuf {
+    if publish_path == solution_path {
+        msbuild_logs_dir.join("publish.binlog")
+    } else {
+        msbuild_logs_dir
+            .join("publish")
+            .join(publish_path.strip_prefix(app_dir).unwrap_or(publish_path))
+            .with_extension("binlog")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_log_path() {
+        let msbuild_logs_dir = Path::new("/layers/heroku_dotnet/msbuild-logs");
+        let app_dir = Path::new("/workspace");
+        let solution_path = Path::new("/workspace/app.sln");
+
+        assert_eq!(
+            binary_log_path(msbuild_logs_dir, app_dir, solution_path, solution_path),
+            PathBuf::from("/layers/heroku_dotnet/msbuild-logs/publish.binlog")
+        );
+        assert_eq!(
+            binary_log_path(
+                msbuild_logs_dir,
+                app_dir,
+                Path::new("/workspace/src/Web/Web.csproj"),
+                solution_path
+            ),
+            PathBuf::from("/layers/heroku_dotnet/msbuild-logs/publish/src/Web/Web.binlog")
+        );
+        assert_eq!(
+            binary_log_path(
+                msbuild_logs_dir,
+                app_dir,
+                Path::new("/workspace/tools/Web/Web.csproj"),
+                solution_path
+            ),
+            PathBuf::from("/layers/heroku_dotnet/msbuild-logs/publish/tools/Web/Web.binlog")
+        );
+    }
+}
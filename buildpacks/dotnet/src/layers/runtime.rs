@@ -1,38 +1,450 @@
+use crate::dotnet::runtime_config::{FrameworkReference, RuntimeConfig};
+use crate::dotnet_buildpack_configuration::{GlobalizationOptions, WriteXorExecuteOptions};
+use crate::utils::{FileCopier, format_bytes};
 use crate::{DotnetBuildpack, DotnetBuildpackError, dotnet_layer_env, utils};
+use bullet_stream::global::print;
+use bullet_stream::style;
 use libcnb::data::layer_name;
-use libcnb::layer::UncachedLayerDefinition;
-use libcnb::layer_env::Scope;
-use std::path::Path;
-
-// These are the paths we want to copy to this layer from the SDK layer
-const RUNTIME_PATHS: &[&str] = &[
-    "dotnet",
-    "host",
-    "shared",
-    "ThirdPartyNotices.txt",
-    "LICENSE.txt",
-];
+use libcnb::layer::{
+    CachedLayerDefinition, InvalidMetadataAction, LayerState, RestoredLayerAction,
+    UncachedLayerDefinition,
+};
+use libcnb::layer_env::{LayerEnv, Scope};
+use libherokubuildpack::inventory::artifact::Artifact;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// These are the paths we want to copy to this layer from the SDK layer. Shared frameworks (in
+// the `shared` directory) are copied separately, as only the ones used by the app are needed.
+const RUNTIME_PATHS: &[&str] = &["dotnet", "host", "ThirdPartyNotices.txt", "LICENSE.txt"];
+
+const SHARED_FRAMEWORKS_DIR: &str = "shared";
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub(crate) struct RuntimeLayerMetadata {
+    sdk_artifact: Artifact<Version, Sha512, Option<()>>,
+    shared_frameworks: Vec<String>,
+}
 
 pub(crate) fn handle(
     context: &libcnb::build::BuildContext<DotnetBuildpack>,
     sdk_layer_path: &Path,
-) -> Result<(), libcnb::Error<DotnetBuildpackError>> {
-    let runtime_layer = context.uncached_layer(
+    sdk_artifact: &Artifact<Version, Sha512, Option<()>>,
+    runtime_config_paths: &[PathBuf],
+    globalization_options: &GlobalizationOptions,
+    write_xor_execute: WriteXorExecuteOptions,
+) -> Result<LayerEnv, libcnb::Error<DotnetBuildpackError>> {
+    print::bullet("Runtime installation");
+    let installed_frameworks =
+        installed_shared_frameworks(&sdk_layer_path.join(SHARED_FRAMEWORKS_DIR))
+            .map_err(DotnetBuildpackError::CopyRuntimeFiles)?;
+    let required_frameworks =
+        if let Some(missing_path) = runtime_config_paths.iter().find(|path| !path.is_file()) {
+            print::sub_bullet(format!(
+                "Installing all shared frameworks ({} not found)",
+                style::value(missing_path.to_string_lossy())
+            ));
+            installed_frameworks.iter().collect()
+        } else if runtime_config_paths.is_empty() {
+            print::sub_bullet("Installing all shared frameworks (no launchable projects found)");
+            installed_frameworks.iter().collect()
+        } else {
+            required_shared_frameworks(runtime_config_paths, &installed_frameworks)?
+        };
+
+    let metadata = RuntimeLayerMetadata {
+        sdk_artifact: sdk_artifact.clone(),
+        shared_frameworks: required_frameworks
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    };
+    let runtime_layer = context.cached_layer(
         layer_name!("runtime"),
-        UncachedLayerDefinition {
+        CachedLayerDefinition {
             build: false,
             launch: true,
+            invalid_metadata_action: &|_| InvalidMetadataAction::DeleteLayer,
+            restored_layer_action: &|layer_metadata: &RuntimeLayerMetadata, _path| {
+                if *layer_metadata == metadata {
+                    RestoredLayerAction::KeepLayer
+                } else {
+                    RestoredLayerAction::DeleteLayer
+                }
+            },
         },
     )?;
-    runtime_layer.write_env(dotnet_layer_env::generate_layer_env(
+
+    match runtime_layer.state {
+        LayerState::Restored { .. } => {
+            print::sub_bullet("Reusing cached runtime");
+        }
+        LayerState::Empty { .. } => {
+            runtime_layer.write_metadata(metadata)?;
+
+            let mut file_copier = FileCopier::default();
+            for path in RUNTIME_PATHS {
+                file_copier
+                    .copy_recursively(sdk_layer_path.join(path), runtime_layer.path().join(path))
+                    .map_err(DotnetBuildpackError::CopyRuntimeFiles)?;
+            }
+            for framework in &required_frameworks {
+                file_copier
+                    .copy_recursively(
+                        framework.path.clone(),
+                        runtime_layer
+                            .path()
+                            .join(SHARED_FRAMEWORKS_DIR)
+                            .join(&framework.name)
+                            .join(&framework.version),
+                    )
+                    .map_err(DotnetBuildpackError::CopyRuntimeFiles)?;
+            }
+            print::sub_bullet(format!(
+                "Installed runtime files {}",
+                style::details(file_copier.summary())
+            ));
+        }
+    }
+    let layer_env = dotnet_layer_env::generate_layer_env(
         &runtime_layer.path(),
         &Scope::Launch,
-    ))?;
+        globalization_options,
+        write_xor_execute,
+    );
+    runtime_layer.write_env(&layer_env)?;
 
-    for path in RUNTIME_PATHS {
-        utils::copy_recursively(sdk_layer_path.join(path), runtime_layer.path().join(path))
-            .map_err(DotnetBuildpackError::CopyRuntimeFiles)?;
+    let mut skipped_frameworks = Vec::new();
+    let mut skipped_size = 0;
+    for framework in &installed_frameworks {
+        if !required_frameworks.contains(&framework) {
+            skipped_size += utils::directory_size(&framework.path)
+                .map_err(DotnetBuildpackError::CopyRuntimeFiles)?;
+            skipped_frameworks.push(framework);
+        }
+    }
+
+    print::sub_bullet(format!(
+        "Installed shared frameworks: {}",
+        format_frameworks(&required_frameworks)
+    ));
+    if !skipped_frameworks.is_empty() {
+        print::sub_bullet(format!(
+            "Skipped unused shared frameworks: {} {}",
+            format_frameworks(&skipped_frameworks),
+            style::details(format!("saved {}", format_bytes(skipped_size)))
+        ));
     }
 
+    Ok(layer_env)
+}
+
+/// Removes a runtime layer restored from a previous build, for apps that bundle their own runtime.
+pub(crate) fn remove(
+    context: &libcnb::build::BuildContext<DotnetBuildpack>,
+) -> Result<(), libcnb::Error<DotnetBuildpackError>> {
+    context.uncached_layer(
+        layer_name!("runtime"),
+        UncachedLayerDefinition {
+            build: false,
+            launch: false,
+        },
+    )?;
     Ok(())
 }
+
+/// A shared framework version installed in the SDK (e.g. `shared/Microsoft.NETCore.App/8.0.1`).
+#[derive(Debug, PartialEq)]
+struct SharedFramework {
+    name: String,
+    version: String,
+    path: PathBuf,
+}
+
+impl SharedFramework {
+    /// Returns whether this framework version can be used by an app referencing `reference`,
+    /// following the default roll forward policy (`Minor`) unless the app rolls forward to newer
+    /// major versions.
+    fn satisfies(&self, reference: &FrameworkReference, roll_forward_to_major: bool) -> bool {
+        match (
+            Version::parse(&self.version),
+            Version::parse(&reference.version),
+        ) {
+            (Ok(version), Ok(minimum_version)) => {
+                self.name == reference.name
+                    && version >= minimum_version
+                    && (roll_forward_to_major || version.major == minimum_version.major)
+            }
+            _ => false,
+        }
+    }
+
+    fn runtime_config_path(&self) -> PathBuf {
+        self.path.join(format!("{}.runtimeconfig.json", self.name))
+    }
+}
+
+impl std::fmt::Display for SharedFramework {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.name, self.version)
+    }
+}
+
+fn installed_shared_frameworks(shared_dir: &Path) -> io::Result<Vec<SharedFramework>> {
+    let mut frameworks = Vec::new();
+    for framework_dir in fs_err::read_dir(shared_dir)? {
+        let framework_dir = framework_dir?;
+        for version_dir in fs_err::read_dir(framework_dir.path())? {
+            let version_dir = version_dir?;
+            frameworks.push(SharedFramework {
+                name: framework_dir.file_name().to_string_lossy().to_string(),
+                version: version_dir.file_name().to_string_lossy().to_string(),
+                path: version_dir.path(),
+            });
+        }
+    }
+    frameworks.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    Ok(frameworks)
+}
+
+/// Resolves the installed shared frameworks needed by the apps with the given runtime config
+/// files, including the frameworks they depend on (e.g. `Microsoft.AspNetCore.App` depends on
+/// `Microsoft.NETCore.App`).
+///
+/// If no installed version of a referenced framework is compatible, all of its installed
+/// versions are included, leaving it up to the .NET host to report the problem at launch.
+fn required_shared_frameworks<'a>(
+    runtime_config_paths: &[PathBuf],
+    installed_frameworks: &'a [SharedFramework],
+) -> Result<Vec<&'a SharedFramework>, DotnetBuildpackError> {
+    let mut required_frameworks: Vec<&SharedFramework> = Vec::new();
+    let mut visited_paths = HashSet::new();
+    let mut pending_paths = runtime_config_paths.to_vec();
+
+    while let Some(path) = pending_paths.pop() {
+        if !visited_paths.insert(path.clone()) {
+            continue;
+        }
+        let runtime_config = read_runtime_config(&path)?;
+
+        for reference in runtime_config.framework_references() {
+            let compatible_frameworks = installed_frameworks
+                .iter()
+                .filter(|framework| {
+                    framework.satisfies(reference, runtime_config.rolls_forward_to_major())
+                })
+                .collect::<Vec<_>>();
+            let selected_frameworks = if compatible_frameworks.is_empty() {
+                installed_frameworks
+                    .iter()
+                    .filter(|framework| framework.name == reference.name)
+                    .collect()
+            } else {
+                compatible_frameworks
+            };
+
+            for framework in selected_frameworks {
+                if !required_frameworks.contains(&framework) {
+                    required_frameworks.push(framework);
+                    if framework.runtime_config_path().is_file() {
+                        pending_paths.push(framework.runtime_config_path());
+                    }
+                }
+            }
+        }
+    }
+
+    required_frameworks.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    Ok(required_frameworks)
+}
+
+fn read_runtime_config(path: &Path) -> Result<RuntimeConfig, DotnetBuildpackError> {
+    fs_err::read_to_string(path)
+        .map_err(DotnetBuildpackError::ReadRuntimeConfig)?
+        .parse::<RuntimeConfig>()
+        .map_err(|error| DotnetBuildpackError::ParseRuntimeConfig(path.to_path_buf(), error))
+}
+
+fn format_frameworks(frameworks: &[&SharedFramework]) -> String {
+    if frameworks.is_empty() {
+        return String::from("none");
+    }
+    frameworks
+        .iter()
+        .map(|framework| style::value(framework.to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libcnb_test::assert_matches;
+    use std::fs;
+
+    const NETCORE_APP_RUNTIME_CONFIG: &str = r#"
+        {
+          "runtimeOptions": {
+            "tfm": "net8.0",
+            "framework": { "name": "Microsoft.NETCore.App", "version": "8.0.0" }
+          }
+        }
+    "#;
+
+    const ASPNETCORE_APP_RUNTIME_CONFIG: &str = r#"
+        {
+          "runtimeOptions": {
+            "tfm": "net8.0",
+            "framework": { "name": "Microsoft.AspNetCore.App", "version": "8.0.0" }
+          }
+        }
+    "#;
+
+    /// Creates an SDK `shared` directory with the .NET and ASP.NET Core runtimes, where the
+    /// ASP.NET Core framework references the .NET runtime in its own runtime config file.
+    fn create_shared_dir(root: &Path) -> PathBuf {
+        let shared_dir = root.join("shared");
+        for (name, version) in [
+            ("Microsoft.NETCore.App", "8.0.1"),
+            ("Microsoft.AspNetCore.App", "8.0.1"),
+        ] {
+            let framework_dir = shared_dir.join(name).join(version);
+            fs::create_dir_all(&framework_dir).unwrap();
+            fs::write(framework_dir.join(format!("{name}.dll")), "0123456789").unwrap();
+        }
+        fs::write(
+            shared_dir
+                .join("Microsoft.AspNetCore.App/8.0.1/Microsoft.AspNetCore.App.runtimeconfig.json"),
+            NETCORE_APP_RUNTIME_CONFIG,
+        )
+        .unwrap();
+        shared_dir
+    }
+
+    fn framework_names(frameworks: &[&SharedFramework]) -> Vec<String> {
+        frameworks.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_installed_shared_frameworks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let shared_dir = create_shared_dir(temp_dir.path());
+
+        let frameworks = installed_shared_frameworks(&shared_dir).unwrap();
+
+        assert_eq!(
+            framework_names(&frameworks.iter().collect::<Vec<_>>()),
+            vec![
+                "Microsoft.AspNetCore.App 8.0.1",
+                "Microsoft.NETCore.App 8.0.1"
+            ]
+        );
+        assert_eq!(
+            frameworks[1].path,
+            shared_dir.join("Microsoft.NETCore.App/8.0.1")
+        );
+    }
+
+    #[test]
+    fn test_required_shared_frameworks_for_console_app() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let installed = installed_shared_frameworks(&create_shared_dir(temp_dir.path())).unwrap();
+        let runtime_config_path = temp_dir.path().join("console.runtimeconfig.json");
+        fs::write(&runtime_config_path, NETCORE_APP_RUNTIME_CONFIG).unwrap();
+
+        let required = required_shared_frameworks(&[runtime_config_path], &installed).unwrap();
+
+        assert_eq!(
+            framework_names(&required),
+            vec!["Microsoft.NETCore.App 8.0.1"]
+        );
+    }
+
+    #[test]
+    fn test_required_shared_frameworks_includes_framework_dependencies() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let installed = installed_shared_frameworks(&create_shared_dir(temp_dir.path())).unwrap();
+        let runtime_config_path = temp_dir.path().join("web.runtimeconfig.json");
+        fs::write(&runtime_config_path, ASPNETCORE_APP_RUNTIME_CONFIG).unwrap();
+
+        let required = required_shared_frameworks(&[runtime_config_path], &installed).unwrap();
+
+        assert_eq!(
+            framework_names(&required),
+            vec![
+                "Microsoft.AspNetCore.App 8.0.1",
+                "Microsoft.NETCore.App 8.0.1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_required_shared_frameworks_for_self_contained_app() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let installed = installed_shared_frameworks(&create_shared_dir(temp_dir.path())).unwrap();
+        let runtime_config_path = temp_dir.path().join("app.runtimeconfig.json");
+        fs::write(
+            &runtime_config_path,
+            r#"{ "runtimeOptions": { "includedFrameworks": [{ "name": "Microsoft.NETCore.App", "version": "8.0.1" }] } }"#,
+        )
+        .unwrap();
+
+        let required = required_shared_frameworks(&[runtime_config_path], &installed).unwrap();
+
+        assert!(required.is_empty());
+    }
+
+    #[test]
+    fn test_required_shared_frameworks_without_compatible_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let installed = installed_shared_frameworks(&create_shared_dir(temp_dir.path())).unwrap();
+        let runtime_config_path = temp_dir.path().join("app.runtimeconfig.json");
+        fs::write(
+            &runtime_config_path,
+            r#"{ "runtimeOptions": { "framework": { "name": "Microsoft.NETCore.App", "version": "9.0.0" } } }"#,
+        )
+        .unwrap();
+
+        let required = required_shared_frameworks(&[runtime_config_path], &installed).unwrap();
+
+        assert_eq!(
+            framework_names(&required),
+            vec!["Microsoft.NETCore.App 8.0.1"]
+        );
+    }
+
+    #[test]
+    fn test_required_shared_frameworks_invalid_runtime_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let installed = installed_shared_frameworks(&create_shared_dir(temp_dir.path())).unwrap();
+        let runtime_config_path = temp_dir.path().join("app.runtimeconfig.json");
+        fs::write(&runtime_config_path, "{ invalid").unwrap();
+
+        let result = required_shared_frameworks(&[runtime_config_path], &installed);
+
+        assert_matches!(result, Err(DotnetBuildpackError::ParseRuntimeConfig(_, _)));
+    }
+
+    #[test]
+    fn test_shared_framework_satisfies() {
+        let framework = SharedFramework {
+            name: "Microsoft.NETCore.App".to_string(),
+            version: "9.0.2".to_string(),
+            path: PathBuf::new(),
+        };
+        let reference = |name: &str, version: &str| FrameworkReference {
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+
+        assert!(framework.satisfies(&reference("Microsoft.NETCore.App", "9.0.0"), false));
+        assert!(!framework.satisfies(&reference("Microsoft.NETCore.App", "9.0.3"), false));
+        assert!(!framework.satisfies(&reference("Microsoft.NETCore.App", "8.0.0"), false));
+        assert!(framework.satisfies(&reference("Microsoft.NETCore.App", "8.0.0"), true));
+        assert!(!framework.satisfies(&reference("Microsoft.AspNetCore.App", "9.0.0"), false));
+    }
+}
@@ -1,7 +1,10 @@
+use crate::dotnet_buildpack_configuration::SdkArtifactSource;
+use crate::utils::format_bytes;
 use crate::{DotnetBuildpack, DotnetBuildpackError};
+use bullet_stream::GlobalTimer;
 use bullet_stream::global::print;
 use bullet_stream::style;
-use fs_err::File;
+use flate2::read::GzDecoder;
 use inventory::artifact::Artifact;
 use inventory::checksum::Checksum;
 use libcnb::data::layer_name;
@@ -11,15 +14,20 @@ use libcnb::layer::{
 };
 use libherokubuildpack::download::DownloadError;
 use libherokubuildpack::inventory;
-use libherokubuildpack::tar::decompress_tarball;
-use retry::delay::Fixed;
-use retry::{OperationResult, retry_with_index};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{
+    ACCEPT_RANGES, CONTENT_RANGE, ETAG, HeaderValue, IF_RANGE, LAST_MODIFIED, RANGE,
+};
+use reqwest::{StatusCode, Url};
+use retry::delay::{Exponential, jitter};
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha512};
-use std::env::temp_dir;
+use std::io::{self, Read};
 use std::path::Path;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tar::Archive;
+use tempfile::TempDir;
 use tracing::instrument;
 
 #[derive(Serialize, Deserialize)]
@@ -32,14 +40,12 @@ pub(crate) enum CustomCause {
     DifferentSdkArtifact(Artifact<Version, Sha512, Option<()>>),
 }
 
-const MAX_RETRIES: usize = 4;
-const RETRY_DELAY: Duration = Duration::from_secs(1);
-
 #[instrument(skip_all, err(Debug))]
 pub(crate) fn handle(
     context: &libcnb::build::BuildContext<DotnetBuildpack>,
     available_at_launch: bool,
     artifact: &Artifact<Version, Sha512, Option<()>>,
+    artifact_source: &SdkArtifactSource,
 ) -> Result<LayerRef<DotnetBuildpack, (), CustomCause>, libcnb::Error<DotnetBuildpackError>> {
     let sdk_layer = context.cached_layer(
         layer_name!("sdk"),
@@ -81,79 +87,470 @@ pub(crate) fn handle(
                 artifact: artifact.clone(),
             })?;
 
-            let tarball_path = temp_dir().join("dotnetsdk.tar.gz");
-
-            download_sdk(artifact, &tarball_path).map_err(SdkLayerError::DownloadArchive)?;
+            let staged_sdk = match artifact_source {
+                SdkArtifactSource::Inventory => {
+                    download_sdk(&artifact.url, &sdk_layer.path(), &DOWNLOAD_POLICY)?
+                }
+                SdkArtifactSource::Mirror(mirror_url) => download_sdk(
+                    &mirror_artifact_url(mirror_url, &artifact.url)?,
+                    &sdk_layer.path(),
+                    &DOWNLOAD_POLICY,
+                )?,
+                SdkArtifactSource::LocalDirectory(artifact_dir) => stage_local_sdk(
+                    &artifact_dir.join(artifact_file_name(&artifact.url)?),
+                    &sdk_layer.path(),
+                )?,
+            };
 
             print::sub_bullet("Verifying SDK checksum");
-            verify_checksum(&artifact.checksum, &tarball_path)?;
+            verify_checksum(&artifact.checksum, staged_sdk.checksum)?;
 
             print::sub_bullet("Installing SDK");
-            extract_tarball(&tarball_path, &sdk_layer.path())?;
+            install_staged_sdk(&staged_sdk.directory, &sdk_layer.path())?;
         }
     }
 
     Ok(sdk_layer)
 }
 
-#[instrument(skip_all, err)]
+/// An SDK archive that has been extracted to a staging directory, along with the checksum
+/// calculated over the downloaded archive bytes.
+///
+/// The staging directory is removed when this value is dropped, so an SDK that fails checksum
+/// verification never ends up in the layer.
+struct StagedSdk {
+    directory: TempDir,
+    checksum: Vec<u8>,
+}
+
+/// Rewrites an inventory artifact URL to point at a mirror, keeping the inventory URL path. Any
+/// path on the mirror URL is used as a prefix (e.g. an Artifactory remote repository path).
+fn mirror_artifact_url(mirror_url: &Url, artifact_url: &str) -> Result<String, SdkLayerError> {
+    let artifact_url = Url::parse(artifact_url)
+        .map_err(|_| SdkLayerError::InvalidArtifactUrl(artifact_url.to_string()))?;
+    Ok(format!(
+        "{}{}",
+        mirror_url.as_str().trim_end_matches('/'),
+        artifact_url.path()
+    ))
+}
+
+/// Returns the file name of an inventory artifact URL (e.g. `dotnet-sdk-8.0.101-linux-x64.tar.gz`).
+fn artifact_file_name(artifact_url: &str) -> Result<String, SdkLayerError> {
+    Url::parse(artifact_url)
+        .ok()
+        .and_then(|url| {
+            url.path_segments()
+                .and_then(|mut segments| segments.next_back().map(str::to_string))
+        })
+        .filter(|file_name| !file_name.is_empty())
+        .ok_or_else(|| SdkLayerError::InvalidArtifactUrl(artifact_url.to_string()))
+}
+
+#[instrument(skip_all, err(Debug))]
 fn download_sdk(
-    artifact: &Artifact<Version, Sha512, Option<()>>,
-    path: &Path,
-) -> Result<(), DownloadError> {
-    let retry_strategy = Fixed::from(RETRY_DELAY).take(MAX_RETRIES);
-    retry_with_index(retry_strategy, |attempt_index| {
-        // The `retry_with_index` function provides a 1-based `attempt_index` (so the first try is 1).
-        let message = if attempt_index == 1 {
-            format!("Downloading SDK from {}", style::url(&artifact.url))
-        } else {
-            format!("Retrying download ({attempt_index}/{})", MAX_RETRIES + 1)
-        };
-        let log_progress = print::sub_start_timer(message);
+    url: &str,
+    layer_path: &Path,
+    policy: &DownloadPolicy,
+) -> Result<StagedSdk, SdkLayerError> {
+    // Uses the same client configuration as `libherokubuildpack::download::download_file`, which
+    // verifies certificates with the operating system verifier. The blocking client applies the
+    // timeout to sending the request and to each read of the body, so it only fails stalled
+    // transfers.
+    let client = Client::builder()
+        .use_rustls_tls()
+        .timeout(policy.read_timeout)
+        .build()
+        .map_err(|error| SdkLayerError::DownloadArchive(DownloadError::HttpError(error)))?;
+    let mut attempts = DownloadAttempts::new(policy);
+
+    loop {
+        match stage_sdk(&client, url, layer_path, &mut attempts) {
+            Err(error) if error.is_retryable() && attempts.wait_for_next() => {}
+            result => return result,
+        }
+    }
+}
+
+#[instrument(skip_all, err(Debug), fields(
+    http.request.method = "GET",
+    url.full = %url,
+))]
+fn stage_sdk(
+    client: &Client,
+    url: &str,
+    layer_path: &Path,
+    attempts: &mut DownloadAttempts,
+) -> Result<StagedSdk, SdkLayerError> {
+    let directory = create_staging_dir(layer_path)?;
+
+    let mut download =
+        ResumableDownload::start(client, url, attempts).map_err(SdkLayerError::DownloadArchive)?;
+    let extract_result = extract_tarball::<Sha512>(&mut download, directory.path());
+    download.finish(extract_result.as_ref().err());
+
+    extract_result.map(|checksum| StagedSdk {
+        directory,
+        checksum,
+    })
+}
+
+/// Retry and timeout settings for downloading the SDK archive.
+struct DownloadPolicy {
+    /// The number of attempts after the first one, shared between resumed and restarted
+    /// transfers.
+    max_retries: usize,
+    initial_retry_delay: Duration,
+    max_retry_delay: Duration,
+    /// The maximum time to wait for the response headers, or for a single read of the body.
+    read_timeout: Duration,
+    /// The maximum duration of a single attempt, after which the transfer is resumed. Transfers
+    /// that can't be resumed aren't limited, since restarting them would discard their progress.
+    attempt_timeout: Duration,
+}
 
-        let download_result = download_file(&artifact.url, path);
-        match download_result {
-            Ok(()) => log_progress.done(),
-            Err(ref error) => log_progress.cancel(format!("Failed: {error}")),
+const DOWNLOAD_POLICY: DownloadPolicy = DownloadPolicy {
+    max_retries: 4,
+    initial_retry_delay: Duration::from_secs(1),
+    max_retry_delay: Duration::from_secs(16),
+    read_timeout: Duration::from_secs(30),
+    attempt_timeout: Duration::from_mins(10),
+};
+
+/// Tracks the download attempts made so far, and waits between them using exponential backoff
+/// with jitter.
+struct DownloadAttempts<'a> {
+    policy: &'a DownloadPolicy,
+    number: usize,
+    delays: Exponential,
+}
+
+impl<'a> DownloadAttempts<'a> {
+    fn new(policy: &'a DownloadPolicy) -> Self {
+        Self {
+            policy,
+            number: 1,
+            delays: Exponential::from(policy.initial_retry_delay),
+        }
+    }
+
+    fn max(&self) -> usize {
+        self.policy.max_retries + 1
+    }
+
+    /// Waits before the next attempt, or returns `false` if there are no retries left.
+    fn wait_for_next(&mut self) -> bool {
+        if self.number > self.policy.max_retries {
+            return false;
         }
-        match download_result {
-            Ok(()) => OperationResult::Ok(()),
+        std::thread::sleep(self.next_delay());
+        self.number += 1;
+        true
+    }
+
+    fn next_delay(&mut self) -> Duration {
+        let delay = self
+            .delays
+            .next()
+            .map_or(self.policy.max_retry_delay, |delay| {
+                delay.min(self.policy.max_retry_delay)
+            });
+        // Half of the delay is randomized, so concurrent builds don't retry in lockstep while
+        // still backing off.
+        delay / 2 + jitter(delay / 2)
+    }
+}
+
+/// A reader over an HTTP download that resumes the transfer with a `Range` request when it fails
+/// part way, if the server supports range requests.
+///
+/// Every byte is returned exactly once, so the reader can feed the checksum and decompression
+/// pipeline directly without restarting it. Failures that can't be resumed are returned to the
+/// caller, which can restart the download from the beginning.
+struct ResumableDownload<'a, 'b> {
+    client: &'a Client,
+    url: &'a str,
+    attempts: &'a mut DownloadAttempts<'b>,
+    response: Response,
+    accepts_ranges: bool,
+    /// The `ETag` or `Last-Modified` value of the first response, so that a resumed transfer is
+    /// only continued if the file hasn't changed on the server.
+    validator: Option<HeaderValue>,
+    bytes_received: u64,
+    started_at: Instant,
+    attempt_started_at: Instant,
+    progress: Option<AttemptProgress>,
+}
+
+impl<'a, 'b> ResumableDownload<'a, 'b> {
+    fn start(
+        client: &'a Client,
+        url: &'a str,
+        attempts: &'a mut DownloadAttempts<'b>,
+    ) -> Result<Self, DownloadError> {
+        let progress = AttemptProgress::start(
+            if attempts.number == 1 {
+                format!("Downloading SDK from {}", style::url(url))
+            } else {
+                format!("Retrying download ({}/{})", attempts.number, attempts.max())
+            },
+            0,
+        );
+
+        let response = match send_request(client.get(url)) {
+            Ok(response) => response,
             Err(error) => {
-                let is_retryable = match &error {
-                    DownloadError::IoError(_) => true,
-                    DownloadError::HttpError(http_error) => http_error
-                        .status()
-                        .is_none_or(|status| !status.is_client_error()),
-                };
-                if is_retryable {
-                    OperationResult::Retry(error)
-                } else {
-                    OperationResult::Err(error)
+                progress.cancel(0, &error);
+                return Err(error);
+            }
+        };
+        let headers = response.headers();
+        let accepts_ranges = headers
+            .get(ACCEPT_RANGES)
+            .is_some_and(|value| value == "bytes");
+        let validator = headers
+            .get(ETAG)
+            .filter(|etag| !etag.as_bytes().starts_with(b"W/"))
+            .or_else(|| headers.get(LAST_MODIFIED))
+            .cloned();
+
+        Ok(Self {
+            client,
+            url,
+            attempts,
+            response,
+            accepts_ranges,
+            validator,
+            bytes_received: 0,
+            started_at: Instant::now(),
+            attempt_started_at: Instant::now(),
+            progress: Some(progress),
+        })
+    }
+
+    /// Logs the outcome of the download, including the bytes transferred and the throughput.
+    fn finish(mut self, error: Option<&SdkLayerError>) {
+        match (self.progress.take(), error) {
+            (Some(progress), None) => progress.timer.done(),
+            (Some(progress), Some(error)) => progress.cancel(self.bytes_received, error),
+            (None, _) => {}
+        }
+
+        if error.is_none() {
+            let elapsed_millis = self.started_at.elapsed().as_millis().max(1);
+            let bytes_per_second = u128::from(self.bytes_received) * 1000 / elapsed_millis;
+            print::sub_bullet(format!(
+                "Downloaded {} ({}/s)",
+                format_bytes(self.bytes_received),
+                format_bytes(u64::try_from(bytes_per_second).unwrap_or(u64::MAX))
+            ));
+        }
+    }
+
+    /// Resumes the transfer at the current offset after `error`, within the retry budget.
+    fn resume(&mut self, mut error: io::Error) -> io::Result<()> {
+        loop {
+            if let Some(progress) = self.progress.take() {
+                progress.cancel(self.bytes_received, &error);
+            }
+            if !self.accepts_ranges || !self.attempts.wait_for_next() {
+                return Err(error);
+            }
+
+            self.attempt_started_at = Instant::now();
+            self.progress = Some(AttemptProgress::start(
+                format!(
+                    "Resuming download at {} ({}/{})",
+                    format_bytes(self.bytes_received),
+                    self.attempts.number,
+                    self.attempts.max()
+                ),
+                self.bytes_received,
+            ));
+
+            let mut request = self
+                .client
+                .get(self.url)
+                .header(RANGE, format!("bytes={}-", self.bytes_received));
+            if let Some(validator) = &self.validator {
+                request = request.header(IF_RANGE, validator);
+            }
+
+            match send_request(request) {
+                Ok(response) if self.is_resumed_response(&response) => {
+                    self.response = response;
+                    return Ok(());
+                }
+                Ok(_) => {
+                    // The server sent the full file (or a different range), so the download
+                    // has to be restarted from the beginning.
+                    self.accepts_ranges = false;
+                    error = io::Error::other("The server did not resume the download");
                 }
+                Err(download_error) => error = io::Error::other(download_error),
             }
         }
-    })
-    .map_err(|error| error.error)
+    }
+
+    fn is_resumed_response(&self, response: &Response) -> bool {
+        response.status() == StatusCode::PARTIAL_CONTENT
+            && response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with(&format!("bytes {}-", self.bytes_received)))
+    }
 }
 
-#[instrument(skip_all, err, fields(
-    http.request.method = "GET",
-    url.full = %url,
-))]
-fn download_file(url: &str, destination: &Path) -> Result<(), DownloadError> {
-    libherokubuildpack::download::download_file(url, destination)
+impl Read for ResumableDownload<'_, '_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read_result = if self.accepts_ranges
+                && self.attempt_started_at.elapsed() > self.attempts.policy.attempt_timeout
+            {
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "The download attempt timed out",
+                ))
+            } else {
+                self.response.read(buf)
+            };
+
+            match read_result {
+                Ok(bytes_read) => {
+                    self.bytes_received += bytes_read as u64;
+                    return Ok(bytes_read);
+                }
+                Err(error) => self.resume(error)?,
+            }
+        }
+    }
+}
+
+/// The progress output of a single download attempt.
+struct AttemptProgress {
+    timer: GlobalTimer,
+    start_offset: u64,
+}
+
+impl AttemptProgress {
+    fn start(message: String, start_offset: u64) -> Self {
+        Self {
+            timer: print::sub_start_timer(message),
+            start_offset,
+        }
+    }
+
+    fn cancel(self, bytes_received: u64, error: &dyn std::fmt::Display) {
+        self.timer.cancel(format!(
+            "Failed after {}: {error}",
+            format_bytes(bytes_received - self.start_offset)
+        ));
+    }
+}
+
+fn send_request(request: RequestBuilder) -> Result<Response, DownloadError> {
+    Ok(request.send()?.error_for_status()?)
+}
+
+#[instrument(skip_all, err(Debug), fields(file.path = %path.display()))]
+fn stage_local_sdk(path: &Path, layer_path: &Path) -> Result<StagedSdk, SdkLayerError> {
+    let log_progress = print::sub_start_timer(format!(
+        "Extracting SDK from local artifact directory {}",
+        style::value(path.to_string_lossy())
+    ));
+
+    let stage_result = fs_err::File::open(path)
+        .map_err(SdkLayerError::OpenLocalArchive)
+        .and_then(|file| {
+            let directory = create_staging_dir(layer_path)?;
+            extract_tarball::<Sha512>(file, directory.path()).map(|checksum| StagedSdk {
+                directory,
+                checksum,
+            })
+        });
+
+    match stage_result {
+        Ok(_) => log_progress.done(),
+        Err(ref error) => log_progress.cancel(format!("Failed: {error}")),
+    }
+    stage_result
+}
+
+fn create_staging_dir(layer_path: &Path) -> Result<TempDir, SdkLayerError> {
+    // The staging directory is created inside the layer so that installing the SDK is a cheap
+    // rename on the same filesystem, and concurrent builds never share a download location.
+    tempfile::Builder::new()
+        .prefix(".staging")
+        .tempdir_in(layer_path)
+        .map_err(SdkLayerError::PrepareStagingDirectory)
+}
+
+/// Decompresses and unpacks a gzipped tarball from `reader` into `destination`, returning the
+/// checksum of all bytes read from `reader`.
+fn extract_tarball<D: Digest>(
+    reader: impl Read,
+    destination: &Path,
+) -> Result<Vec<u8>, SdkLayerError> {
+    let mut digest_reader = DigestReader::<_, D>::new(reader);
+
+    let unpack_result = Archive::new(GzDecoder::new(&mut digest_reader))
+        .unpack(destination)
+        // Consume any trailing bytes (such as padding after the end of the tar archive) so
+        // that the checksum covers the entire downloaded file.
+        .and_then(|()| io::copy(&mut digest_reader, &mut io::sink()));
+
+    match unpack_result {
+        Ok(_) => Ok(digest_reader.finalize()),
+        Err(error) if digest_reader.source_failed => Err(SdkLayerError::DownloadArchive(
+            DownloadError::IoError(error),
+        )),
+        Err(error) => Err(SdkLayerError::DecompressArchive(error)),
+    }
+}
+
+/// A reader that feeds every byte read from the underlying reader into a digest.
+///
+/// Read errors from the underlying reader are recorded, so that errors caused by the source (e.g.
+/// a dropped connection) can be told apart from errors that occur while processing the data.
+struct DigestReader<R, D> {
+    inner: R,
+    digest: D,
+    source_failed: bool,
+}
+
+impl<R: Read, D: Digest> DigestReader<R, D> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            digest: D::new(),
+            source_failed: false,
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.digest.finalize().to_vec()
+    }
+}
+
+impl<R: Read, D: Digest> Read for DigestReader<R, D> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.inner.read(buf).inspect_err(|_| {
+            self.source_failed = true;
+        })?;
+        self.digest.update(&buf[..bytes_read]);
+        Ok(bytes_read)
+    }
 }
 
 #[instrument(skip_all, err(Debug))]
-fn verify_checksum<D>(checksum: &Checksum<D>, path: impl AsRef<Path>) -> Result<(), SdkLayerError>
+fn verify_checksum<D>(
+    checksum: &Checksum<D>,
+    calculated_checksum: Vec<u8>,
+) -> Result<(), SdkLayerError>
 where
     D: Digest,
 {
-    let calculated_checksum = fs_err::read(path.as_ref())
-        .map_err(SdkLayerError::ReadArchive)
-        .map(D::digest)?
-        .to_vec();
-
     if calculated_checksum == checksum.value {
         Ok(())
     } else {
@@ -165,21 +562,50 @@ where
 }
 
 #[instrument(skip_all, err(Debug))]
-fn extract_tarball(path: &Path, destination: &Path) -> Result<(), SdkLayerError> {
-    decompress_tarball(
-        &mut File::open(path).map_err(SdkLayerError::OpenArchive)?.into(),
-        destination,
-    )
-    .map_err(SdkLayerError::DecompressArchive)
+fn install_staged_sdk(staging_dir: &TempDir, destination: &Path) -> Result<(), SdkLayerError> {
+    for entry in fs_err::read_dir(staging_dir.path()).map_err(SdkLayerError::InstallStagedSdk)? {
+        let entry = entry.map_err(SdkLayerError::InstallStagedSdk)?;
+        fs_err::rename(entry.path(), destination.join(entry.file_name()))
+            .map_err(SdkLayerError::InstallStagedSdk)?;
+    }
+    Ok(())
 }
 
 #[derive(Debug)]
 pub(crate) enum SdkLayerError {
+    InvalidArtifactUrl(String),
+    PrepareStagingDirectory(std::io::Error),
     DownloadArchive(libherokubuildpack::download::DownloadError),
+    OpenLocalArchive(std::io::Error),
     DecompressArchive(std::io::Error),
     VerifyArchiveChecksum { expected: Vec<u8>, actual: Vec<u8> },
-    OpenArchive(std::io::Error),
-    ReadArchive(std::io::Error),
+    InstallStagedSdk(std::io::Error),
+}
+
+impl SdkLayerError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            SdkLayerError::DownloadArchive(DownloadError::IoError(_)) => true,
+            SdkLayerError::DownloadArchive(DownloadError::HttpError(http_error)) => http_error
+                .status()
+                .is_none_or(|status| !status.is_client_error()),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for SdkLayerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SdkLayerError::InvalidArtifactUrl(url) => write!(f, "Invalid artifact URL: {url}"),
+            SdkLayerError::DownloadArchive(error) => write!(f, "{error}"),
+            SdkLayerError::PrepareStagingDirectory(error)
+            | SdkLayerError::OpenLocalArchive(error)
+            | SdkLayerError::DecompressArchive(error)
+            | SdkLayerError::InstallStagedSdk(error) => write!(f, "{error}"),
+            SdkLayerError::VerifyArchiveChecksum { .. } => write!(f, "Checksum mismatch"),
+        }
+    }
 }
 
 impl From<SdkLayerError> for libcnb::Error<DotnetBuildpackError> {
@@ -187,3 +613,558 @@ impl From<SdkLayerError> for libcnb::Error<DotnetBuildpackError> {
         libcnb::Error::BuildpackError(DotnetBuildpackError::SdkLayer(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use libcnb_test::assert_matches;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, mpsc};
+
+    const INVENTORY_URL: &str = "https://builds.dotnet.microsoft.com/dotnet/Sdk/8.0.101/dotnet-sdk-8.0.101-linux-x64.tar.gz";
+
+    const TEST_DOWNLOAD_POLICY: DownloadPolicy = DownloadPolicy {
+        max_retries: 2,
+        initial_retry_delay: Duration::from_millis(1),
+        max_retry_delay: Duration::from_millis(1),
+        read_timeout: Duration::from_secs(5),
+        attempt_timeout: Duration::from_secs(5),
+    };
+
+    /// Starts a local HTTP server stand-in that passes each (lowercased) request head to
+    /// `handler`, which writes the raw response to the stream. Each connection is handled on its
+    /// own thread, and closed once `handler` returns. Returns the server base URL and a receiver
+    /// of the request heads.
+    fn start_http_server(
+        handler: impl Fn(&str, &mut TcpStream) + Send + Sync + 'static,
+    ) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        let handler = Arc::new(handler);
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().map_while(Result::ok) {
+                let handler = Arc::clone(&handler);
+                let sender = sender.clone();
+                std::thread::spawn(move || {
+                    let request_head = BufReader::new(&stream)
+                        .lines()
+                        .map_while(Result::ok)
+                        .take_while(|line| !line.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("\r\n")
+                        .to_lowercase();
+                    let _ = sender.send(request_head.clone());
+                    handler(&request_head, &mut stream);
+                });
+            }
+        });
+
+        (base_url, receiver)
+    }
+
+    /// Writes a response head announcing `content_length` bytes, followed by `body`, which may
+    /// be shorter to simulate a dropped connection.
+    fn write_response(
+        stream: &mut TcpStream,
+        status: &str,
+        headers: &[&str],
+        content_length: usize,
+        body: &[u8],
+    ) {
+        let mut head = format!("HTTP/1.1 {status}\r\nContent-Length: {content_length}\r\n");
+        for header in headers {
+            head.push_str(header);
+            head.push_str("\r\n");
+        }
+        head.push_str("Connection: close\r\n\r\n");
+        let _ = stream.write_all(head.as_bytes());
+        let _ = stream.write_all(body);
+    }
+
+    fn received_requests(receiver: &mpsc::Receiver<String>) -> Vec<String> {
+        receiver.try_iter().collect()
+    }
+
+    fn create_tarball(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+        for (path, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(
+                io::ErrorKind::ConnectionReset,
+                "connection reset",
+            ))
+        }
+    }
+
+    #[test]
+    fn test_extract_tarball_returns_checksum_of_archive() {
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh"), ("sdk/foo.txt", "foo")]);
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let checksum = extract_tarball::<Sha512>(tarball.as_slice(), temp_dir.path()).unwrap();
+
+        assert_eq!(checksum, Sha512::digest(&tarball).to_vec());
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("sdk/foo.txt")).unwrap(),
+            "foo"
+        );
+        assert!(temp_dir.path().join("dotnet").is_file());
+    }
+
+    #[test]
+    fn test_extract_tarball_checksum_includes_trailing_bytes() {
+        let mut tarball = create_tarball(&[("dotnet", "#!/bin/sh")]);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let checksum_without_trailing_bytes =
+            extract_tarball::<Sha512>(tarball.as_slice(), temp_dir.path()).unwrap();
+
+        tarball.extend_from_slice(&[0; 1024]);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let checksum = extract_tarball::<Sha512>(tarball.as_slice(), temp_dir.path()).unwrap();
+
+        assert_eq!(checksum, Sha512::digest(&tarball).to_vec());
+        assert_ne!(checksum, checksum_without_trailing_bytes);
+    }
+
+    #[test]
+    fn test_extract_tarball_invalid_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let result = extract_tarball::<Sha512>(b"not a tarball".as_slice(), temp_dir.path());
+
+        assert_matches!(result, Err(SdkLayerError::DecompressArchive(_)));
+    }
+
+    #[test]
+    fn test_extract_tarball_source_error_is_retryable_download_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let error = extract_tarball::<Sha512>(FailingReader, temp_dir.path()).unwrap_err();
+
+        assert_matches!(
+            error,
+            SdkLayerError::DownloadArchive(DownloadError::IoError(_))
+        );
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let expected = Sha512::digest(b"foo").to_vec();
+        let checksum = format!("sha512:{}", hex::encode(&expected))
+            .parse::<Checksum<Sha512>>()
+            .unwrap();
+
+        assert_matches!(verify_checksum(&checksum, expected.clone()), Ok(()));
+        assert_matches!(
+            verify_checksum(&checksum, Sha512::digest(b"bar").to_vec()),
+            Err(SdkLayerError::VerifyArchiveChecksum { expected: value, .. }) if value == expected
+        );
+    }
+
+    #[test]
+    fn test_mirror_artifact_url() {
+        assert_eq!(
+            mirror_artifact_url(
+                &Url::parse("https://artifactory.example.com").unwrap(),
+                INVENTORY_URL
+            )
+            .unwrap(),
+            "https://artifactory.example.com/dotnet/Sdk/8.0.101/dotnet-sdk-8.0.101-linux-x64.tar.gz"
+        );
+        assert_eq!(
+            mirror_artifact_url(
+                &Url::parse("https://artifactory.example.com/artifactory/dotnet-remote/").unwrap(),
+                INVENTORY_URL
+            )
+            .unwrap(),
+            "https://artifactory.example.com/artifactory/dotnet-remote/dotnet/Sdk/8.0.101/dotnet-sdk-8.0.101-linux-x64.tar.gz"
+        );
+        assert!(matches!(
+            mirror_artifact_url(
+                &Url::parse("https://artifactory.example.com").unwrap(),
+                "not a url"
+            ),
+            Err(SdkLayerError::InvalidArtifactUrl(url)) if url == "not a url"
+        ));
+    }
+
+    #[test]
+    fn test_artifact_file_name() {
+        assert_eq!(
+            artifact_file_name(INVENTORY_URL).unwrap(),
+            "dotnet-sdk-8.0.101-linux-x64.tar.gz"
+        );
+        for artifact_url in [
+            "not a url",
+            "https://builds.dotnet.microsoft.com/dotnet/Sdk/",
+        ] {
+            assert!(matches!(
+                artifact_file_name(artifact_url),
+                Err(SdkLayerError::InvalidArtifactUrl(url)) if url == artifact_url
+            ));
+        }
+    }
+
+    #[test]
+    fn test_download_sdk_from_mirror() {
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh")]);
+        let expected_checksum = Sha512::digest(&tarball).to_vec();
+        let (base_url, requests) = start_http_server(move |_, stream| {
+            write_response(stream, "200 OK", &[], tarball.len(), &tarball);
+        });
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let staged_sdk = download_sdk(
+            &mirror_artifact_url(&Url::parse(&base_url).unwrap(), INVENTORY_URL).unwrap(),
+            layer_dir.path(),
+            &TEST_DOWNLOAD_POLICY,
+        )
+        .unwrap();
+
+        assert!(
+            requests
+                .recv()
+                .unwrap()
+                .starts_with("get /dotnet/sdk/8.0.101/dotnet-sdk-8.0.101-linux-x64.tar.gz ")
+        );
+        assert_eq!(staged_sdk.checksum, expected_checksum);
+        assert!(staged_sdk.directory.path().join("dotnet").is_file());
+    }
+
+    #[test]
+    fn test_download_sdk_client_error_is_not_retried() {
+        let (base_url, requests) = start_http_server(|_, stream| {
+            write_response(stream, "404 Not Found", &[], 9, b"Not Found");
+        });
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let error = download_sdk(
+            &format!("{base_url}/sdk.tar.gz"),
+            layer_dir.path(),
+            &TEST_DOWNLOAD_POLICY,
+        )
+        .err()
+        .unwrap();
+
+        assert_matches!(
+            error,
+            SdkLayerError::DownloadArchive(DownloadError::HttpError(_))
+        );
+        assert!(!error.is_retryable());
+        assert_eq!(received_requests(&requests).len(), 1);
+    }
+
+    #[test]
+    fn test_download_sdk_resumes_interrupted_transfer() {
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh"), ("LICENSE.txt", "MIT")]);
+        let expected_checksum = Sha512::digest(&tarball).to_vec();
+        let split_at = tarball.len() / 2;
+        let (base_url, requests) = start_http_server(move |request_head, stream| {
+            let range_header = format!("range: bytes={split_at}-");
+            if request_head.contains(&range_header) && request_head.contains("if-range: \"v1\"") {
+                write_response(
+                    stream,
+                    "206 Partial Content",
+                    &[&format!(
+                        "Content-Range: bytes {split_at}-{}/{}",
+                        tarball.len() - 1,
+                        tarball.len()
+                    )],
+                    tarball.len() - split_at,
+                    &tarball[split_at..],
+                );
+            } else {
+                write_response(
+                    stream,
+                    "200 OK",
+                    &["Accept-Ranges: bytes", "ETag: \"v1\""],
+                    tarball.len(),
+                    &tarball[..split_at],
+                );
+            }
+        });
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let staged_sdk = download_sdk(
+            &format!("{base_url}/sdk.tar.gz"),
+            layer_dir.path(),
+            &TEST_DOWNLOAD_POLICY,
+        )
+        .unwrap();
+
+        assert_eq!(staged_sdk.checksum, expected_checksum);
+        assert!(staged_sdk.directory.path().join("LICENSE.txt").is_file());
+        let requests = received_requests(&requests);
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains(&format!("range: bytes={split_at}-")));
+    }
+
+    #[test]
+    fn test_download_sdk_restarts_transfer_without_range_support() {
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh")]);
+        let expected_checksum = Sha512::digest(&tarball).to_vec();
+        let request_count = Arc::new(AtomicUsize::new(0));
+        let (base_url, requests) = start_http_server(move |_, stream| {
+            let body = if request_count.fetch_add(1, Ordering::SeqCst) == 0 {
+                &tarball[..tarball.len() / 2]
+            } else {
+                &tarball[..]
+            };
+            write_response(stream, "200 OK", &[], tarball.len(), body);
+        });
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let staged_sdk = download_sdk(
+            &format!("{base_url}/sdk.tar.gz"),
+            layer_dir.path(),
+            &TEST_DOWNLOAD_POLICY,
+        )
+        .unwrap();
+
+        assert_eq!(staged_sdk.checksum, expected_checksum);
+        let requests = received_requests(&requests);
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|request| !request.contains("range:")));
+    }
+
+    #[test]
+    fn test_download_sdk_restarts_transfer_when_range_is_ignored() {
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh")]);
+        let request_count = Arc::new(AtomicUsize::new(0));
+        let (base_url, requests) = start_http_server(move |_, stream| {
+            let body = if request_count.fetch_add(1, Ordering::SeqCst) == 0 {
+                &tarball[..tarball.len() / 2]
+            } else {
+                &tarball[..]
+            };
+            write_response(
+                stream,
+                "200 OK",
+                &["Accept-Ranges: bytes"],
+                tarball.len(),
+                body,
+            );
+        });
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        download_sdk(
+            &format!("{base_url}/sdk.tar.gz"),
+            layer_dir.path(),
+            &TEST_DOWNLOAD_POLICY,
+        )
+        .unwrap();
+
+        let requests = received_requests(&requests);
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1].contains("range:"));
+        assert!(!requests[2].contains("range:"));
+    }
+
+    #[test]
+    fn test_download_sdk_gives_up_after_max_retries() {
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh")]);
+        let (base_url, requests) = start_http_server(move |_, stream| {
+            write_response(
+                stream,
+                "200 OK",
+                &[],
+                tarball.len(),
+                &tarball[..tarball.len() / 2],
+            );
+        });
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let error = download_sdk(
+            &format!("{base_url}/sdk.tar.gz"),
+            layer_dir.path(),
+            &TEST_DOWNLOAD_POLICY,
+        )
+        .err()
+        .unwrap();
+
+        assert_matches!(
+            error,
+            SdkLayerError::DownloadArchive(DownloadError::IoError(_))
+        );
+        assert_eq!(
+            received_requests(&requests).len(),
+            TEST_DOWNLOAD_POLICY.max_retries + 1
+        );
+    }
+
+    #[test]
+    fn test_download_sdk_resumes_after_attempt_timeout() {
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh")]);
+        let expected_checksum = Sha512::digest(&tarball).to_vec();
+        let (base_url, requests) = start_http_server(move |request_head, stream| {
+            if let Some(offset) = request_head
+                .split("range: bytes=")
+                .nth(1)
+                .and_then(|range| range.split('-').next())
+                .and_then(|offset| offset.parse::<usize>().ok())
+            {
+                write_response(
+                    stream,
+                    "206 Partial Content",
+                    &[&format!(
+                        "Content-Range: bytes {offset}-{}/{}",
+                        tarball.len() - 1,
+                        tarball.len()
+                    )],
+                    tarball.len() - offset,
+                    &tarball[offset..],
+                );
+            } else {
+                // Trickle the body, so that the attempt times out before it completes.
+                write_response(
+                    stream,
+                    "200 OK",
+                    &["Accept-Ranges: bytes"],
+                    tarball.len(),
+                    &[],
+                );
+                for byte in &tarball {
+                    if stream.write_all(&[*byte]).is_err() {
+                        break;
+                    }
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+            }
+        });
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let staged_sdk = download_sdk(
+            &format!("{base_url}/sdk.tar.gz"),
+            layer_dir.path(),
+            &DownloadPolicy {
+                attempt_timeout: Duration::from_millis(50),
+                ..TEST_DOWNLOAD_POLICY
+            },
+        )
+        .unwrap();
+
+        assert_eq!(staged_sdk.checksum, expected_checksum);
+        let requests = received_requests(&requests);
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].contains("range: bytes="));
+    }
+
+    #[test]
+    fn test_download_sdk_without_range_support_ignores_attempt_timeout() {
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh")]);
+        let expected_checksum = Sha512::digest(&tarball).to_vec();
+        let (base_url, requests) = start_http_server(move |_request_head, stream| {
+            // Trickle the body, so that the transfer takes longer than the attempt timeout.
+            write_response(stream, "200 OK", &[], tarball.len(), &[]);
+            for byte in &tarball {
+                if stream.write_all(&[*byte]).is_err() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        });
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let staged_sdk = download_sdk(
+            &format!("{base_url}/sdk.tar.gz"),
+            layer_dir.path(),
+            &DownloadPolicy {
+                attempt_timeout: Duration::from_millis(50),
+                ..TEST_DOWNLOAD_POLICY
+            },
+        )
+        .unwrap();
+
+        assert_eq!(staged_sdk.checksum, expected_checksum);
+        assert_eq!(received_requests(&requests).len(), 1);
+    }
+
+    #[test]
+    fn test_download_attempts_back_off_exponentially() {
+        let policy = DownloadPolicy {
+            initial_retry_delay: Duration::from_secs(1),
+            max_retry_delay: Duration::from_secs(4),
+            ..TEST_DOWNLOAD_POLICY
+        };
+        let mut attempts = DownloadAttempts::new(&policy);
+
+        for max_delay in [1, 2, 4, 4] {
+            let delay = attempts.next_delay();
+            let max_delay = Duration::from_secs(max_delay);
+            assert!(delay >= max_delay / 2 && delay <= max_delay, "{delay:?}");
+        }
+    }
+
+    #[test]
+    fn test_stage_local_sdk() {
+        let artifact_dir = tempfile::tempdir().unwrap();
+        let tarball = create_tarball(&[("dotnet", "#!/bin/sh")]);
+        let tarball_path = artifact_dir
+            .path()
+            .join(artifact_file_name(INVENTORY_URL).unwrap());
+        fs::write(&tarball_path, &tarball).unwrap();
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let staged_sdk = stage_local_sdk(&tarball_path, layer_dir.path()).unwrap();
+
+        assert_eq!(staged_sdk.checksum, Sha512::digest(&tarball).to_vec());
+        assert!(staged_sdk.directory.path().join("dotnet").is_file());
+    }
+
+    #[test]
+    fn test_stage_local_sdk_missing_archive() {
+        let artifact_dir = tempfile::tempdir().unwrap();
+        let layer_dir = tempfile::tempdir().unwrap();
+
+        let result = stage_local_sdk(
+            &artifact_dir
+                .path()
+                .join(artifact_file_name(INVENTORY_URL).unwrap()),
+            layer_dir.path(),
+        );
+
+        assert_matches!(result.err(), Some(SdkLayerError::OpenLocalArchive(_)));
+    }
+
+    #[test]
+    fn test_install_staged_sdk() {
+        let layer_dir = tempfile::tempdir().unwrap();
+        let staging_dir = tempfile::Builder::new()
+            .prefix(".staging")
+            .tempdir_in(layer_dir.path())
+            .unwrap();
+        fs::create_dir_all(staging_dir.path().join("shared")).unwrap();
+        fs::write(staging_dir.path().join("dotnet"), "").unwrap();
+        let staging_path = staging_dir.path().to_path_buf();
+
+        install_staged_sdk(&staging_dir, layer_dir.path()).unwrap();
+        drop(staging_dir);
+
+        assert!(layer_dir.path().join("dotnet").is_file());
+        assert!(layer_dir.path().join("shared").is_dir());
+        assert!(!staging_path.exists());
+    }
+}
@@ -0,0 +1,278 @@
+use crate::utils::{CopyMethod, FileCopier};
+use crate::{DotnetBuildpack, DotnetBuildpackError};
+use bullet_stream::global::print;
+use libcnb::build::BuildContext;
+use libcnb::data::layer_name;
+use libcnb::layer::{
+    CachedLayerDefinition, EmptyLayerCause, InvalidMetadataAction, LayerRef, LayerState,
+    RestoredLayerAction, UncachedLayerDefinition,
+};
+use libcnb::layer_env::{LayerEnv, ModificationBehavior, Scope};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize, Deserialize)]
+struct WorkloadsLayerMetadata {
+    sdk_version: Version,
+}
+
+pub(crate) enum CustomCause {
+    Ok,
+    DifferentSdkVersion(Version),
+}
+
+/// The SDK directories that the workload install root links to, so the `dotnet` executable in the
+/// install root resolves the same host, shared frameworks and SDKs as the SDK layer.
+const LINKED_SDK_DIRS: &[&str] = &["host", "shared", "sdk"];
+
+/// The directories that workloads are installed to, relative to the install root
+const WORKLOAD_DIRS: &[&str] = &["packs", "metadata", "template-packs", "library-packs"];
+
+/// Creates (or reuses) the layer that workloads are installed into, and returns the `dotnet`
+/// executable to install workloads with.
+///
+/// Workloads are installed in the `dotnet` root that the executable runs from, so the layer
+/// contains a separate install root: a copy of the SDK's `dotnet` executable, links to the SDK's
+/// host, shared frameworks and SDKs, and the workload manifests bundled with the SDK. This keeps
+/// the (cached) SDK layer unchanged. The layer's environment points the SDK to the installed
+/// workload manifests and packs (with `DOTNETSDK_WORKLOAD_MANIFEST_ROOTS` and
+/// `DOTNETSDK_WORKLOAD_PACK_ROOTS`).
+pub(crate) fn handle(
+    context: &BuildContext<DotnetBuildpack>,
+    available_at_launch: bool,
+    sdk_layer_path: &Path,
+    sdk_version: &Version,
+) -> Result<LayerRef<DotnetBuildpack, (), CustomCause>, libcnb::Error<DotnetBuildpackError>> {
+    let workloads_layer = context.cached_layer(
+        layer_name!("workloads"),
+        CachedLayerDefinition {
+            build: true,
+            launch: available_at_launch,
+            invalid_metadata_action: &|_| InvalidMetadataAction::DeleteLayer,
+            restored_layer_action: &|metadata: &WorkloadsLayerMetadata, _path| {
+                if metadata.sdk_version == *sdk_version {
+                    (RestoredLayerAction::KeepLayer, CustomCause::Ok)
+                } else {
+                    (
+                        RestoredLayerAction::DeleteLayer,
+                        CustomCause::DifferentSdkVersion(metadata.sdk_version.clone()),
+                    )
+                }
+            },
+        },
+    )?;
+
+    match workloads_layer.state {
+        LayerState::Restored { .. } => {
+            print::sub_bullet(format!(
+                "Reusing cached workloads (SDK version {sdk_version})"
+            ));
+        }
+        LayerState::Empty { ref cause } => {
+            if let EmptyLayerCause::RestoredLayerAction {
+                cause: CustomCause::DifferentSdkVersion(old_sdk_version),
+            } = cause
+            {
+                print::sub_bullet(format!(
+                    "Deleting cached workloads (SDK version {old_sdk_version})"
+                ));
+            }
+            copy_bundled_manifests(sdk_layer_path, &workloads_layer.path())
+                .map_err(DotnetBuildpackError::ConfigureWorkloads)?;
+            workloads_layer.write_metadata(WorkloadsLayerMetadata {
+                sdk_version: sdk_version.clone(),
+            })?;
+        }
+    }
+
+    configure_install_root(sdk_layer_path, &workloads_layer.path())
+        .map_err(DotnetBuildpackError::ConfigureWorkloads)?;
+
+    let scope = if available_at_launch {
+        Scope::All
+    } else {
+        Scope::Build
+    };
+    workloads_layer.write_env(
+        LayerEnv::new()
+            .chainable_insert(
+                scope.clone(),
+                ModificationBehavior::Override,
+                "DOTNETSDK_WORKLOAD_MANIFEST_ROOTS",
+                workloads_layer.path().join("sdk-manifests"),
+            )
+            .chainable_insert(
+                scope,
+                ModificationBehavior::Override,
+                "DOTNETSDK_WORKLOAD_PACK_ROOTS",
+                workloads_layer.path(),
+            ),
+    )?;
+
+    Ok(workloads_layer)
+}
+
+/// Removes a workloads layer restored from a previous build, for apps that no longer need
+/// workloads.
+pub(crate) fn remove(
+    context: &BuildContext<DotnetBuildpack>,
+) -> Result<(), libcnb::Error<DotnetBuildpackError>> {
+    context.uncached_layer(
+        layer_name!("workloads"),
+        UncachedLayerDefinition {
+            build: false,
+            launch: false,
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the `dotnet` executable of the workload install root in the workloads layer
+pub(crate) fn dotnet_executable(workloads_layer_path: &Path) -> PathBuf {
+    workloads_layer_path.join("dotnet")
+}
+
+/// Copies the workload manifests bundled with the SDK to the install root, where manifest updates
+/// are installed. Files are copied (rather than linked), so updates don't change the SDK layer.
+fn copy_bundled_manifests(sdk_layer_path: &Path, workloads_layer_path: &Path) -> io::Result<()> {
+    let bundled_manifests_dir = sdk_layer_path.join("sdk-manifests");
+    let manifests_dir = workloads_layer_path.join("sdk-manifests");
+    if bundled_manifests_dir.is_dir() {
+        FileCopier::starting_with(CopyMethod::Copy)
+            .copy_recursively(&bundled_manifests_dir, &manifests_dir)
+    } else {
+        fs_err::create_dir_all(&manifests_dir)
+    }
+}
+
+fn configure_install_root(sdk_layer_path: &Path, workloads_layer_path: &Path) -> io::Result<()> {
+    // The `dotnet` executable is copied, as it resolves the `dotnet` root from its own (resolved)
+    // location.
+    fs_err::copy(
+        sdk_layer_path.join("dotnet"),
+        dotnet_executable(workloads_layer_path),
+    )?;
+
+    for dir_name in LINKED_SDK_DIRS {
+        let link = workloads_layer_path.join(dir_name);
+        // Links restored from the cache are replaced, but anything else is left in place (and
+        // reported by the `symlink` call failing).
+        if link
+            .symlink_metadata()
+            .is_ok_and(|metadata| metadata.is_symlink())
+        {
+            fs_err::remove_file(&link)?;
+        }
+        fs_err::os::unix::fs::symlink(sdk_layer_path.join(dir_name), &link)?;
+    }
+
+    for dir_name in WORKLOAD_DIRS {
+        fs_err::create_dir_all(workloads_layer_path.join(dir_name))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_sdk_layer(sdk_layer_path: &Path) {
+        for path in [
+            "host/fxr/9.0.4/libhostfxr.so",
+            "shared/Microsoft.NETCore.App/9.0.4/libcoreclr.so",
+            "sdk/9.0.203/dotnet.dll",
+            "sdk-manifests/9.0.200/microsoft.net.workload.mono.toolchain.current/WorkloadManifest.json",
+            "packs/Microsoft.NETCore.App.Ref/9.0.4/data/FrameworkList.xml",
+        ] {
+            let path = sdk_layer_path.join(path);
+            fs_err::create_dir_all(path.parent().unwrap()).unwrap();
+            fs_err::write(path, "").unwrap();
+        }
+        fs_err::write(sdk_layer_path.join("dotnet"), "#!/bin/sh").unwrap();
+    }
+
+    /// Returns the files in a directory (recursively) with their contents
+    fn directory_contents(dir: &Path) -> BTreeMap<PathBuf, String> {
+        let mut contents = BTreeMap::new();
+        for entry in fs_err::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                contents.extend(directory_contents(&path));
+            } else {
+                contents.insert(path.clone(), fs_err::read_to_string(&path).unwrap());
+            }
+        }
+        contents
+    }
+
+    #[test]
+    fn test_configure_install_root() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sdk_layer_path = temp_dir.path().join("sdk");
+        let workloads_layer_path = temp_dir.path().join("workloads");
+        create_sdk_layer(&sdk_layer_path);
+        fs_err::create_dir_all(&workloads_layer_path).unwrap();
+
+        copy_bundled_manifests(&sdk_layer_path, &workloads_layer_path).unwrap();
+        // The install root is configured again when the layer is restored from the cache.
+        for _ in 0..2 {
+            configure_install_root(&sdk_layer_path, &workloads_layer_path).unwrap();
+        }
+
+        assert!(dotnet_executable(&workloads_layer_path).is_file());
+        for dir_name in LINKED_SDK_DIRS {
+            assert_eq!(
+                fs_err::read_link(workloads_layer_path.join(dir_name)).unwrap(),
+                sdk_layer_path.join(dir_name)
+            );
+        }
+        for dir_name in WORKLOAD_DIRS {
+            assert!(workloads_layer_path.join(dir_name).is_dir());
+        }
+        assert!(
+            workloads_layer_path
+                .join("sdk-manifests/9.0.200/microsoft.net.workload.mono.toolchain.current/WorkloadManifest.json")
+                .is_file()
+        );
+    }
+
+    #[test]
+    fn test_configure_install_root_leaves_sdk_layer_unchanged() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sdk_layer_path = temp_dir.path().join("sdk");
+        let workloads_layer_path = temp_dir.path().join("workloads");
+        create_sdk_layer(&sdk_layer_path);
+        fs_err::create_dir_all(&workloads_layer_path).unwrap();
+        let sdk_layer_contents = directory_contents(&sdk_layer_path);
+
+        copy_bundled_manifests(&sdk_layer_path, &workloads_layer_path).unwrap();
+        configure_install_root(&sdk_layer_path, &workloads_layer_path).unwrap();
+        // Simulate a manifest update and a pack install in the install root
+        fs_err::write(
+            workloads_layer_path.join("sdk-manifests/9.0.200/microsoft.net.workload.mono.toolchain.current/WorkloadManifest.json"),
+            "updated",
+        )
+        .unwrap();
+        fs_err::create_dir_all(
+            workloads_layer_path.join("packs/Microsoft.NET.Runtime.WebAssembly.Sdk"),
+        )
+        .unwrap();
+
+        assert_eq!(directory_contents(&sdk_layer_path), sdk_layer_contents);
+    }
+
+    #[test]
+    fn test_configure_install_root_keeps_existing_directories() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sdk_layer_path = temp_dir.path().join("sdk");
+        let workloads_layer_path = temp_dir.path().join("workloads");
+        create_sdk_layer(&sdk_layer_path);
+        fs_err::create_dir_all(workloads_layer_path.join("sdk/9.0.203")).unwrap();
+
+        assert!(configure_install_root(&sdk_layer_path, &workloads_layer_path).is_err());
+        assert!(workloads_layer_path.join("sdk/9.0.203").is_dir());
+    }
+}
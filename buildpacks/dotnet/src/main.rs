@@ -4,46 +4,71 @@ mod dotnet;
 mod dotnet_buildpack_configuration;
 mod dotnet_layer_env;
 mod dotnet_sdk_command;
+mod ef_migrations;
+mod emulation;
 mod errors;
+mod globalization;
 mod launch_process;
+mod launch_smoke_check;
 mod layers;
+mod native_aot;
+mod native_dependencies;
+mod procfile;
 mod project_toml;
+mod publish_scheduler;
 mod utils;
+mod workloads;
 
 use crate::app_source::{
     AppSource, DiscoveryError, FILE_BASED_APP_EXTENSIONS, LoadError, PROJECT_EXTENSIONS,
     SOLUTION_EXTENSIONS,
 };
 use crate::dotnet::global_json::{GlobalJson, SdkConfig, SdkConfigError};
+use crate::dotnet::msbuild_diagnostics::{self, Diagnostic};
 use crate::dotnet::project::Project;
-use crate::dotnet::runtime_identifier;
+use crate::dotnet::runtime_config::RuntimeConfig;
+use crate::dotnet::runtime_identifier::{self, RuntimeIdentifier};
 use crate::dotnet::solution::Solution;
 use crate::dotnet::target_framework_moniker::{ParseTargetFrameworkError, TargetFrameworkMoniker};
 use crate::dotnet_buildpack_configuration::{
-    DotnetBuildpackConfiguration, DotnetBuildpackConfigurationError, ExecutionEnvironment,
+    DotnetBuildpackConfiguration, DotnetBuildpackConfigurationError, EfMigrationsOptions,
+    ExecutionEnvironment, GlobalizationOptions, ProcfileMode, PublishMode, PublishStrategy,
+    SdkArtifactSource, VerbosityLevel,
 };
-use crate::dotnet_sdk_command::{DotnetPublishCommand, DotnetTestCommand};
+use crate::dotnet_sdk_command::{
+    DotnetEfMigrationsBundleCommand, DotnetPublishCommand, DotnetTestCommand,
+};
+use crate::ef_migrations::MigrationsProjectsError;
+use crate::globalization::GlobalizationMode;
+use crate::launch_process::{LaunchProfileError, ProjectLaunchProfile};
+use crate::launch_smoke_check::{LaunchSmokeCheck, LaunchSmokeCheckError, LaunchSmokeCheckOutcome};
 use crate::layers::sdk::SdkLayerError;
+use crate::native_aot::NativeAotPrerequisite;
+use crate::procfile::Procfile;
 use crate::project_toml::DotnetConfig;
+use crate::publish_scheduler::{PublishGraph, PublishJob, PublishProjectsError};
 use crate::utils::{PathsExt, list_files};
 use bullet_stream::fun_run::{self, CommandWithName};
 use bullet_stream::global::print;
 use bullet_stream::style;
-use indoc::printdoc;
+use indoc::{formatdoc, printdoc};
 use inventory::artifact::{Arch, Os};
 use inventory::{Inventory, ParseInventoryError};
+use libcnb::additional_buildpack_binary_path;
 use libcnb::build::{BuildContext, BuildResult, BuildResultBuilder};
-use libcnb::data::launch::{LaunchBuilder, Process};
+use libcnb::data::launch::{LaunchBuilder, Process, WorkingDirectory};
 use libcnb::data::layer_name;
 use libcnb::detect::{DetectContext, DetectResult, DetectResultBuilder};
 use libcnb::generic::{GenericMetadata, GenericPlatform};
 use libcnb::layer::UncachedLayerDefinition;
-use libcnb::layer_env::{LayerEnv, Scope};
+use libcnb::layer_env::{LayerEnv, ModificationBehavior, Scope};
 use libcnb::{Buildpack, Env, Target, buildpack_main};
 use libherokubuildpack::inventory;
 use libherokubuildpack::inventory::artifact::Artifact;
 use semver::{Version, VersionReq};
 use sha2::Sha512;
+use std::collections::HashMap;
+use std::env::temp_dir;
 use std::io;
 use std::io::{Write, stderr};
 use std::path::{Path, PathBuf};
@@ -131,6 +156,40 @@ impl Buildpack for DotnetBuildpack {
         let solution =
             Solution::try_from(app_source).map_err(DotnetBuildpackError::LoadAppSource)?;
 
+        let project_graph = solution.project_graph();
+        if matches!(
+            buildpack_configuration.msbuild_verbosity_level,
+            Some(VerbosityLevel::Detailed | VerbosityLevel::Diagnostic)
+        ) {
+            let roots = project_graph.roots();
+            let roots = if roots.is_empty() {
+                solution
+                    .projects
+                    .iter()
+                    .map(|project| project.path.as_path())
+                    .collect()
+            } else {
+                roots
+            };
+            print::sub_bullet(format!(
+                "Project dependency tree:\n{}",
+                project_graph.format_tree(&roots, &context.app_dir)
+            ));
+        }
+        for cycle in project_graph.cycles() {
+            print::warning(formatdoc! {"
+                Circular project reference detected:
+                {cycle}
+
+                MSBuild doesn't support circular project references, so publishing is likely
+                to fail. Remove one of the `ProjectReference` items to break the cycle.
+            ", cycle = cycle
+            .iter()
+            .map(|path| path.strip_prefix(&context.app_dir).unwrap_or(path).to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" -> ")});
+        }
+
         let sdk_version_requirement = detect_sdk_version_requirement(&context, &solution)?;
 
         let sdk_artifact = resolve_sdk_artifact(&context.target, sdk_version_requirement)?;
@@ -140,12 +199,35 @@ impl Buildpack for DotnetBuildpack {
             ExecutionEnvironment::Test => Scope::All,
         };
         let sdk_available_at_launch = matches!(sdk_scope, Scope::Launch | Scope::All);
+        let write_xor_execute = buildpack_configuration
+            .write_xor_execute
+            .with_emulation_defaults(emulation::is_emulated());
 
-        let sdk_layer = layers::sdk::handle(&context, sdk_available_at_launch, &sdk_artifact)?;
+        // Relative local artifact directories are resolved against the app directory.
+        let sdk_artifact_source = match buildpack_configuration.sdk_artifact_source {
+            SdkArtifactSource::LocalDirectory(path) => {
+                SdkArtifactSource::LocalDirectory(context.app_dir.join(path))
+            }
+            source => source,
+        };
+
+        let sdk_layer = layers::sdk::handle(
+            &context,
+            sdk_available_at_launch,
+            &sdk_artifact,
+            &sdk_artifact_source,
+        )?;
         sdk_layer.write_env(dotnet_layer_env::generate_layer_env(
             sdk_layer.path().as_path(),
             &sdk_scope,
+            &buildpack_configuration.globalization_options,
+            write_xor_execute,
         ))?;
+        log_write_xor_execute(
+            buildpack_configuration.write_xor_execute.build,
+            write_xor_execute.build,
+            "during build",
+        );
 
         let nuget_cache_layer = layers::nuget_cache::handle(&context, sdk_available_at_launch)?;
         nuget_cache_layer.write_env(
@@ -178,7 +260,7 @@ impl Buildpack for DotnetBuildpack {
             dotnet_cli_layer.path(),
         ))?;
 
-        let command_env = dotnet_cli_layer.read_env()?.apply(
+        let mut command_env = dotnet_cli_layer.read_env()?.apply(
             Scope::Build,
             &nuget_cache_layer.read_env()?.apply(
                 Scope::Build,
@@ -209,57 +291,468 @@ impl Buildpack for DotnetBuildpack {
             .map_err(DotnetBuildpackError::RestoreDotnetToolsCommand)?;
         }
 
+        let workload_projects = workloads::projects_requiring_workloads(&solution)
+            .map(|project| style::value(&project.assembly_name))
+            .collect::<Vec<_>>();
+        if buildpack_configuration
+            .workload_restore
+            .unwrap_or(!workload_projects.is_empty())
+        {
+            print::bullet("Restore .NET workloads");
+            if workload_projects.is_empty() {
+                print::sub_bullet("Workload restore enabled by configuration");
+            } else {
+                print::sub_bullet(format!(
+                    "Detected projects that need the {} workload: {}",
+                    style::value(workloads::WASM_TOOLS_WORKLOAD),
+                    workload_projects.join(", ")
+                ));
+            }
+            let workloads_layer = layers::workloads::handle(
+                &context,
+                sdk_available_at_launch,
+                &sdk_layer.path(),
+                &sdk_artifact.version,
+            )?;
+
+            let mut restore_workloads_command = Command::new(layers::workloads::dotnet_executable(
+                &workloads_layer.path(),
+            ));
+            restore_workloads_command
+                .args(["workload", "restore", &solution.path.to_string_lossy()])
+                .current_dir(&context.app_dir)
+                .envs(&command_env);
+            print::sub_stream_with(
+                format!(
+                    "Running {}",
+                    style::command(restore_workloads_command.name())
+                ),
+                |stdout, stderr| restore_workloads_command.stream_output(stdout, stderr),
+            )
+            .map_err(DotnetBuildpackError::RestoreWorkloadsCommand)?;
+            command_env = workloads_layer
+                .read_env()?
+                .apply(Scope::Build, &command_env);
+        } else {
+            layers::workloads::remove(&context)?;
+        }
+
         let mut launch_builder = LaunchBuilder::new();
         match buildpack_configuration.execution_environment {
             ExecutionEnvironment::Production => {
                 print::bullet("Publish app");
+                let publish_mode = buildpack_configuration.publish_mode;
+                if let Some(publish_mode) = publish_mode {
+                    print::sub_bullet(format!(
+                        "Using configured publish mode: {}",
+                        style::value(publish_mode.to_string())
+                    ));
+                }
 
-                let mut publish_command = Command::from(DotnetPublishCommand {
-                    path: solution.path.clone(),
-                    configuration: buildpack_configuration.build_configuration,
-                    runtime_identifier: runtime_identifier::get_runtime_identifier(
-                        sdk_artifact.os,
-                        sdk_artifact.arch,
-                    ),
-                    verbosity_level: buildpack_configuration.msbuild_verbosity_level,
-                });
-                publish_command
-                    .current_dir(&context.app_dir)
-                    .envs(&command_env);
-
-                print::sub_stream_with(
-                    format!("Running {}", style::command(publish_command.name())),
-                    |stdout, stderr| publish_command.stream_output(stdout, stderr),
-                )
-                .map_err(DotnetBuildpackError::PublishCommand)?;
+                let native_aot_projects = launch_process::launchable_projects(&solution)
+                    .filter(|project| project.publish_aot)
+                    .map(|project| style::value(&project.assembly_name))
+                    .collect::<Vec<_>>();
+                if !native_aot_projects.is_empty() {
+                    print::sub_bullet(format!(
+                        "Native AOT compilation enabled for: {}",
+                        native_aot_projects.join(", ")
+                    ));
+                    let missing_prerequisites = native_aot::missing_prerequisites(&command_env);
+                    if !missing_prerequisites.is_empty() {
+                        Err(DotnetBuildpackError::MissingNativeAotPrerequisites(
+                            missing_prerequisites,
+                        ))?;
+                    }
+                }
+
+                // A configured publish mode is passed to `dotnet publish` as global properties,
+                // which would also apply to Native AOT projects when publishing the solution.
+                let publish_strategy = if buildpack_configuration.publish_strategy
+                    == PublishStrategy::Solution
+                    && publish_mode.is_some()
+                    && !native_aot_projects.is_empty()
+                {
+                    print::sub_bullet(
+                        "Publishing launchable projects separately, since the configured publish mode doesn't apply to Native AOT projects",
+                    );
+                    PublishStrategy::Projects
+                } else {
+                    buildpack_configuration.publish_strategy
+                };
+
+                let publish_projects = match publish_strategy {
+                    PublishStrategy::Solution => None,
+                    PublishStrategy::Projects => {
+                        let projects =
+                            launch_process::launchable_projects(&solution).collect::<Vec<_>>();
+                        if projects.is_empty() {
+                            print::sub_bullet(
+                                "No launchable projects found, publishing the solution instead",
+                            );
+                            None
+                        } else {
+                            print::sub_bullet(format!(
+                                "Publishing {} launchable project(s) separately",
+                                projects.len()
+                            ));
+                            Some(projects)
+                        }
+                    }
+                };
+
+                let msbuild_logs_dir = if buildpack_configuration.msbuild_binary_log {
+                    Some(layers::msbuild_logs::handle(&context)?)
+                } else {
+                    None
+                };
+
+                let build_artifacts_dir = temp_dir().join("build_artifacts");
+                let create_publish_command =
+                    |publish_path: &Path,
+                     publish_mode: Option<PublishMode>,
+                     artifacts_path: PathBuf| {
+                        let binary_log_path = msbuild_logs_dir.as_ref().map(|dir| {
+                            layers::msbuild_logs::binary_log_path(
+                                dir,
+                                &context.app_dir,
+                                publish_path,
+                                &solution.path,
+                            )
+                        });
+
+                        let mut publish_command = Command::from(DotnetPublishCommand {
+                            path: publish_path.to_path_buf(),
+                            configuration: buildpack_configuration.build_configuration.clone(),
+                            runtime_identifier: runtime_identifier::get_runtime_identifier(
+                                sdk_artifact.os,
+                                sdk_artifact.arch,
+                            ),
+                            publish_mode,
+                            ready_to_run: buildpack_configuration.publish_ready_to_run,
+                            verbosity_level: buildpack_configuration.msbuild_verbosity_level,
+                            binary_log_path: binary_log_path.clone(),
+                            artifacts_path,
+                        });
+                        publish_command
+                            .current_dir(&context.app_dir)
+                            .envs(&command_env);
+                        if binary_log_path.is_some() {
+                            // Binary logs only include the environment variables read as `MSBuild`
+                            // properties, unless this variable is set (which would embed every
+                            // environment variable, including secrets, in the log).
+                            publish_command.env_remove("MSBUILDLOGALLENVIRONMENTVARIABLES");
+                        }
+                        (publish_command, binary_log_path)
+                    };
+
+                let concurrency = buildpack_configuration.publish_concurrency;
+                let publish_stdout = match &publish_projects {
+                    Some(projects) if projects.len() > 1 && concurrency.get() > 1 => {
+                        let graph = PublishGraph::new(projects, &project_graph);
+                        let mut binary_log_paths = Vec::new();
+                        // Projects published at the same time use separate build artifacts
+                        // directories, so concurrent `MSBuild` processes (and their implicit
+                        // restores) never write to the same intermediate files.
+                        let mut jobs = projects
+                            .iter()
+                            .enumerate()
+                            .map(|(index, project)| {
+                                let (command, binary_log_path) = create_publish_command(
+                                    &project.path,
+                                    launch_process::publish_command_mode(project, publish_mode),
+                                    build_artifacts_dir.join(index.to_string()),
+                                );
+                                binary_log_paths.extend(binary_log_path);
+                                PublishJob {
+                                    name: project.assembly_name.clone(),
+                                    command,
+                                }
+                            })
+                            .collect::<Vec<_>>();
+
+                        let publish_result = print::sub_stream_with(
+                            format!(
+                                "Publishing projects in parallel (up to {concurrency} at a time)"
+                            ),
+                            |stdout, _stderr| {
+                                publish_scheduler::publish_projects(
+                                    &mut jobs,
+                                    &graph,
+                                    concurrency,
+                                    stdout,
+                                )
+                            },
+                        );
+                        for binary_log_path in &binary_log_paths {
+                            log_binary_log_path(binary_log_path);
+                        }
+                        publish_result.map_err(DotnetBuildpackError::PublishProjects)?
+                    }
+                    _ => {
+                        let publish_paths = publish_projects.as_ref().map_or_else(
+                            || vec![(solution.path.clone(), publish_mode)],
+                            |projects| {
+                                projects
+                                    .iter()
+                                    .map(|project| {
+                                        (
+                                            project.path.clone(),
+                                            launch_process::publish_command_mode(
+                                                project,
+                                                publish_mode,
+                                            ),
+                                        )
+                                    })
+                                    .collect()
+                            },
+                        );
+
+                        // Projects are published one at a time with the same artifacts path, so
+                        // shared dependencies built by an earlier command are up to date (and
+                        // skipped by `MSBuild`'s incremental build) when later projects are
+                        // published.
+                        let mut publish_stdout = String::new();
+                        for (publish_path, publish_mode) in &publish_paths {
+                            let (mut publish_command, binary_log_path) = create_publish_command(
+                                publish_path,
+                                *publish_mode,
+                                build_artifacts_dir.clone(),
+                            );
+                            let publish_result = print::sub_stream_with(
+                                format!("Running {}", style::command(publish_command.name())),
+                                |stdout, stderr| publish_command.stream_output(stdout, stderr),
+                            );
+                            if let Some(binary_log_path) = &binary_log_path {
+                                log_binary_log_path(binary_log_path);
+                            }
+                            let publish_output =
+                                publish_result.map_err(DotnetBuildpackError::PublishCommand)?;
+                            publish_stdout.push_str(&publish_output.stdout_lossy());
+                        }
+                        publish_stdout
+                    }
+                };
+
+                let trim_analysis_warnings =
+                    msbuild_diagnostics::parse_diagnostics(&publish_stdout)
+                        .into_iter()
+                        .filter(Diagnostic::is_trim_analysis_warning)
+                        .collect::<Vec<_>>();
+                if !trim_analysis_warnings.is_empty()
+                    || launch_process::launchable_projects(&solution)
+                        .any(|project| project.publish_trimmed || project.publish_aot)
+                {
+                    log_trim_analysis_warnings(&trim_analysis_warnings);
+                    if buildpack_configuration.publish_fail_on_trim_warnings
+                        && !trim_analysis_warnings.is_empty()
+                    {
+                        Err(DotnetBuildpackError::TrimAnalysisWarnings(
+                            trim_analysis_warnings,
+                        ))?;
+                    }
+                }
+                // The launch environment of the runtime layer, which framework-dependent apps run
+                // with at launch (unless the SDK is available at launch).
+                let mut runtime_layer_env = None;
                 if !sdk_available_at_launch {
-                    layers::runtime::handle(&context, &sdk_layer.path())?;
+                    let launchable_projects =
+                        launch_process::launchable_projects(&solution).collect::<Vec<_>>();
+                    let framework_dependent_projects = launchable_projects
+                        .iter()
+                        .filter(|project| {
+                            launch_process::project_publish_mode(project, publish_mode)
+                                == PublishMode::FrameworkDependent
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !launchable_projects.is_empty() && framework_dependent_projects.is_empty() {
+                        print::bullet("Runtime installation");
+                        print::sub_bullet(
+                            "Skipping runtime installation (all launchable projects are self-contained or Native AOT apps)",
+                        );
+                        layers::runtime::remove(&context)?;
+                    } else {
+                        let runtime_config_paths = framework_dependent_projects
+                            .into_iter()
+                            .map(|project| launch_process::project_runtime_config_path(project))
+                            .collect::<Vec<_>>();
+                        runtime_layer_env = Some(layers::runtime::handle(
+                            &context,
+                            &sdk_layer.path(),
+                            &sdk_artifact,
+                            &runtime_config_paths,
+                            &buildpack_configuration.globalization_options,
+                            write_xor_execute,
+                        )?);
+                    }
                 }
 
+                let ef_migrations_process = create_ef_migrations_bundle(
+                    &context,
+                    &solution,
+                    &buildpack_configuration.ef_migrations,
+                    buildpack_configuration.build_configuration.as_deref(),
+                    runtime_identifier::get_runtime_identifier(sdk_artifact.os, sdk_artifact.arch),
+                    &command_env,
+                )?;
+
+                log_native_dependencies(
+                    &solution,
+                    &runtime_identifier::get_runtime_identifier(sdk_artifact.os, sdk_artifact.arch),
+                    &buildpack_configuration.run_image_libraries,
+                )?;
+                log_globalization_modes(
+                    &solution,
+                    publish_mode,
+                    sdk_available_at_launch,
+                    &buildpack_configuration.globalization_options,
+                    &context.target,
+                    &buildpack_configuration.run_image_libraries,
+                );
+
                 print::bullet("Process types");
+                let launch_profiles = launch_process::load_launch_profiles(
+                    &solution,
+                    buildpack_configuration.launch_profile.as_deref(),
+                )
+                .map_err(DotnetBuildpackError::LaunchProfile)?;
+                log_launch_profiles(&context.app_dir, &solution, &launch_profiles);
                 print::sub_bullet("Detecting process types from published artifacts");
-                let processes =
-                    launch_process::detect_solution_processes(&context.app_dir, &solution);
+                let mut processes = launch_process::detect_solution_processes(
+                    &context.app_dir,
+                    &solution,
+                    publish_mode,
+                    &launch_profiles,
+                    buildpack_configuration.launch_process_mode,
+                );
+                processes.extend(ef_migrations_process);
                 if processes.is_empty() {
                     print::sub_bullet("No processes were detected");
+                }
+                log_detected_processes(&processes);
+
+                let procfile_path = context.app_dir.join("Procfile");
+                let procfile = if procfile_path.exists() {
+                    Some(
+                        fs_err::read_to_string(&procfile_path)
+                            .map_err(DotnetBuildpackError::ReadProcfile)?
+                            .parse::<Procfile>()
+                            .unwrap_or_default(),
+                    )
                 } else {
-                    for process in &processes {
-                        print::sub_bullet(format!(
-                            "Found {}: {}",
-                            style::value(process.r#type.to_string()),
-                            process.command.join(" ")
-                        ));
+                    None
+                };
+
+                let registered_processes = match &procfile {
+                    None if processes.is_empty() => vec![],
+                    None => {
+                        print::sub_bullet("No Procfile detected");
+                        print::sub_bullet("Registering detected process types as launch processes");
+                        processes
                     }
-                    if Path::exists(&context.app_dir.join("Procfile")) {
+                    Some(procfile) => {
                         print::sub_bullet("Procfile detected");
-                        print::sub_bullet(
-                            "Skipping process type registration (add process types to your Procfile as needed)",
-                        );
+                        log_missing_procfile_entrypoints(&context.app_dir, procfile);
+                        register_processes_with_procfile(
+                            processes,
+                            procfile,
+                            buildpack_configuration.procfile_mode,
+                        )
+                    }
+                };
+                if !registered_processes.is_empty() && !launch_profiles.is_empty() {
+                    context
+                        .uncached_layer(
+                            layer_name!("launch-profiles"),
+                            UncachedLayerDefinition {
+                                build: false,
+                                launch: true,
+                            },
+                        )?
+                        .write_env(launch_process::generate_launch_profiles_env(
+                            &solution,
+                            &launch_profiles,
+                        ))?;
+                }
+                launch_builder.processes(registered_processes);
+
+                if buildpack_configuration.launch_smoke_check {
+                    // Framework-dependent apps are checked with the shared frameworks installed in
+                    // the runtime layer, as they are at launch.
+                    let smoke_check_env = runtime_layer_env.as_ref().map_or_else(
+                        || command_env.clone(),
+                        |layer_env| layer_env.apply(Scope::Launch, &command_env),
+                    );
+                    run_launch_smoke_checks(
+                        &solution,
+                        publish_mode,
+                        &launch_profiles,
+                        &smoke_check_env,
+                    )?;
+                }
+
+                print::bullet("Startup settings");
+                print::sub_bullet(format!(
+                    "ReadyToRun compilation: {}",
+                    style::value(if buildpack_configuration.publish_ready_to_run {
+                        "enabled"
                     } else {
-                        launch_builder.processes(processes.clone());
-                        print::sub_bullet("No Procfile detected");
-                        print::sub_bullet("Registering detected process types as launch processes");
+                        "disabled"
+                    })
+                ));
+                let runtime_options_env_vars = buildpack_configuration
+                    .runtime_options
+                    .environment_variables();
+                if runtime_options_env_vars.is_empty() {
+                    print::sub_bullet("Using the .NET runtime's default JIT compilation settings");
+                } else {
+                    for (name, value) in runtime_options_env_vars {
+                        print::sub_bullet(format!(
+                            "Setting {} at launch",
+                            style::value(format!("{name}={value}"))
+                        ));
                     }
+                    context
+                        .uncached_layer(
+                            layer_name!("runtime-options"),
+                            UncachedLayerDefinition {
+                                build: false,
+                                launch: true,
+                            },
+                        )?
+                        .write_env(dotnet_layer_env::generate_runtime_options_env(
+                            &buildpack_configuration.runtime_options,
+                        ))?;
+                }
+                let web_process_types = launch_process::web_process_types(&solution);
+                if !web_process_types.is_empty() {
+                    layers::kestrel::handle(&context, &web_process_types)?;
+                    let process_types = web_process_types
+                        .iter()
+                        .map(|process_type| style::value(process_type.to_string()))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    print::sub_bullet(format!(
+                        "Configuring Kestrel to listen on {} at launch for: {process_types}",
+                        style::value("$PORT")
+                    ));
+                    print::sub_bullet(format!(
+                        "Setting {} at launch for: {process_types}",
+                        style::value("ASPNETCORE_FORWARDEDHEADERS_ENABLED=true")
+                    ));
+                }
+                configure_gc_defaults(&context, &solution)?;
+                log_write_xor_execute(
+                    buildpack_configuration.write_xor_execute.launch,
+                    write_xor_execute.launch,
+                    "at launch",
+                );
+                if write_xor_execute.launch.is_none() {
+                    print::sub_bullet(format!(
+                        "Setting {} at launch when the app runs under emulation",
+                        style::value("DOTNET_EnableWriteXorExecute=0")
+                    ));
                 }
             }
             ExecutionEnvironment::Test => {
@@ -271,6 +764,8 @@ impl Buildpack for DotnetBuildpack {
             }
         }
 
+        configure_write_xor_execute_at_launch(&context, write_xor_execute.launch)?;
+
         print::all_done(&Some(started));
 
         BuildResultBuilder::new()
@@ -283,6 +778,550 @@ impl Buildpack for DotnetBuildpack {
     }
 }
 
+fn log_launch_profiles(
+    app_dir: &Path,
+    solution: &Solution,
+    launch_profiles: &HashMap<PathBuf, ProjectLaunchProfile>,
+) {
+    for project in launch_process::launchable_projects(solution) {
+        if let Some(launch_profile) = launch_profiles.get(&project.path) {
+            print::sub_bullet(format!(
+                "Using launch profile {} from {}",
+                style::value(&launch_profile.name),
+                style::value(
+                    launch_profile
+                        .launch_settings_path
+                        .strip_prefix(app_dir)
+                        .unwrap_or(&launch_profile.launch_settings_path)
+                        .to_string_lossy()
+                )
+            ));
+            if !launch_profile.ignored_arguments.is_empty() {
+                print::sub_bullet(format!(
+                    "Ignoring launch profile arguments {}",
+                    style::value(shell_words::join(&launch_profile.ignored_arguments))
+                ));
+            }
+            for name in &launch_profile.ignored_environment_variables {
+                print::sub_bullet(format!(
+                    "Ignoring launch profile environment variable {}",
+                    style::value(name)
+                ));
+            }
+        }
+    }
+}
+
+fn log_detected_processes(processes: &[Process]) {
+    for process in processes {
+        let command = process
+            .command
+            .iter()
+            .chain(&process.args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let working_directory = match &process.working_directory {
+            WorkingDirectory::App => String::new(),
+            WorkingDirectory::Directory(dir) => {
+                format!(" (in {})", dir.to_string_lossy())
+            }
+        };
+        print::sub_bullet(format!(
+            "Found {}: {command}{working_directory}",
+            style::value(process.r#type.to_string()),
+        ));
+    }
+}
+
+/// Checks that the native libraries needed by each launchable project's published app are bundled
+/// with the app or available on the run image, and warns about missing libraries.
+fn log_native_dependencies(
+    solution: &Solution,
+    runtime_identifier: &RuntimeIdentifier,
+    run_image_libraries: &[String],
+) -> Result<(), DotnetBuildpackError> {
+    print::bullet("Native library dependencies");
+    let mut found_native_dependencies = false;
+    for project in launch_process::launchable_projects(solution) {
+        let native_dependencies = native_dependencies::check_published_app(
+            &launch_process::project_publish_dir(project),
+            &project.assembly_name,
+            runtime_identifier,
+            run_image_libraries,
+        )
+        .map_err(DotnetBuildpackError::CheckNativeDependencies)?;
+
+        if !native_dependencies.native_libraries.is_empty() {
+            found_native_dependencies = true;
+            print::sub_bullet(format!(
+                "Found {} native {} in {}: {}",
+                native_dependencies.native_libraries.len(),
+                if native_dependencies.native_libraries.len() == 1 {
+                    "library"
+                } else {
+                    "libraries"
+                },
+                style::value(&project.assembly_name),
+                native_dependencies
+                    .native_libraries
+                    .iter()
+                    .map(style::value)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        if !native_dependencies.missing_libraries.is_empty() {
+            found_native_dependencies = true;
+            let missing_libraries = native_dependencies
+                .missing_libraries
+                .iter()
+                .map(|library| format!("- `{}` (needed by {})", library.name, library.needed_by))
+                .collect::<Vec<_>>()
+                .join("\n");
+            print::warning(formatdoc! {"
+                Missing native library dependencies
+
+                The published `{name}` app needs native libraries that aren't bundled with the
+                app, and aren't known to be available on the run image:
+
+                {missing_libraries}
+
+                The app may crash when it loads these libraries. Install the libraries on the
+                run image (e.g. with another buildpack), or use packages that bundle them. If
+                the libraries are available at launch, add them to `RUN_IMAGE_LIBRARIES` (or
+                `run_image_libraries` in `project.toml`) to silence this warning.
+            ", name = project.assembly_name});
+        }
+    }
+    if !found_native_dependencies {
+        print::sub_bullet("No native libraries found");
+    }
+    Ok(())
+}
+
+/// Logs the globalization mode of each launchable project's published app, and warns about apps
+/// that need ICU when it isn't known to be available on the run image.
+fn log_globalization_modes(
+    solution: &Solution,
+    publish_mode: Option<PublishMode>,
+    sdk_available_at_launch: bool,
+    globalization_options: &GlobalizationOptions,
+    target: &Target,
+    run_image_libraries: &[String],
+) {
+    print::bullet("Globalization");
+    let mut icu_projects = Vec::new();
+    let mut globalization_options_applied = false;
+    for project in launch_process::launchable_projects(solution) {
+        let runtime_config =
+            fs_err::read_to_string(launch_process::project_runtime_config_path(project))
+                .ok()
+                .and_then(|contents| contents.parse::<RuntimeConfig>().ok());
+        // The configured options are set in the launch environment of the .NET runtime (or SDK)
+        // layer, which self-contained apps don't use.
+        let applies_globalization_options = sdk_available_at_launch
+            || launch_process::project_publish_mode(project, publish_mode)
+                == PublishMode::FrameworkDependent;
+        globalization_options_applied |= applies_globalization_options;
+
+        let mode = globalization::project_globalization_mode(
+            project,
+            runtime_config.as_ref(),
+            applies_globalization_options.then_some(globalization_options),
+        );
+        print::sub_bullet(format!(
+            "{} uses {}",
+            style::value(&project.assembly_name),
+            style::value(mode.to_string())
+        ));
+        if mode == GlobalizationMode::Icu {
+            icu_projects.push(project.assembly_name.as_str());
+        }
+    }
+
+    if globalization_options_applied {
+        for (name, value) in globalization_options.environment_variables() {
+            print::sub_bullet(format!(
+                "Setting {} at launch",
+                style::value(format!("{name}={value}"))
+            ));
+        }
+    }
+
+    if !icu_projects.is_empty() && !globalization::run_image_has_icu(target, run_image_libraries) {
+        print::warning(formatdoc! {"
+            ICU isn't known to be available on the run image
+
+            These apps load the ICU libraries at launch (for culture-specific formatting and
+            string comparisons), and will fail to start if they're missing: {names}.
+
+            Install ICU on the run image, or enable globalization-invariant mode (with the
+            `InvariantGlobalization` project property, or `GLOBALIZATION_INVARIANT=true`), or
+            bundle ICU with the app (with the `Microsoft.ICU.ICU4C.Runtime` package and
+            `GLOBALIZATION_APP_LOCAL_ICU`). If ICU is available at launch, add `libicuuc.so` with
+            its version to `RUN_IMAGE_LIBRARIES` to silence this warning.
+        ", names = icu_projects.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ")});
+    }
+}
+
+/// Logs the effective W^X setting during the build or at launch, and whether it was configured or
+/// disabled due to emulation.
+fn log_write_xor_execute(configured: Option<bool>, effective: Option<bool>, phase: &str) {
+    match effective {
+        Some(enabled) => print::sub_bullet(format!(
+            "Setting {} {phase}{}",
+            style::value(format!(
+                "DOTNET_EnableWriteXorExecute={}",
+                if enabled { "1" } else { "0" }
+            )),
+            if configured.is_none() {
+                " (emulation detected)"
+            } else {
+                ""
+            }
+        )),
+        None => print::sub_bullet(format!(
+            "Using the .NET runtime's default W^X setting ({}) {phase}",
+            style::value("enabled")
+        )),
+    }
+}
+
+/// Adds an exec.d program that disables W^X at launch when the app runs under emulation, unless W^X
+/// is configured for launch (in which case it's set in the launch environment of the .NET layers).
+fn configure_write_xor_execute_at_launch(
+    context: &BuildContext<DotnetBuildpack>,
+    configured: Option<bool>,
+) -> Result<(), libcnb::Error<DotnetBuildpackError>> {
+    let write_xor_execute_layer = context.uncached_layer(
+        layer_name!("write-xor-execute"),
+        UncachedLayerDefinition {
+            build: false,
+            launch: configured.is_none(),
+        },
+    )?;
+    if configured.is_none() {
+        write_xor_execute_layer.write_exec_d_programs([(
+            "configure_write_xor_execute",
+            additional_buildpack_binary_path!("configure_write_xor_execute"),
+        )])?;
+    }
+    Ok(())
+}
+
+/// Adds an exec.d program that sets GC defaults from the dyno's memory limit and CPU quota at
+/// launch, except for the GC settings that a launchable project's published app configures itself.
+fn configure_gc_defaults(
+    context: &BuildContext<DotnetBuildpack>,
+    solution: &Solution,
+) -> Result<(), libcnb::Error<DotnetBuildpackError>> {
+    let gc_layer = context.uncached_layer(
+        layer_name!("gc"),
+        UncachedLayerDefinition {
+            build: false,
+            launch: true,
+        },
+    )?;
+    gc_layer.write_exec_d_programs([(
+        "configure_gc",
+        additional_buildpack_binary_path!("configure_gc"),
+    )])?;
+    print::sub_bullet("Setting GC defaults from the dyno's memory limit and CPU quota at launch");
+
+    // The exec.d program skips GC settings that are already set, so the settings configured by a
+    // project's runtimeconfig are set for its process, where they'd otherwise be overridden.
+    let mut layer_env = LayerEnv::new();
+    for project in launch_process::launchable_projects(solution) {
+        let gc_settings =
+            fs_err::read_to_string(launch_process::project_runtime_config_path(project))
+                .ok()
+                .and_then(|contents| contents.parse::<RuntimeConfig>().ok())
+                .map(|runtime_config| runtime_config.gc_settings())
+                .unwrap_or_default();
+        if gc_settings.is_empty() {
+            continue;
+        }
+
+        let process_type = launch_process::launch_process_type(solution, project);
+        print::sub_bullet(format!(
+            "Using the GC settings configured by {} for the {} process",
+            style::value(&project.assembly_name),
+            style::value(process_type.to_string())
+        ));
+        for (name, value) in gc_settings {
+            layer_env = layer_env.chainable_insert(
+                Scope::Process(process_type.to_string()),
+                ModificationBehavior::Default,
+                name,
+                value,
+            );
+        }
+    }
+    gc_layer.write_env(layer_env)?;
+    Ok(())
+}
+
+/// Creates an Entity Framework Core migrations bundle for apps that reference the
+/// `Microsoft.EntityFrameworkCore.Design` package (when enabled), and returns the `release` process
+/// that runs it.
+/// The bundle is created with the `dotnet ef` tool, which must be restored from the tool manifest.
+fn create_ef_migrations_bundle(
+    context: &BuildContext<DotnetBuildpack>,
+    solution: &Solution,
+    ef_migrations: &EfMigrationsOptions,
+    build_configuration: Option<&str>,
+    runtime_identifier: RuntimeIdentifier,
+    command_env: &Env,
+) -> Result<Option<Process>, libcnb::Error<DotnetBuildpackError>> {
+    if !ef_migrations.enabled {
+        if let Ok(Some(projects)) =
+            ef_migrations::migrations_projects(solution, ef_migrations.project.as_deref())
+            && ef_migrations::has_dotnet_ef_tool(&context.app_dir)
+        {
+            print::bullet("Entity Framework Core migrations");
+            print::sub_bullet(format!(
+                "Skipping the migrations bundle for {} (set {} to run migrations in the release phase)",
+                style::value(&projects.project.assembly_name),
+                style::value("EF_MIGRATIONS_ENABLED=true")
+            ));
+        }
+        return Ok(None);
+    }
+
+    let Some(projects) =
+        ef_migrations::migrations_projects(solution, ef_migrations.project.as_deref())
+            .map_err(DotnetBuildpackError::EfMigrationsProjects)?
+    else {
+        return Ok(None);
+    };
+
+    print::bullet("Entity Framework Core migrations");
+    if !ef_migrations::has_dotnet_ef_tool(&context.app_dir) {
+        print::warning(formatdoc! {"
+            The `dotnet ef` tool isn't in your tool manifest
+
+            The `{startup_project}` project references the `{package}`
+            package, but the `{tool}` tool needed to create a migrations bundle isn't listed in
+            `.config/dotnet-tools.json`, so no `release` process is registered.
+
+            Add the tool to your tool manifest (with `dotnet tool install {tool}`) to run your
+            migrations in the release phase.
+        ", startup_project = projects.startup_project.assembly_name, package = ef_migrations::EF_CORE_DESIGN_PACKAGE, tool = ef_migrations::DOTNET_EF_TOOL});
+        return Ok(None);
+    }
+
+    print::sub_bullet(format!(
+        "Creating a migrations bundle for {} (startup project: {})",
+        style::value(&projects.project.assembly_name),
+        style::value(&projects.startup_project.assembly_name)
+    ));
+    let ef_migrations_layer = context.uncached_layer(
+        layer_name!("ef-migrations"),
+        UncachedLayerDefinition {
+            build: false,
+            launch: true,
+        },
+    )?;
+    let bundle_path = ef_migrations_layer.path().join("efbundle");
+
+    let mut bundle_command = Command::from(DotnetEfMigrationsBundleCommand {
+        project_path: projects.project.path.clone(),
+        startup_project_path: projects.startup_project.path.clone(),
+        output_path: bundle_path.clone(),
+        runtime_identifier,
+        configuration: build_configuration.map(str::to_string),
+        context: ef_migrations.context.clone(),
+    });
+    bundle_command
+        .current_dir(&context.app_dir)
+        .envs(command_env);
+    print::sub_stream_with(
+        format!("Running {}", style::command(bundle_command.name())),
+        |stdout, stderr| bundle_command.stream_output(stdout, stderr),
+    )
+    .map_err(DotnetBuildpackError::EfMigrationsBundleCommand)?;
+
+    if let Some(connection_env_var) = &ef_migrations.connection_env_var {
+        print::sub_bullet(format!(
+            "Using the connection string from {} at release time",
+            style::value(format!("${connection_env_var}"))
+        ));
+    }
+    Ok(Some(ef_migrations::release_process(
+        &bundle_path,
+        ef_migrations.connection_env_var.as_deref(),
+    )))
+}
+
+/// Starts each launchable project's published app to check that it launches: web apps must accept
+/// connections on a dummy `PORT`, and other apps must not fail when run with `--help`.
+fn run_launch_smoke_checks(
+    solution: &Solution,
+    publish_mode: Option<PublishMode>,
+    launch_profiles: &HashMap<PathBuf, ProjectLaunchProfile>,
+    command_env: &Env,
+) -> Result<(), DotnetBuildpackError> {
+    print::bullet("Launch smoke check");
+    for project in launch_process::launchable_projects(solution) {
+        // A missing entrypoint means the app can't start at launch either.
+        let mut command = launch_process::project_launch_command(
+            project,
+            launch_process::project_publish_mode(project, publish_mode),
+            launch_profiles.get(&project.path),
+        )
+        .map_err(|error| {
+            DotnetBuildpackError::LaunchSmokeCheck(
+                project.assembly_name.clone(),
+                LaunchSmokeCheckError::Io(error),
+            )
+        })?;
+        command.envs(command_env);
+
+        let name = style::value(&project.assembly_name);
+        let (check, timeout) = if launch_process::is_web_app(project) {
+            print::sub_bullet(format!(
+                "Starting {name} with a dummy {}",
+                style::value("PORT")
+            ));
+            (
+                LaunchSmokeCheck::WebApp,
+                launch_smoke_check::WEB_APP_TIMEOUT,
+            )
+        } else {
+            print::sub_bullet(format!("Running {name} with {}", style::value("--help")));
+            (
+                LaunchSmokeCheck::Help,
+                launch_smoke_check::EXECUTABLE_TIMEOUT,
+            )
+        };
+        match launch_smoke_check::run(command, check, timeout).map_err(|error| {
+            DotnetBuildpackError::LaunchSmokeCheck(project.assembly_name.clone(), error)
+        })? {
+            LaunchSmokeCheckOutcome::Listening(port, elapsed) => {
+                print::sub_bullet(format!(
+                    "Accepted connections on port {port} after {:.1}s",
+                    elapsed.as_secs_f64()
+                ));
+            }
+            LaunchSmokeCheckOutcome::Exited => print::sub_bullet("Exited successfully"),
+            LaunchSmokeCheckOutcome::StillRunning(elapsed) => {
+                print::sub_bullet(format!(
+                    "Still running after {}s (stopped)",
+                    elapsed.as_secs()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the detected processes to register for an app with a `Procfile`, according to the
+/// configured Procfile mode.
+fn register_processes_with_procfile(
+    processes: Vec<Process>,
+    procfile: &Procfile,
+    procfile_mode: ProcfileMode,
+) -> Vec<Process> {
+    if processes.is_empty() {
+        return processes;
+    }
+    match procfile_mode {
+        ProcfileMode::Skip => {
+            print::sub_bullet(
+                "Skipping process type registration (add process types to your Procfile as needed)",
+            );
+            vec![]
+        }
+        ProcfileMode::Override => {
+            print::sub_bullet("Registering all detected process types as launch processes");
+            processes
+        }
+        ProcfileMode::Merge => {
+            let (defined, undefined): (Vec<_>, Vec<_>) = processes
+                .into_iter()
+                .partition(|process| procfile.contains(process.r#type.as_str()));
+            for process in &defined {
+                print::sub_bullet(format!(
+                    "Skipping {} (defined in the Procfile)",
+                    style::value(process.r#type.to_string())
+                ));
+            }
+            if !undefined.is_empty() {
+                print::sub_bullet(
+                    "Registering detected process types that aren't defined in the Procfile",
+                );
+            }
+            undefined
+        }
+    }
+}
+
+/// Warns about Procfile commands that run files that don't exist in the published app
+fn log_missing_procfile_entrypoints(app_dir: &Path, procfile: &Procfile) {
+    for (process_type, command) in &procfile.processes {
+        for path in procfile::command_entrypoint_paths(command) {
+            if !app_dir.join(&path).exists() {
+                print::warning(formatdoc! {"
+                    The {process_type} process in your Procfile runs {path}, which doesn't exist
+                    in the published app.
+
+                    Check that the path matches the output of `dotnet publish` (e.g.
+                    `<project directory>/bin/publish/<AssemblyName>`).
+                ", process_type = style::value(process_type), path = style::value(path.to_string_lossy())});
+            }
+        }
+    }
+}
+
+fn log_binary_log_path(binary_log_path: &Path) {
+    if binary_log_path.is_file() {
+        print::sub_bullet(format!(
+            "MSBuild binary log written to {}",
+            style::value(binary_log_path.to_string_lossy())
+        ));
+    }
+}
+
+// The maximum number of trim analysis warnings listed in the build log summary.
+const MAX_LOGGED_TRIM_ANALYSIS_WARNINGS: usize = 20;
+
+fn log_trim_analysis_warnings(warnings: &[Diagnostic]) {
+    print::bullet("Trim analysis");
+    if warnings.is_empty() {
+        print::sub_bullet("No trim or Native AOT analysis warnings found");
+        return;
+    }
+
+    print::sub_bullet(format!(
+        "Found {} trim or Native AOT analysis warning(s): {}",
+        warnings.len(),
+        warning_code_counts(warnings)
+    ));
+    for warning in warnings.iter().take(MAX_LOGGED_TRIM_ANALYSIS_WARNINGS) {
+        print::sub_bullet(warning.to_string());
+    }
+    if warnings.len() > MAX_LOGGED_TRIM_ANALYSIS_WARNINGS {
+        print::sub_bullet(format!(
+            "...and {} more (see the publish output above)",
+            warnings.len() - MAX_LOGGED_TRIM_ANALYSIS_WARNINGS
+        ));
+    }
+}
+
+/// Returns the distinct diagnostic codes and their number of occurrences, e.g. `IL2026` (2)
+fn warning_code_counts(diagnostics: &[Diagnostic]) -> String {
+    let mut counts = std::collections::BTreeMap::<&str, usize>::new();
+    for diagnostic in diagnostics {
+        *counts.entry(diagnostic.code.as_str()).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(code, count)| format!("{} ({count})", style::value(code)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn load_project_toml_config(app_dir: &Path) -> Result<Option<DotnetConfig>, DotnetBuildpackError> {
     detect::project_toml_file(app_dir).map_or_else(
         || Ok(None),
@@ -359,6 +1398,21 @@ fn detect_sdk_version_requirement(
 fn get_solution_sdk_version_requirement(
     solution: &Solution,
 ) -> Result<VersionReq, DotnetBuildpackError> {
+    if !solution.referenced_projects.is_empty() {
+        print::sub_bullet(format!(
+            "Including {} referenced project(s) that aren't part of the solution",
+            solution.referenced_projects.len()
+        ));
+    }
+    // Referenced projects outside the solution may be libraries targeting frameworks that aren't
+    // supported for SDK version inference (e.g. `netstandard2.0`), so those are skipped.
+    let referenced_project_tfms = solution.referenced_projects.iter().filter_map(|project| {
+        project
+            .target_framework
+            .parse::<TargetFrameworkMoniker>()
+            .ok()
+    });
+
     solution
         .projects
         .iter()
@@ -369,13 +1423,15 @@ fn get_solution_sdk_version_requirement(
                 .map_err(DotnetBuildpackError::ParseTargetFrameworkMoniker)
         })
         .collect::<Result<Vec<_>, _>>()?
-        .iter()
+        .into_iter()
+        .chain(referenced_project_tfms)
         // Select the most recent TFM sorted lexicographically, which is sufficient for now as the
         // only expected TFMs follow a consistent format: `netX.0` (e.g. `net6.0`, `net8.0` etc).
         .max_by_key(|tfm| tfm.version_part.clone())
         .ok_or_else(|| DotnetBuildpackError::NoSolutionProjects(solution.path.clone()))
         .map(|tfm| {
-            VersionReq::try_from(tfm).map_err(DotnetBuildpackError::ParseSolutionVersionRequirement)
+            VersionReq::try_from(&tfm)
+                .map_err(DotnetBuildpackError::ParseSolutionVersionRequirement)
         })?
 }
 
@@ -401,6 +1457,7 @@ fn detect_global_json_sdk_configuration(
 enum DotnetBuildpackError {
     BuildpackDetection(io::Error),
     ReadProjectTomlFile(io::Error),
+    ReadProcfile(io::Error),
     ParseProjectToml(toml::de::Error),
     NoSolutionProjects(PathBuf),
     ConfiguredSolutionFileNotFound(PathBuf),
@@ -415,9 +1472,22 @@ enum DotnetBuildpackError {
     ResolveSdkVersion(VersionReq),
     SdkLayer(SdkLayerError),
     RestoreDotnetToolsCommand(fun_run::CmdError),
+    ConfigureWorkloads(io::Error),
+    ConfigureKestrel(io::Error),
+    RestoreWorkloadsCommand(fun_run::CmdError),
     ParseBuildpackConfiguration(DotnetBuildpackConfigurationError),
+    MissingNativeAotPrerequisites(Vec<NativeAotPrerequisite>),
     PublishCommand(fun_run::CmdError),
+    PublishProjects(PublishProjectsError),
+    TrimAnalysisWarnings(Vec<Diagnostic>),
+    EfMigrationsProjects(MigrationsProjectsError),
+    EfMigrationsBundleCommand(fun_run::CmdError),
     CopyRuntimeFiles(io::Error),
+    ReadRuntimeConfig(io::Error),
+    ParseRuntimeConfig(PathBuf, serde_json::Error),
+    LaunchProfile(LaunchProfileError),
+    LaunchSmokeCheck(String, LaunchSmokeCheckError),
+    CheckNativeDependencies(io::Error),
 }
 
 impl From<DotnetBuildpackError> for libcnb::Error<DotnetBuildpackError> {
@@ -0,0 +1,121 @@
+use libcnb::Env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+// The default library directories searched by the linker, in addition to `LIBRARY_PATH`.
+const DEFAULT_LIBRARY_DIRS: &[&str] = &[
+    "/usr/lib/x86_64-linux-gnu",
+    "/usr/lib/aarch64-linux-gnu",
+    "/usr/lib",
+    "/usr/lib64",
+];
+
+/// A native toolchain prerequisite for publishing Native AOT apps on Linux.
+/// See <https://learn.microsoft.com/en-us/dotnet/core/deploying/native-aot/#prerequisites>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum NativeAotPrerequisite {
+    /// The compiler and linker used by the Native AOT compiler (`ILCompiler`)
+    Clang,
+    /// The zlib development library, linked into every Native AOT app
+    Zlib,
+}
+
+impl NativeAotPrerequisite {
+    /// Returns the Ubuntu package that provides the prerequisite
+    pub(crate) fn package_name(self) -> &'static str {
+        match self {
+            NativeAotPrerequisite::Clang => "clang",
+            NativeAotPrerequisite::Zlib => "zlib1g-dev",
+        }
+    }
+}
+
+impl fmt::Display for NativeAotPrerequisite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NativeAotPrerequisite::Clang => write!(f, "clang"),
+            NativeAotPrerequisite::Zlib => write!(f, "zlib"),
+        }
+    }
+}
+
+/// Returns the Native AOT prerequisites that can't be found using the `PATH` and `LIBRARY_PATH`
+/// of the given environment.
+pub(crate) fn missing_prerequisites(env: &Env) -> Vec<NativeAotPrerequisite> {
+    let executable_dirs = search_paths(env, "PATH").collect::<Vec<_>>();
+    let library_dirs = search_paths(env, "LIBRARY_PATH")
+        .chain(DEFAULT_LIBRARY_DIRS.iter().map(PathBuf::from))
+        .collect::<Vec<_>>();
+
+    let mut missing = Vec::new();
+    if find_file(&executable_dirs, &["clang"]).is_none() {
+        missing.push(NativeAotPrerequisite::Clang);
+    }
+    if find_file(&library_dirs, &["libz.so", "libz.a"]).is_none() {
+        missing.push(NativeAotPrerequisite::Zlib);
+    }
+    missing
+}
+
+fn search_paths(env: &Env, key: &str) -> impl Iterator<Item = PathBuf> {
+    env.get(key)
+        .map(|value| std::env::split_paths(&value).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| !path.as_os_str().is_empty())
+}
+
+/// Returns the first file with one of the given names in the given directories
+fn find_file(dirs: &[PathBuf], file_names: &[&str]) -> Option<PathBuf> {
+    dirs.iter()
+        .flat_map(|dir| file_names.iter().map(|file_name| dir.join(file_name)))
+        .find(|path| Path::is_file(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_find_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let bin_dir = temp_dir.path().join("bin");
+        let lib_dir = temp_dir.path().join("lib");
+        fs::create_dir_all(&bin_dir).unwrap();
+        fs::create_dir_all(&lib_dir).unwrap();
+        fs::write(lib_dir.join("libz.a"), "").unwrap();
+
+        let dirs = [bin_dir, lib_dir.clone()];
+        assert_eq!(
+            find_file(&dirs, &["libz.so", "libz.a"]),
+            Some(lib_dir.join("libz.a"))
+        );
+        assert_eq!(find_file(&dirs, &["clang"]), None);
+    }
+
+    #[test]
+    fn test_missing_prerequisites_with_clang_in_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("clang"), "").unwrap();
+
+        let mut env = Env::new();
+        env.insert("PATH", temp_dir.path());
+
+        assert!(!missing_prerequisites(&env).contains(&NativeAotPrerequisite::Clang));
+    }
+
+    #[test]
+    fn test_missing_prerequisites_with_zlib_in_library_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("libz.so"), "").unwrap();
+
+        let mut env = Env::new();
+        env.insert("LIBRARY_PATH", temp_dir.path());
+
+        assert_eq!(
+            missing_prerequisites(&env),
+            vec![NativeAotPrerequisite::Clang]
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use crate::dotnet::deps_json::DepsJson;
+use crate::dotnet::runtime_identifier::RuntimeIdentifier;
+use elf::ElfBytes;
+use elf::endian::AnyEndian;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// Shared libraries available on the Heroku run images (in addition to those configured with
+/// `RUN_IMAGE_LIBRARIES`). Only libraries with stable sonames across the supported run images are
+/// listed.
+const DEFAULT_RUN_IMAGE_LIBRARIES: &[&str] = &[
+    "ld-linux-aarch64.so.1",
+    "ld-linux-x86-64.so.2",
+    "libbz2.so.1.0",
+    "libc.so.6",
+    "libcom_err.so.2",
+    "libcrypto.so.3",
+    "libcurl.so.4",
+    "libdl.so.2",
+    "libexpat.so.1",
+    "libffi.so.8",
+    "libfontconfig.so.1",
+    "libfreetype.so.6",
+    "libgcc_s.so.1",
+    "libgmp.so.10",
+    "libgssapi_krb5.so.2",
+    "libjpeg.so.8",
+    "libk5crypto.so.3",
+    "libkrb5.so.3",
+    "liblber.so.2",
+    "libldap.so.2",
+    "liblzma.so.5",
+    "libm.so.6",
+    "libpng16.so.16",
+    "libpq.so.5",
+    "libpthread.so.0",
+    "libresolv.so.2",
+    "librt.so.1",
+    "libsasl2.so.2",
+    "libsqlite3.so.0",
+    "libssl.so.3",
+    "libstdc++.so.6",
+    "libutil.so.1",
+    "libuuid.so.1",
+    "libyaml-0.so.2",
+    "libz.so.1",
+    "libzstd.so.1",
+];
+
+/// Libraries that are only used for optional features, and can be missing at launch. The .NET
+/// runtime's tracing provider (`libcoreclrtraceptprovider.so`) is only loaded when `liblttng-ust`
+/// is installed.
+const OPTIONAL_LIBRARIES: &[&str] = &["liblttng-ust.so.0", "liblttng-ust.so.1"];
+
+/// Packages that load native libraries at runtime (with `dlopen`), which therefore aren't
+/// listed as dependencies of any published file.
+const PACKAGE_LIBRARIES: &[(&str, &str)] = &[
+    ("HarfBuzzSharp", "libHarfBuzzSharp.so"),
+    (
+        "Microsoft.AspNetCore.Authentication.Negotiate",
+        "libgssapi_krb5.so.2",
+    ),
+    ("SkiaSharp", "libSkiaSharp.so"),
+    ("System.DirectoryServices.Protocols", "libldap.so.2"),
+    ("System.Drawing.Common", "libgdiplus.so"),
+];
+
+/// A native library that a published app needs, but that isn't bundled with the app or available
+/// on the run image.
+#[derive(Debug, PartialEq)]
+pub(crate) struct MissingLibrary {
+    pub(crate) name: String,
+    pub(crate) needed_by: NeededBy,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum NeededBy {
+    /// A published native library (a `DT_NEEDED` entry), and the package that contributes it
+    NativeLibrary {
+        file_name: String,
+        package: Option<String>,
+    },
+    /// A package that loads the library at runtime
+    Package(String),
+}
+
+impl fmt::Display for NeededBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NeededBy::NativeLibrary {
+                file_name,
+                package: Some(package),
+            } => write!(f, "`{file_name}` from the `{package}` package"),
+            NeededBy::NativeLibrary {
+                file_name,
+                package: None,
+            } => write!(f, "`{file_name}`"),
+            NeededBy::Package(package) => write!(f, "the `{package}` package"),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct NativeDependencies {
+    /// The file names of the native libraries published with the app
+    pub(crate) native_libraries: BTreeSet<String>,
+    pub(crate) missing_libraries: Vec<MissingLibrary>,
+}
+
+/// Finds the native libraries published with an app (in the publish directory, or its
+/// `runtimes/<rid>/native` directory), and the libraries they (or the app's packages) need that
+/// aren't bundled with the app or available on the run image.
+pub(crate) fn check_published_app(
+    publish_dir: &Path,
+    assembly_name: &str,
+    runtime_identifier: &RuntimeIdentifier,
+    run_image_libraries: &[String],
+) -> io::Result<NativeDependencies> {
+    let deps_json = fs_err::read_to_string(publish_dir.join(format!("{assembly_name}.deps.json")))
+        .ok()
+        .and_then(|contents| contents.parse::<DepsJson>().ok())
+        .unwrap_or_default();
+
+    let mut native_libraries = Vec::new();
+    for dir in [
+        publish_dir.to_path_buf(),
+        publish_dir
+            .join("runtimes")
+            .join(runtime_identifier.to_string())
+            .join("native"),
+    ] {
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs_err::read_dir(&dir)? {
+            let path = entry?.path();
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !is_shared_library_file_name(&file_name) || !path.is_file() {
+                continue;
+            }
+            if let Some(needed) = needed_libraries(&fs_err::read(&path)?) {
+                native_libraries.push((file_name, needed));
+            }
+        }
+    }
+
+    let available = |library: &str| {
+        native_libraries
+            .iter()
+            .map(|(file_name, _)| file_name.as_str())
+            .chain(run_image_libraries.iter().map(String::as_str))
+            .chain(DEFAULT_RUN_IMAGE_LIBRARIES.iter().copied())
+            .chain(OPTIONAL_LIBRARIES.iter().copied())
+            .any(|candidate| provides(candidate, library))
+    };
+
+    let mut missing_libraries = Vec::new();
+    for (file_name, needed) in &native_libraries {
+        for library in needed.iter().filter(|library| !available(library)) {
+            missing_libraries.push(MissingLibrary {
+                name: library.clone(),
+                needed_by: NeededBy::NativeLibrary {
+                    file_name: file_name.clone(),
+                    package: deps_json
+                        .native_library_package(file_name)
+                        .map(str::to_string),
+                },
+            });
+        }
+    }
+    for package in deps_json.package_names() {
+        for (_, library) in PACKAGE_LIBRARIES
+            .iter()
+            .filter(|(name, library)| *name == package && !available(library))
+        {
+            missing_libraries.push(MissingLibrary {
+                name: (*library).to_string(),
+                needed_by: NeededBy::Package(package.to_string()),
+            });
+        }
+    }
+
+    Ok(NativeDependencies {
+        native_libraries: native_libraries
+            .into_iter()
+            .map(|(file_name, _)| file_name)
+            .collect(),
+        missing_libraries,
+    })
+}
+
+/// Returns the `DT_NEEDED` entries of an ELF shared library, or `None` if the file isn't one
+fn needed_libraries(data: &[u8]) -> Option<Vec<String>> {
+    let file = ElfBytes::<AnyEndian>::minimal_parse(data).ok()?;
+    let common = file.find_common_data().ok()?;
+    let (Some(dynamic), Some(strings)) = (common.dynamic, common.dynsyms_strs) else {
+        return Some(vec![]);
+    };
+    Some(
+        dynamic
+            .iter()
+            .filter(|entry| entry.d_tag == elf::abi::DT_NEEDED)
+            .filter_map(|entry| usize::try_from(entry.d_val()).ok())
+            .filter_map(|offset| strings.get(offset).ok())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Returns whether a file name is a shared library name, with or without a version suffix (e.g.
+/// `libfoo.so` or `libfoo.so.1`)
+fn is_shared_library_file_name(file_name: &str) -> bool {
+    file_name.split('.').skip(1).any(|part| part == "so")
+}
+
+/// Returns whether a library file (or soname) provides the given library. Libraries loaded by
+/// name without a version (e.g. `libgdiplus.so`) are provided by any version.
+fn provides(candidate: &str, library: &str) -> bool {
+    candidate == library
+        || candidate
+            .strip_prefix(library)
+            .is_some_and(|suffix| suffix.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_deps_json(publish_dir: &Path, packages: &[(&str, &[&str])]) {
+        let targets = packages
+            .iter()
+            .map(|(package, native)| {
+                (
+                    format!("{package}/1.0.0"),
+                    serde_json::json!({
+                        "native": native
+                            .iter()
+                            .map(|path| (path.to_string(), serde_json::json!({})))
+                            .collect::<serde_json::Map<_, _>>()
+                    }),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        let libraries = packages
+            .iter()
+            .map(|(package, _)| {
+                (
+                    format!("{package}/1.0.0"),
+                    serde_json::json!({ "type": "package" }),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        fs::write(
+            publish_dir.join("foo.deps.json"),
+            serde_json::json!({
+                "targets": { ".NETCoreApp,Version=v8.0/linux-x64": targets },
+                "libraries": libraries
+            })
+            .to_string(),
+        )
+        .unwrap();
+    }
+
+    /// Copies the test executable, a dynamically linked ELF file that needs `libc.so.6`
+    fn copy_elf_file(destination: &Path) {
+        fs::create_dir_all(destination.parent().unwrap()).unwrap();
+        fs::copy(std::env::current_exe().unwrap(), destination).unwrap();
+    }
+
+    #[test]
+    fn test_needed_libraries() {
+        let needed =
+            needed_libraries(&fs::read(std::env::current_exe().unwrap()).unwrap()).unwrap();
+        assert!(needed.contains(&"libc.so.6".to_string()));
+        assert_eq!(needed_libraries(b"INPUT(libfoo.so.1)"), None);
+    }
+
+    #[test]
+    fn test_provides() {
+        assert!(provides("libgdiplus.so.0", "libgdiplus.so"));
+        assert!(provides("libfontconfig.so.1", "libfontconfig.so.1"));
+        assert!(!provides("libfontconfig.so.1", "libfontconfig.so.2"));
+        assert!(!provides("libssl.so.3", "libssl.so.30"));
+        assert!(!provides("libgdiplus-extra.so", "libgdiplus.so"));
+    }
+
+    #[test]
+    fn test_check_published_app() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let publish_dir = temp_dir.path();
+        copy_elf_file(&publish_dir.join("libSkiaSharp.so"));
+        copy_elf_file(
+            &publish_dir
+                .join("runtimes")
+                .join("linux-x64")
+                .join("native")
+                .join("libe_sqlite3.so"),
+        );
+        // Native libraries for other runtime identifiers, and other files, aren't checked.
+        copy_elf_file(
+            &publish_dir
+                .join("runtimes")
+                .join("linux-arm64")
+                .join("native")
+                .join("libfoo.so"),
+        );
+        fs::write(publish_dir.join("libbar.so"), "INPUT(libbar.so.1)").unwrap();
+        fs::write(publish_dir.join("foo.dll"), "").unwrap();
+        write_deps_json(
+            publish_dir,
+            &[
+                (
+                    "SkiaSharp.NativeAssets.Linux",
+                    &["runtimes/linux-x64/native/libSkiaSharp.so"],
+                ),
+                ("SkiaSharp", &[]),
+                ("System.Drawing.Common", &[]),
+                ("System.DirectoryServices.Protocols", &[]),
+            ],
+        );
+
+        let result =
+            check_published_app(publish_dir, "foo", &RuntimeIdentifier::LinuxX64, &[]).unwrap();
+        assert_eq!(
+            result.native_libraries,
+            BTreeSet::from(["libSkiaSharp.so".to_string(), "libe_sqlite3.so".to_string()])
+        );
+        // The test executable's dependencies are all available on the run image, and
+        // `SkiaSharp` loads the bundled `libSkiaSharp.so`.
+        assert_eq!(
+            result.missing_libraries,
+            vec![MissingLibrary {
+                name: "libgdiplus.so".to_string(),
+                needed_by: NeededBy::Package("System.Drawing.Common".to_string()),
+            }]
+        );
+
+        let result = check_published_app(
+            publish_dir,
+            "foo",
+            &RuntimeIdentifier::LinuxX64,
+            &["libgdiplus.so.0".to_string()],
+        )
+        .unwrap();
+        assert!(result.missing_libraries.is_empty());
+    }
+
+    #[test]
+    fn test_check_published_app_missing_package_native_library() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let publish_dir = temp_dir.path();
+        write_deps_json(publish_dir, &[("SkiaSharp", &[])]);
+
+        assert_eq!(
+            check_published_app(publish_dir, "foo", &RuntimeIdentifier::LinuxX64, &[])
+                .unwrap()
+                .missing_libraries,
+            vec![MissingLibrary {
+                name: "libSkiaSharp.so".to_string(),
+                needed_by: NeededBy::Package("SkiaSharp".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_published_app_without_publish_output() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            check_published_app(
+                &temp_dir.path().join("bin").join("publish"),
+                "foo",
+                &RuntimeIdentifier::LinuxX64,
+                &[]
+            )
+            .unwrap(),
+            NativeDependencies::default()
+        );
+    }
+
+    #[test]
+    fn test_needed_by_display() {
+        assert_eq!(
+            NeededBy::NativeLibrary {
+                file_name: "libSkiaSharp.so".to_string(),
+                package: Some("SkiaSharp.NativeAssets.Linux".to_string()),
+            }
+            .to_string(),
+            "`libSkiaSharp.so` from the `SkiaSharp.NativeAssets.Linux` package"
+        );
+        assert_eq!(
+            NeededBy::NativeLibrary {
+                file_name: "libcoreclrtraceptprovider.so".to_string(),
+                package: None,
+            }
+            .to_string(),
+            "`libcoreclrtraceptprovider.so`"
+        );
+        assert_eq!(
+            NeededBy::Package("System.Drawing.Common".to_string()).to_string(),
+            "the `System.Drawing.Common` package"
+        );
+    }
+}
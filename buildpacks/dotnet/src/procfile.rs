@@ -0,0 +1,185 @@
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+/// The process types declared in a `Procfile`, in the order they're declared.
+/// See <https://devcenter.heroku.com/articles/procfile>
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Procfile {
+    pub(crate) processes: Vec<(String, String)>,
+}
+
+impl Procfile {
+    pub(crate) fn contains(&self, process_type: &str) -> bool {
+        self.processes.iter().any(|(name, _)| name == process_type)
+    }
+}
+
+impl FromStr for Procfile {
+    type Err = std::convert::Infallible;
+
+    /// Parses a `Procfile`. Like the Procfile buildpack, lines that aren't `<type>: <command>`
+    /// declarations (e.g. comments) are ignored, and later declarations of a process type replace
+    /// earlier ones.
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let mut procfile = Procfile::default();
+        for line in contents.lines() {
+            let Some((name, command)) = line.split_once(':') else {
+                continue;
+            };
+            let (name, command) = (name.trim(), command.trim());
+            if name.is_empty()
+                || command.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                continue;
+            }
+            procfile.processes.retain(|(existing, _)| existing != name);
+            procfile
+                .processes
+                .push((name.to_string(), command.to_string()));
+        }
+        Ok(procfile)
+    }
+}
+
+/// Returns the files a Procfile command executes, relative to the app directory: executables
+/// referenced by path (e.g. `./foo` or `bin/publish/foo`), and assemblies run with `dotnet`.
+/// `cd` commands are followed, and arguments that reference environment variables are skipped,
+/// since they can only be resolved at launch.
+pub(crate) fn command_entrypoint_paths(command: &str) -> Vec<PathBuf> {
+    let mut working_dir = PathBuf::new();
+    let mut paths = Vec::new();
+
+    for segment in command.split([';', '&', '|']) {
+        let Ok(words) = shell_words::split(segment) else {
+            continue;
+        };
+        let mut words = words
+            .iter()
+            .map(String::as_str)
+            .skip_while(|word| *word == "exec");
+
+        let entrypoint = match (words.next(), words.next()) {
+            (Some("cd"), Some(dir)) => {
+                match app_relative_path(&working_dir, dir) {
+                    Some(dir) => working_dir = dir,
+                    None => return paths,
+                }
+                continue;
+            }
+            (Some("dotnet"), Some(assembly))
+                if Path::new(assembly)
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("dll")) =>
+            {
+                assembly
+            }
+            (Some(executable), _) if executable.contains('/') => executable,
+            _ => continue,
+        };
+        paths.extend(app_relative_path(&working_dir, entrypoint));
+    }
+    paths
+}
+
+/// Resolves a path used in a Procfile command relative to the app directory (`/workspace` at
+/// launch). Returns `None` for paths outside the app directory or that reference variables.
+fn app_relative_path(working_dir: &Path, path: &str) -> Option<PathBuf> {
+    if path.contains('$') || path.starts_with('~') {
+        return None;
+    }
+    let path = match Path::new(path).strip_prefix("/workspace") {
+        Ok(path) => path.to_path_buf(),
+        Err(_) if Path::new(path).is_absolute() => return None,
+        Err(_) => working_dir.join(path),
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_procfile() {
+        let procfile = indoc::indoc! {"
+            # Run database migrations on release
+            release: ./efbundle --connection $DATABASE_URL
+            web: cd bin/publish; ./foo
+            worker:
+            invalid line
+            web: cd bin/publish && ./foo --verbose
+        "}
+        .parse::<Procfile>()
+        .unwrap();
+
+        assert_eq!(
+            procfile.processes,
+            vec![
+                (
+                    "release".to_string(),
+                    "./efbundle --connection $DATABASE_URL".to_string()
+                ),
+                (
+                    "web".to_string(),
+                    "cd bin/publish && ./foo --verbose".to_string()
+                ),
+            ]
+        );
+        assert!(procfile.contains("web"));
+        assert!(!procfile.contains("worker"));
+    }
+
+    #[test]
+    fn test_command_entrypoint_paths() {
+        for (command, expected) in [
+            (
+                "cd bin/publish; ./foo --urls http://*:$PORT",
+                vec!["bin/publish/foo"],
+            ),
+            (
+                "cd 'My App/bin/publish' && exec ./'My App'",
+                vec!["My App/bin/publish/My App"],
+            ),
+            (
+                "dotnet web/bin/publish/web.dll",
+                vec!["web/bin/publish/web.dll"],
+            ),
+            (
+                "cd /workspace/web/bin/publish; dotnet web.dll",
+                vec!["web/bin/publish/web.dll"],
+            ),
+            (
+                "cd bin; ../tools/migrate && bin/publish/foo",
+                vec!["tools/migrate", "bin/bin/publish/foo"],
+            ),
+            ("foo --bar", vec![]),
+            ("dotnet foo", vec![]),
+            ("$HOME/bin/foo", vec![]),
+            ("/usr/bin/env foo", vec![]),
+            ("cd $APP_DIR; ./foo", vec![]),
+            ("cd ..; ./foo", vec![]),
+        ] {
+            assert_eq!(
+                command_entrypoint_paths(command),
+                expected.into_iter().map(PathBuf::from).collect::<Vec<_>>(),
+                "{command}"
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
 #[derive(Debug, Deserialize)]
@@ -23,14 +24,59 @@ struct BuildpacksSection {
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct DotnetConfig {
+    pub(crate) ef_migrations: Option<EfMigrationsConfig>,
+    pub(crate) globalization: Option<GlobalizationConfig>,
+    pub(crate) launch_process_mode: Option<String>,
+    pub(crate) launch_profile: Option<String>,
+    pub(crate) launch_smoke_check: Option<bool>,
     pub(crate) msbuild: Option<MsbuildConfig>,
+    pub(crate) procfile_mode: Option<String>,
+    pub(crate) publish_mode: Option<String>,
+    pub(crate) publish_strategy: Option<String>,
+    pub(crate) publish_concurrency: Option<NonZeroUsize>,
+    pub(crate) publish_ready_to_run: Option<bool>,
+    pub(crate) publish_fail_on_trim_warnings: Option<bool>,
+    pub(crate) run_image_libraries: Option<Vec<String>>,
+    pub(crate) runtime: Option<RuntimeOptionsConfig>,
+    pub(crate) sdk: Option<SdkConfig>,
     pub(crate) solution_file: Option<PathBuf>,
+    pub(crate) workload_restore: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct EfMigrationsConfig {
+    pub(crate) enabled: Option<bool>,
+    pub(crate) project: Option<String>,
+    pub(crate) context: Option<String>,
+    pub(crate) connection_env_var: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct GlobalizationConfig {
+    pub(crate) invariant: Option<bool>,
+    pub(crate) app_local_icu: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct MsbuildConfig {
     pub(crate) configuration: Option<String>,
     pub(crate) verbosity: Option<String>,
+    pub(crate) binary_log: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RuntimeOptionsConfig {
+    pub(crate) tiered_pgo: Option<bool>,
+    pub(crate) tiered_compilation: Option<bool>,
+    pub(crate) ready_to_run: Option<bool>,
+    pub(crate) write_xor_execute: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct SdkConfig {
+    pub(crate) mirror_url: Option<String>,
+    pub(crate) artifact_dir: Option<PathBuf>,
+    pub(crate) write_xor_execute: Option<bool>,
 }
 
 pub(crate) fn parse(contents: &str) -> Result<Option<DotnetConfig>, toml::de::Error> {
@@ -53,8 +99,32 @@ mod tests {
         let project_toml_content = r#"
 [com.heroku.buildpacks.dotnet]
 solution_file = "foo.sln"
+ef_migrations.enabled = true
+ef_migrations.project = "Data"
+ef_migrations.context = "BloggingContext"
+ef_migrations.connection_env_var = "DATABASE_CONNECTION_STRING"
+globalization.invariant = true
+globalization.app_local_icu = "72.1.0.3"
+launch_process_mode = "shell"
+launch_profile = "https"
+launch_smoke_check = true
+procfile_mode = "merge"
+publish_mode = "self-contained"
+publish_strategy = "projects"
+publish_concurrency = 4
+publish_ready_to_run = true
+publish_fail_on_trim_warnings = true
+run_image_libraries = ["libgdiplus.so.0"]
+runtime.tiered_pgo = false
+runtime.ready_to_run = true
+runtime.write_xor_execute = true
 msbuild.configuration = "Debug"
 msbuild.verbosity = "Detailed"
+msbuild.binary_log = true
+sdk.mirror_url = "https://artifactory.example.com/dotnet"
+sdk.artifact_dir = "vendor/dotnet"
+sdk.write_xor_execute = false
+workload_restore = true
 "#;
 
         let result = parse(project_toml_content).unwrap();
@@ -63,6 +133,45 @@ msbuild.verbosity = "Detailed"
 
         let config = result.unwrap();
         assert_eq!(config.solution_file, Some(PathBuf::from("foo.sln")));
+        assert_eq!(config.workload_restore, Some(true));
+        assert_eq!(config.ef_migrations.as_ref().unwrap().enabled, Some(true));
+        assert_eq!(
+            config.ef_migrations.as_ref().unwrap().project,
+            Some("Data".to_string())
+        );
+        assert_eq!(
+            config.ef_migrations.as_ref().unwrap().context,
+            Some("BloggingContext".to_string())
+        );
+        assert_eq!(
+            config.ef_migrations.as_ref().unwrap().connection_env_var,
+            Some("DATABASE_CONNECTION_STRING".to_string())
+        );
+        assert_eq!(config.globalization.as_ref().unwrap().invariant, Some(true));
+        assert_eq!(
+            config.globalization.as_ref().unwrap().app_local_icu,
+            Some("72.1.0.3".to_string())
+        );
+        assert_eq!(config.launch_process_mode, Some("shell".to_string()));
+        assert_eq!(config.launch_profile, Some("https".to_string()));
+        assert_eq!(config.launch_smoke_check, Some(true));
+        assert_eq!(config.procfile_mode, Some("merge".to_string()));
+        assert_eq!(config.publish_mode, Some("self-contained".to_string()));
+        assert_eq!(config.publish_strategy, Some("projects".to_string()));
+        assert_eq!(config.publish_concurrency, NonZeroUsize::new(4));
+        assert_eq!(config.publish_ready_to_run, Some(true));
+        assert_eq!(config.publish_fail_on_trim_warnings, Some(true));
+        assert_eq!(
+            config.run_image_libraries,
+            Some(vec!["libgdiplus.so.0".to_string()])
+        );
+        assert_eq!(config.runtime.as_ref().unwrap().tiered_pgo, Some(false));
+        assert_eq!(config.runtime.as_ref().unwrap().tiered_compilation, None);
+        assert_eq!(config.runtime.as_ref().unwrap().ready_to_run, Some(true));
+        assert_eq!(
+            config.runtime.as_ref().unwrap().write_xor_execute,
+            Some(true)
+        );
         assert_eq!(
             config.msbuild.as_ref().unwrap().configuration,
             Some("Debug".to_string())
@@ -71,6 +180,16 @@ msbuild.verbosity = "Detailed"
             config.msbuild.as_ref().unwrap().verbosity,
             Some("Detailed".to_string())
         );
+        assert_eq!(config.msbuild.as_ref().unwrap().binary_log, Some(true));
+        assert_eq!(
+            config.sdk.as_ref().unwrap().mirror_url,
+            Some("https://artifactory.example.com/dotnet".to_string())
+        );
+        assert_eq!(
+            config.sdk.as_ref().unwrap().artifact_dir,
+            Some(PathBuf::from("vendor/dotnet"))
+        );
+        assert_eq!(config.sdk.as_ref().unwrap().write_xor_execute, Some(false));
     }
 
     #[test]
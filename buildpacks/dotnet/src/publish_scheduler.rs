@@ -0,0 +1,601 @@
+use crate::dotnet::project::Project;
+use crate::dotnet::project_graph::ProjectGraph;
+use bullet_stream::fun_run::{self, CommandWithName, NamedOutput};
+use bullet_stream::style;
+use std::collections::{HashSet, VecDeque};
+use std::io::{self, Write};
+use std::num::NonZeroUsize;
+use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
+
+/// A `dotnet publish` command for a launchable project
+pub(crate) struct PublishJob {
+    /// The name used to prefix the command output, e.g. the project's assembly name
+    pub(crate) name: String,
+    pub(crate) command: Command,
+}
+
+#[derive(Debug)]
+pub(crate) struct PublishProjectsError {
+    /// The projects whose `dotnet publish` command failed
+    pub(crate) failed: Vec<(String, fun_run::CmdError)>,
+    /// The projects that weren't published because a project they reference failed to publish
+    pub(crate) skipped: Vec<String>,
+}
+
+/// The `ProjectReference` graph of the projects to publish
+#[derive(Debug)]
+pub(crate) struct PublishGraph {
+    /// The indices of the other published projects each project (transitively) references
+    dependencies: Vec<Vec<usize>>,
+}
+
+impl PublishGraph {
+    pub(crate) fn new(published_projects: &[&Project], project_graph: &ProjectGraph) -> Self {
+        let dependencies = published_projects
+            .iter()
+            .enumerate()
+            .map(|(index, project)| {
+                let references = project_graph
+                    .transitive_references(&project.path)
+                    .into_iter()
+                    .collect::<HashSet<_>>();
+                published_projects
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, project)| {
+                        *other != index && references.contains(&project.path)
+                    })
+                    .map(|(other, _)| other)
+                    .collect()
+            })
+            .collect();
+
+        Self { dependencies }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JobState {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+/// Decides which project to publish next. A project is published once the published projects it
+/// references have been published, so it's skipped when one of them fails. Each project is built
+/// with its own build artifacts directory, so projects that share dependencies can be published
+/// at the same time.
+struct Scheduler<'a> {
+    graph: &'a PublishGraph,
+    states: Vec<JobState>,
+}
+
+impl<'a> Scheduler<'a> {
+    fn new(graph: &'a PublishGraph) -> Self {
+        Self {
+            graph,
+            states: vec![JobState::Pending; graph.dependencies.len()],
+        }
+    }
+
+    fn running_count(&self) -> usize {
+        self.count(JobState::Running)
+    }
+
+    fn count(&self, state: JobState) -> usize {
+        self.states.iter().filter(|&&s| s == state).count()
+    }
+
+    /// Returns the next project that can be published (in order), and marks it as running.
+    /// Projects that reference a project that failed to publish are skipped.
+    fn start_next(&mut self) -> Option<usize> {
+        for index in 0..self.states.len() {
+            if self.states[index] != JobState::Pending {
+                continue;
+            }
+            if self.graph.dependencies[index].iter().any(|&dependency| {
+                matches!(
+                    self.states[dependency],
+                    JobState::Failed | JobState::Skipped
+                )
+            }) {
+                self.states[index] = JobState::Skipped;
+                continue;
+            }
+            if self.is_ready(index) {
+                self.states[index] = JobState::Running;
+                return Some(index);
+            }
+        }
+
+        // Pending projects can only be blocked by running projects, unless the projects reference
+        // each other in a cycle (which `MSBuild` would reject anyway).
+        if self.running_count() == 0 {
+            for state in &mut self.states {
+                if *state == JobState::Pending {
+                    *state = JobState::Skipped;
+                }
+            }
+        }
+        None
+    }
+
+    fn is_ready(&self, index: usize) -> bool {
+        self.graph.dependencies[index]
+            .iter()
+            .all(|&dependency| self.states[dependency] == JobState::Succeeded)
+    }
+
+    fn finish(&mut self, index: usize, succeeded: bool) {
+        self.states[index] = if succeeded {
+            JobState::Succeeded
+        } else {
+            JobState::Failed
+        };
+    }
+}
+
+/// Writes the output of concurrently running commands in the order the commands were started,
+/// prefixing each line with the name of the command's project. The output of the earliest
+/// started command that hasn't finished yet is written as it arrives, and the output of the
+/// other commands is buffered until it's their turn.
+struct OrderedOutput<W> {
+    writer: W,
+    prefixes: Vec<String>,
+    started: VecDeque<usize>,
+    buffers: Vec<Vec<u8>>,
+    at_line_start: Vec<bool>,
+    finished: Vec<bool>,
+}
+
+impl<W: Write> OrderedOutput<W> {
+    fn new(names: &[String], writer: W) -> Self {
+        let width = names.iter().map(String::len).max().unwrap_or_default();
+        Self {
+            writer,
+            prefixes: names
+                .iter()
+                .map(|name| format!("[{name:width$}] "))
+                .collect(),
+            started: VecDeque::new(),
+            buffers: vec![Vec::new(); names.len()],
+            at_line_start: vec![true; names.len()],
+            finished: vec![false; names.len()],
+        }
+    }
+
+    fn start(&mut self, index: usize) {
+        self.started.push_back(index);
+    }
+
+    fn write(&mut self, index: usize, bytes: &[u8]) -> io::Result<()> {
+        let mut prefixed = Vec::with_capacity(bytes.len());
+        for &byte in bytes {
+            if self.at_line_start[index] {
+                prefixed.extend_from_slice(self.prefixes[index].as_bytes());
+            }
+            prefixed.push(byte);
+            self.at_line_start[index] = byte == b'\n';
+        }
+
+        if self.started.front() == Some(&index) {
+            self.writer.write_all(&prefixed)
+        } else {
+            self.buffers[index].extend(prefixed);
+            Ok(())
+        }
+    }
+
+    fn finish(&mut self, index: usize) -> io::Result<()> {
+        if !self.at_line_start[index] {
+            self.write(index, b"\n")?;
+        }
+        self.finished[index] = true;
+
+        while let Some(&head) = self.started.front() {
+            self.writer
+                .write_all(&std::mem::take(&mut self.buffers[head]))?;
+            if !self.finished[head] {
+                break;
+            }
+            self.started.pop_front();
+        }
+        self.writer.flush()
+    }
+}
+
+enum Event {
+    Output(usize, Vec<u8>),
+    Finished(usize, Result<NamedOutput, fun_run::CmdError>),
+}
+
+/// Forwards a command's output to the thread that writes the ordered output
+struct OutputSender {
+    index: usize,
+    sender: mpsc::Sender<Event>,
+}
+
+impl Write for OutputSender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sender
+            .send(Event::Output(self.index, buf.to_vec()))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs the publish commands, up to `concurrency` at a time, in the order allowed by the project
+/// graph. Returns the combined standard output of the commands, or the failed and skipped
+/// projects once all the other projects have been published.
+pub(crate) fn publish_projects(
+    jobs: &mut [PublishJob],
+    graph: &PublishGraph,
+    concurrency: NonZeroUsize,
+    writer: impl Write,
+) -> Result<String, PublishProjectsError> {
+    let names = jobs.iter().map(|job| job.name.clone()).collect::<Vec<_>>();
+    let mut output = OrderedOutput::new(&names, writer);
+    let mut scheduler = Scheduler::new(graph);
+    let mut commands = jobs
+        .iter_mut()
+        .map(|job| Some(&mut job.command))
+        .collect::<Vec<_>>();
+    let mut results = names.iter().map(|_| None).collect::<Vec<_>>();
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        loop {
+            while scheduler.running_count() < concurrency.get() {
+                let Some(index) = scheduler.start_next() else {
+                    break;
+                };
+                let command = commands[index]
+                    .take()
+                    .expect("each project to only be published once");
+
+                output.start(index);
+                // Failing to write the build output shouldn't fail the build
+                output
+                    .write(
+                        index,
+                        format!("Running {}\n", style::command(command.name())).as_bytes(),
+                    )
+                    .ok();
+
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    let result = command.stream_output(
+                        OutputSender {
+                            index,
+                            sender: sender.clone(),
+                        },
+                        OutputSender {
+                            index,
+                            sender: sender.clone(),
+                        },
+                    );
+                    sender.send(Event::Finished(index, result)).ok();
+                });
+            }
+
+            if scheduler.running_count() == 0 {
+                break;
+            }
+
+            match receiver
+                .recv()
+                .expect("running publish commands to report when they finish")
+            {
+                Event::Output(index, bytes) => {
+                    output.write(index, &bytes).ok();
+                }
+                Event::Finished(index, result) => {
+                    scheduler.finish(index, result.is_ok());
+                    output.finish(index).ok();
+                    results[index] = Some(result);
+                }
+            }
+        }
+    });
+
+    let mut stdout = String::new();
+    let mut failed = Vec::new();
+    let mut skipped = Vec::new();
+    for (name, result) in names.into_iter().zip(results) {
+        match result {
+            Some(Ok(named_output)) => stdout.push_str(&named_output.stdout_lossy()),
+            Some(Err(error)) => failed.push((name, error)),
+            None => skipped.push(name),
+        }
+    }
+
+    if failed.is_empty() && skipped.is_empty() {
+        Ok(stdout)
+    } else {
+        Err(PublishProjectsError { failed, skipped })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dotnet::project::{ProjectBuilder, ProjectType};
+
+    fn create_graph(solution_projects: &[Project], published: &[usize]) -> PublishGraph {
+        let published_projects = published
+            .iter()
+            .map(|&index| &solution_projects[index])
+            .collect::<Vec<_>>();
+        PublishGraph::new(&published_projects, &ProjectGraph::new(solution_projects))
+    }
+
+    #[test]
+    fn test_publish_graph_follows_transitive_references() {
+        let solution_projects = vec![
+            ProjectBuilder::new("/app/api/api.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/shared/shared.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/worker/worker.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/api/api.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/shared/shared.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/data/data.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/data/data.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+        ];
+        let graph = create_graph(&solution_projects, &[0, 1, 3]);
+
+        // `worker` references `data` through `api` and `shared`
+        assert_eq!(graph.dependencies, vec![vec![2], vec![0, 2], vec![]]);
+    }
+
+    #[test]
+    fn test_scheduler_runs_independent_projects_concurrently() {
+        let solution_projects = vec![
+            ProjectBuilder::new("/app/a/a.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+            ProjectBuilder::new("/app/b/b.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+            ProjectBuilder::new("/app/c/c.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+        ];
+        let graph = create_graph(&solution_projects, &[0, 1, 2]);
+        let mut scheduler = Scheduler::new(&graph);
+
+        assert_eq!(scheduler.start_next(), Some(0));
+        assert_eq!(scheduler.start_next(), Some(1));
+        assert_eq!(scheduler.start_next(), Some(2));
+        assert_eq!(scheduler.start_next(), None);
+        assert_eq!(scheduler.running_count(), 3);
+    }
+
+    #[test]
+    fn test_scheduler_runs_projects_with_shared_dependencies_concurrently() {
+        let solution_projects = vec![
+            ProjectBuilder::new("/app/a/a.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/lib/lib.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/b/b.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/lib/lib.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/c/c.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+            ProjectBuilder::new("/app/lib/lib.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+        ];
+        let graph = create_graph(&solution_projects, &[0, 1, 2]);
+        let mut scheduler = Scheduler::new(&graph);
+
+        // `a` and `b` both reference `lib`, which isn't published itself and is built in each
+        // project's own build artifacts directory
+        assert_eq!(scheduler.start_next(), Some(0));
+        assert_eq!(scheduler.start_next(), Some(1));
+        assert_eq!(scheduler.start_next(), Some(2));
+        assert_eq!(scheduler.start_next(), None);
+        assert_eq!(scheduler.running_count(), 3);
+    }
+
+    #[test]
+    fn test_scheduler_waits_for_referenced_projects() {
+        let solution_projects = vec![
+            ProjectBuilder::new("/app/web/web.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/api/api.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/api/api.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+        ];
+        let graph = create_graph(&solution_projects, &[0, 1]);
+        let mut scheduler = Scheduler::new(&graph);
+
+        assert_eq!(scheduler.start_next(), Some(1));
+        assert_eq!(scheduler.start_next(), None);
+
+        scheduler.finish(1, true);
+        assert_eq!(scheduler.start_next(), Some(0));
+    }
+
+    #[test]
+    fn test_scheduler_skips_projects_referencing_failed_projects() {
+        let solution_projects = vec![
+            ProjectBuilder::new("/app/web/web.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/api/api.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/api/api.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+            ProjectBuilder::new("/app/worker/worker.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+        ];
+        let graph = create_graph(&solution_projects, &[0, 1, 2]);
+        let mut scheduler = Scheduler::new(&graph);
+
+        assert_eq!(scheduler.start_next(), Some(1));
+        assert_eq!(scheduler.start_next(), Some(2));
+        scheduler.finish(1, false);
+        scheduler.finish(2, true);
+
+        assert_eq!(scheduler.start_next(), None);
+        assert_eq!(scheduler.count(JobState::Skipped), 1);
+        assert_eq!(scheduler.states[0], JobState::Skipped);
+    }
+
+    #[test]
+    fn test_scheduler_skips_projects_with_circular_references() {
+        let solution_projects = vec![
+            ProjectBuilder::new("/app/a/a.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/b/b.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/b/b.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/a/a.csproj"])
+                .build(),
+        ];
+        let graph = create_graph(&solution_projects, &[0, 1]);
+        let mut scheduler = Scheduler::new(&graph);
+
+        assert_eq!(scheduler.start_next(), None);
+        assert_eq!(scheduler.count(JobState::Skipped), 2);
+    }
+
+    #[test]
+    fn test_ordered_output_buffers_output_of_later_commands() {
+        let names = ["web".to_string(), "worker".to_string()];
+        let mut buffer = Vec::new();
+        let mut output = OrderedOutput::new(&names, &mut buffer);
+
+        output.start(0);
+        output.start(1);
+        output.write(1, b"worker line 1\nworker ").unwrap();
+        output.write(0, b"web line 1\n").unwrap();
+        output.write(1, b"line 2\n").unwrap();
+        output.write(0, b"web line 2").unwrap();
+        output.finish(0).unwrap();
+        output.write(1, b"worker line 3\n").unwrap();
+        output.finish(1).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            indoc::indoc! {"
+                [web   ] web line 1
+                [web   ] web line 2
+                [worker] worker line 1
+                [worker] worker line 2
+                [worker] worker line 3
+            "}
+        );
+    }
+
+    #[test]
+    fn test_publish_projects_aggregates_errors() {
+        let solution_projects = vec![
+            ProjectBuilder::new("/app/web/web.csproj")
+                .project_type(ProjectType::WebApplication)
+                .project_references(&["/app/api/api.csproj"])
+                .build(),
+            ProjectBuilder::new("/app/api/api.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+            ProjectBuilder::new("/app/worker/worker.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+        ];
+        let graph = create_graph(&solution_projects, &[0, 1, 2]);
+        let command = |script: &str| {
+            let mut command = Command::new("bash");
+            command.args(["-c", script]);
+            command
+        };
+        let mut jobs = vec![
+            PublishJob {
+                name: "web".to_string(),
+                command: command("echo web"),
+            },
+            PublishJob {
+                name: "api".to_string(),
+                command: command("echo api; exit 1"),
+            },
+            PublishJob {
+                name: "worker".to_string(),
+                command: command("echo worker"),
+            },
+        ];
+
+        let mut buffer = Vec::new();
+        let error = publish_projects(
+            &mut jobs,
+            &graph,
+            NonZeroUsize::new(2).unwrap(),
+            &mut buffer,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error
+                .failed
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["api"]
+        );
+        assert_eq!(error.skipped, vec!["web".to_string()]);
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("[api   ] api\n"));
+        assert!(output.contains("[worker] worker\n"));
+        assert!(!output.contains("[web   ]"));
+    }
+
+    #[test]
+    fn test_publish_projects_combines_stdout() {
+        let solution_projects = vec![
+            ProjectBuilder::new("/app/a/a.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+            ProjectBuilder::new("/app/b/b.csproj")
+                .project_type(ProjectType::WebApplication)
+                .build(),
+        ];
+        let graph = create_graph(&solution_projects, &[0, 1]);
+        let mut jobs = ["a", "b"]
+            .into_iter()
+            .map(|name| {
+                let mut command = Command::new("echo");
+                command.arg(format!("output from {name}"));
+                PublishJob {
+                    name: name.to_string(),
+                    command,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let stdout =
+            publish_projects(&mut jobs, &graph, NonZeroUsize::new(2).unwrap(), io::sink()).unwrap();
+
+        assert_eq!(stdout, "output from a\noutput from b\n");
+    }
+}
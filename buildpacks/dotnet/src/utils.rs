@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io;
 use std::path::{Path, PathBuf};
 
@@ -36,19 +37,150 @@ impl<T: AsRef<Path>> PathsExt for [T] {
     }
 }
 
-pub(crate) fn copy_recursively<P: AsRef<Path>>(src: P, dst: P) -> std::io::Result<()> {
-    if src.as_ref().is_dir() {
-        fs_err::create_dir_all(dst.as_ref())?;
-        for entry in fs_err::read_dir(src.as_ref())?.filter_map(Result::ok) {
-            let src_path = entry.path();
-            let dst_path = dst.as_ref().join(entry.file_name());
+/// The methods used by [`FileCopier`] to copy files, from cheapest to most expensive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum CopyMethod {
+    /// A copy-on-write clone of the file data (`FICLONE`), supported by filesystems like Btrfs
+    /// and XFS.
+    Reflink,
+    /// A hard link to the same file, when the source and destination share a filesystem.
+    Hardlink,
+    Copy,
+}
+
+impl std::fmt::Display for CopyMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyMethod::Reflink => write!(f, "reflinked"),
+            CopyMethod::Hardlink => write!(f, "hardlinked"),
+            CopyMethod::Copy => write!(f, "copied"),
+        }
+    }
+}
+
+/// Recursively copies files and directories, preserving symlinks and permissions.
+///
+/// Files are reflinked or hardlinked when possible, falling back to a regular copy. Once a method
+/// fails (e.g. because the filesystem doesn't support it, or the source and destination are on
+/// different filesystems), the next method is used for all remaining files.
+pub(crate) struct FileCopier {
+    method: CopyMethod,
+    copied_files: BTreeMap<CopyMethod, usize>,
+}
+
+impl Default for FileCopier {
+    fn default() -> Self {
+        Self::starting_with(CopyMethod::Reflink)
+    }
+}
+
+impl FileCopier {
+    pub(crate) fn starting_with(method: CopyMethod) -> Self {
+        Self {
+            method,
+            copied_files: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn copy_recursively<P: AsRef<Path>>(&mut self, src: P, dst: P) -> io::Result<()> {
+        let (src, dst) = (src.as_ref(), dst.as_ref());
+        let metadata = fs_err::symlink_metadata(src)?;
+
+        if metadata.is_symlink() {
+            fs_err::os::unix::fs::symlink(fs_err::read_link(src)?, dst)?;
+        } else if metadata.is_dir() {
+            fs_err::create_dir_all(dst)?;
+            for entry in fs_err::read_dir(src)? {
+                let entry = entry?;
+                self.copy_recursively(&entry.path(), &dst.join(entry.file_name()))?;
+            }
+            // Permissions are set last, so that read-only directories can still be populated.
+            fs_err::set_permissions(dst, metadata.permissions())?;
+        } else {
+            self.copy_file(src, dst)?;
+        }
+        Ok(())
+    }
+
+    fn copy_file(&mut self, src: &Path, dst: &Path) -> io::Result<()> {
+        loop {
+            let result = match self.method {
+                CopyMethod::Reflink => reflink_file(src, dst),
+                CopyMethod::Hardlink => fs_err::hard_link(src, dst),
+                CopyMethod::Copy => fs_err::copy(src, dst).map(|_| ()),
+            };
+
+            match (result, self.method) {
+                (Ok(()), method) => {
+                    *self.copied_files.entry(method).or_default() += 1;
+                    return Ok(());
+                }
+                (Err(error), CopyMethod::Copy) => return Err(error),
+                (Err(_), CopyMethod::Reflink) => self.method = CopyMethod::Hardlink,
+                (Err(_), CopyMethod::Hardlink) => self.method = CopyMethod::Copy,
+            }
+        }
+    }
+
+    /// Returns the number of files copied with each method, e.g. `120 reflinked, 3 copied`.
+    pub(crate) fn summary(&self) -> String {
+        self.copied_files
+            .iter()
+            .map(|(method, count)| format!("{count} {method}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn reflink_file(src: &Path, dst: &Path) -> io::Result<()> {
+    let src_file = fs_err::File::open(src)?;
+    let dst_file = fs_err::File::create_new(dst)?;
+
+    #[cfg(target_os = "linux")]
+    let result = rustix::fs::ioctl_ficlone(&dst_file, &src_file)
+        .map_err(io::Error::from)
+        .and_then(|()| dst_file.set_permissions(src_file.metadata()?.permissions()));
+    #[cfg(not(target_os = "linux"))]
+    let result = Err(io::Error::from(io::ErrorKind::Unsupported));
 
-            copy_recursively(&src_path, &dst_path)?;
+    result.inspect_err(|_| {
+        let _ = fs_err::remove_file(dst);
+    })
+}
+
+/// Returns the total size of all files in a directory (recursively). Symlinks aren't followed.
+pub(crate) fn directory_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs_err::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            size += directory_size(&entry.path())?;
+        } else if file_type.is_file() {
+            size += entry.metadata()?.len();
         }
-    } else {
-        fs_err::copy(src, dst)?;
     }
-    Ok(())
+    Ok(size)
+}
+
+/// Formats a byte count using binary units (e.g. `212.4 MiB`).
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut divisor = 1024;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if bytes < divisor * 1024 {
+            break;
+        }
+        divisor *= 1024;
+        unit = next_unit;
+    }
+    let tenths = u128::from(bytes) * 10 / u128::from(divisor);
+    format!("{}.{} {unit}", tenths / 10, tenths % 10)
 }
 
 /// Convert a [`libcnb::Env`] to a sorted vector of key-value string slice tuples, for easier
@@ -145,6 +277,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_directory_size() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "12345").unwrap();
+        fs::create_dir_all(temp_dir.path().join("nested/dir")).unwrap();
+        fs::write(temp_dir.path().join("nested/dir/b.txt"), "123").unwrap();
+
+        assert_eq!(directory_size(temp_dir.path()).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(222_720_000), "212.4 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
     #[test]
     fn test_allows_letters_digits_hyphen() {
         assert_eq!(to_rfc1123_label("abc-123").unwrap(), "abc-123");
@@ -203,6 +353,10 @@ mod tests {
         assert!(to_rfc1123_label("###@@@%%%").is_err());
     }
 
+    fn copy_recursively(src: &Path, dst: &Path) -> io::Result<()> {
+        FileCopier::default().copy_recursively(src, dst)
+    }
+
     #[test]
     fn test_copy_recursively_file() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -287,6 +441,117 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_copy_recursively_preserves_symlinks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        let dst_dir = temp_dir.path().join("dst");
+
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("libfoo.so.1"), "library").unwrap();
+        std::os::unix::fs::symlink("libfoo.so.1", src_dir.join("libfoo.so")).unwrap();
+
+        copy_recursively(&src_dir, &dst_dir).unwrap();
+
+        assert!(
+            fs::symlink_metadata(dst_dir.join("libfoo.so"))
+                .unwrap()
+                .is_symlink()
+        );
+        assert_eq!(
+            fs::read_link(dst_dir.join("libfoo.so")).unwrap(),
+            PathBuf::from("libfoo.so.1")
+        );
+        assert_eq!(
+            fs::read_to_string(dst_dir.join("libfoo.so")).unwrap(),
+            "library"
+        );
+    }
+
+    #[test]
+    fn test_copy_recursively_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        for method in [CopyMethod::Reflink, CopyMethod::Hardlink, CopyMethod::Copy] {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let src_dir = temp_dir.path().join("src");
+            let dst_dir = temp_dir.path().join("dst");
+
+            fs::create_dir_all(src_dir.join("bin")).unwrap();
+            fs::write(src_dir.join("bin/dotnet"), "#!/bin/sh").unwrap();
+            fs::set_permissions(
+                src_dir.join("bin/dotnet"),
+                fs::Permissions::from_mode(0o750),
+            )
+            .unwrap();
+            fs::set_permissions(src_dir.join("bin"), fs::Permissions::from_mode(0o710)).unwrap();
+
+            FileCopier::starting_with(method)
+                .copy_recursively(&src_dir, &dst_dir)
+                .unwrap();
+
+            let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&dst_dir.join("bin/dotnet")), 0o750, "{method}");
+            assert_eq!(mode(&dst_dir.join("bin")), 0o710, "{method}");
+        }
+    }
+
+    #[test]
+    fn test_copy_recursively_falls_back_to_copy() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        let dst_dir = temp_dir.path().join("dst");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("file1.txt"), "file1 content").unwrap();
+        fs::write(src_dir.join("file2.txt"), "file2 content").unwrap();
+
+        let mut copier = FileCopier::starting_with(CopyMethod::Copy);
+        copier.copy_recursively(&src_dir, &dst_dir).unwrap();
+
+        assert_eq!(copier.summary(), "2 copied");
+        assert_ne!(
+            fs::metadata(src_dir.join("file1.txt")).unwrap().ino(),
+            fs::metadata(dst_dir.join("file1.txt")).unwrap().ino()
+        );
+    }
+
+    #[test]
+    fn test_copy_recursively_hardlinks_on_same_filesystem() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src_file = temp_dir.path().join("src.txt");
+        let dst_file = temp_dir.path().join("dst.txt");
+        fs::write(&src_file, "content").unwrap();
+
+        let mut copier = FileCopier::starting_with(CopyMethod::Hardlink);
+        copier.copy_recursively(&src_file, &dst_file).unwrap();
+
+        assert_eq!(copier.summary(), "1 hardlinked");
+        assert_eq!(
+            fs::metadata(&src_file).unwrap().ino(),
+            fs::metadata(&dst_file).unwrap().ino()
+        );
+    }
+
+    #[test]
+    fn test_copy_recursively_reflink_fallback_leaves_no_partial_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src_file = temp_dir.path().join("src.txt");
+        let dst_file = temp_dir.path().join("dst.txt");
+        fs::write(&src_file, "content").unwrap();
+
+        let mut copier = FileCopier::default();
+        copier.copy_recursively(&src_file, &dst_file).unwrap();
+
+        // Depending on the filesystem, the file is either reflinked or falls back to a hardlink,
+        // but it's only ever copied once.
+        assert!(["1 reflinked", "1 hardlinked"].contains(&copier.summary().as_str()));
+        assert_eq!(fs::read_to_string(&dst_file).unwrap(), "content");
+    }
+
     fn with_readonly_dir<F: FnOnce()>(dir: &Path, f: F) {
         with_modified_permissions(dir, 0o444, f);
     }
@@ -0,0 +1,45 @@
+use crate::dotnet::project::Project;
+use crate::dotnet::solution::Solution;
+
+/// The workload that provides the WebAssembly build tools (used for Blazor WebAssembly AOT
+/// compilation and native builds)
+pub(crate) const WASM_TOOLS_WORKLOAD: &str = "wasm-tools";
+
+/// Returns the projects detected to need a workload that isn't included in the .NET SDK.
+pub(crate) fn projects_requiring_workloads(solution: &Solution) -> impl Iterator<Item = &Project> {
+    solution
+        .all_projects()
+        .filter(|project| project.wasm_native_build)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dotnet::project::ProjectBuilder;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_projects_requiring_workloads() {
+        let solution = Solution {
+            path: PathBuf::from("/tmp/app/app.sln"),
+            projects: vec![
+                ProjectBuilder::new("/tmp/app/Server/Server.csproj").build(),
+                ProjectBuilder::new("/tmp/app/Client/Client.csproj")
+                    .wasm_native_build()
+                    .build(),
+            ],
+            referenced_projects: vec![
+                ProjectBuilder::new("/tmp/app/Shared/Shared.csproj")
+                    .wasm_native_build()
+                    .build(),
+            ],
+        };
+
+        assert_eq!(
+            projects_requiring_workloads(&solution)
+                .map(|project| project.assembly_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Client", "Shared"]
+        );
+    }
+}
@@ -30,6 +30,68 @@ fn test_dotnet_publish_multi_tfm_solution() {
     );
 }
 
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_solution_with_projects_publish_strategy() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/solution_with_web_and_console_projects")
+            .env("PUBLISH_STRATEGY", "projects"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+
+            let rid = get_rid();
+            assert_contains!(
+                context.pack_stdout,
+                "- Publishing 2 launchable project(s) separately"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                &format!("- Running `dotnet publish /workspace/web/web.csproj --runtime {rid}")
+            );
+            assert_contains!(
+                context.pack_stdout,
+                &format!(
+                    "- Running `dotnet publish /workspace/worker/worker.csproj --runtime {rid}"
+                )
+            );
+            assert_contains!(context.pack_stdout, "web -> /workspace/web/bin/publish/");
+            assert_contains!(
+                context.pack_stdout,
+                "worker -> /workspace/worker/bin/publish/"
+            );
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_solution_projects_in_parallel() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/solution_with_web_and_console_projects")
+            .env("PUBLISH_STRATEGY", "projects")
+            .env("PUBLISH_CONCURRENCY", "2"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                context.pack_stdout,
+                "- Publishing projects in parallel (up to 2 at a time)"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "[web   ] Running `dotnet publish /workspace/web/web.csproj"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "[worker]   worker -> /workspace/worker/bin/publish/"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "[web   ]   web -> /workspace/web/bin/publish/"
+            );
+        },
+    );
+}
+
 #[test]
 #[ignore = "integration test"]
 fn test_dotnet_publish_with_compilation_error() {
@@ -42,16 +104,15 @@ fn test_dotnet_publish_with_compilation_error() {
                 &indoc! {r"
                   ! Unable to publish
                   !
-                  ! The `dotnet publish` command failed (exit status: 1).
-                  !
-                  ! The most common cause is a compilation error. Review the command output above
-                  ! to find and fix the issue.
-                  !
-                  ! The failure may also be temporary due to a network or service outage. Retrying
-                  ! your build often resolves this.
-                  !
-                  ! If the log suggests a NuGet issue, check the service status before retrying:
-                  ! https://status.nuget.org"}
+                  ! The `dotnet publish` command failed (exit status: 1) with the following"}
+            );
+            assert_contains!(
+                &context.pack_stdout,
+                "! - /workspace/Program.cs(1,9): CS0117: 'Console' does not contain a definition for 'WriteFoo' (project: /workspace/consoleapp.csproj)"
+            );
+            assert_contains!(
+                &context.pack_stdout,
+                "!   https://learn.microsoft.com/dotnet/csharp/misc/cs0117"
             );
         },
     );
@@ -184,7 +245,15 @@ fn test_dotnet_publish_file_based_app_basic_console_with_assembly_name() {
             assert_contains!(context.pack_stdout, "foo.cs -> /workspace/bin/publish/");
             assert_contains!(
                 context.pack_stdout,
-                "- Found `bar`: bash -c cd bin/publish; ./bar"
+                "- Native AOT compilation enabled for: `bar`"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- Skipping runtime installation (all launchable projects are self-contained or Native AOT apps)"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- Found `bar`: ./bar (in bin/publish)"
             );
             context.start_container(ContainerConfig::new().entrypoint("bar"), |container| {
                 let log_output = container.logs_wait();
@@ -216,9 +285,39 @@ fn test_dotnet_publish_file_based_app_basic_web() {
                 "- Running `dotnet publish /workspace/foo.cs"
             );
             assert_contains!(context.pack_stdout, "foo.cs -> /workspace/bin/publish/");
+            assert_contains!(context.pack_stdout, "- Found `web`: ./foo (in bin/publish)");
+            assert_contains!(
+                context.pack_stdout,
+                "- Configuring Kestrel to listen on `$PORT` at launch for: `web`"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- Setting `ASPNETCORE_FORWARDEDHEADERS_ENABLED=true` at launch for: `web`"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- Setting GC defaults from the dyno's memory limit and CPU quota at launch"
+            );
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_launch_smoke_check() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/solution_with_web_and_console_projects")
+            .env("LAUNCH_SMOKE_CHECK", "true"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(context.pack_stdout, "- Launch smoke check");
+            assert_contains!(context.pack_stdout, "- Starting `web` with a dummy `PORT`");
+            assert_contains!(context.pack_stdout, "- Accepted connections on port");
             assert_contains!(
                 context.pack_stdout,
-                "- Found `web`: bash -c cd bin/publish; ./foo --urls http://*:$PORT"
+                indoc! { r"
+                  - Running `worker` with `--help`
+                  - Exited successfully"}
             );
         },
     );
@@ -236,7 +335,7 @@ fn test_dotnet_publish_process_registration_with_procfile() {
                 indoc! { r"
                     - Process types
                       - Detecting process types from published artifacts
-                      - Found `web`: bash -c cd bin/publish; ./foo --urls http://*:$PORT
+                      - Found `web`: ./foo (in bin/publish)
                       - Procfile detected
                       - Skipping process type registration (add process types to your Procfile as needed)"}
             );
@@ -244,6 +343,49 @@ fn test_dotnet_publish_process_registration_with_procfile() {
     );
 }
 
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_process_registration_with_procfile_in_merge_mode() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/basic_web_9.0_with_procfile")
+            .env("PROCFILE_MODE", "merge"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                &context.pack_stdout,
+                indoc! { r"
+                    - Process types
+                      - Detecting process types from published artifacts
+                      - Found `web`: ./foo (in bin/publish)
+                      - Procfile detected
+                      - Skipping `web` (defined in the Procfile)
+                    - Startup settings"}
+            );
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_process_registration_with_procfile_in_override_mode() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/basic_web_9.0_with_procfile")
+            .env("PROCFILE_MODE", "override"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                &context.pack_stdout,
+                indoc! { r"
+                    - Process types
+                      - Detecting process types from published artifacts
+                      - Found `web`: ./foo (in bin/publish)
+                      - Procfile detected
+                      - Registering all detected process types as launch processes"}
+            );
+        },
+    );
+}
+
 #[test]
 #[ignore = "integration test"]
 fn test_dotnet_publish_process_registration_without_procfile() {
@@ -256,10 +398,25 @@ fn test_dotnet_publish_process_registration_without_procfile() {
                 indoc! { r"
                 - Process types
                   - Detecting process types from published artifacts
-                  - Found `web`: bash -c cd bin/publish; ./foo --urls http://*:$PORT
+                  - Found `web`: ./foo (in bin/publish)
                   - No Procfile detected
                   - Registering detected process types as launch processes
-                - Done"}
+                - Startup settings"}
+            );
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_process_registration_with_shell_launch_process_mode() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/basic_web_9.0").env("LAUNCH_PROCESS_MODE", "shell"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                &context.pack_stdout,
+                "- Found `web`: bash -c cd bin/publish; ./foo"
             );
         },
     );
@@ -332,7 +489,7 @@ fn test_dotnet_publish_with_space_in_project_filename() {
 
             assert_contains!(
                 &context.pack_stdout,
-                r"Found `console-app`: bash -c cd 'console app/bin/publish'; ./'console app'"
+                r"Found `console-app`: ./console app (in console app/bin/publish)"
             );
 
             context.start_container(
@@ -364,17 +521,190 @@ fn test_dotnet_publish_slnx_with_web_and_console_projects_and_directory_build_pr
                 &formatdoc! {r"
                   - Process types
                     - Detecting process types from published artifacts
-                    - Found `web`: bash -c cd web/bin/publish; ./web --urls http://*:$PORT
-                    - Found `worker`: bash -c cd worker/bin/publish; ./worker
+                    - Found `web`: ./web (in web/bin/publish)
+                    - Found `worker`: ./worker (in worker/bin/publish)
                     - No Procfile detected
                     - Registering detected process types as launch processes
-                  - Done"}
+                  - Startup settings"}
             );
             assert_contains!(context.pack_stdout, "web -> /workspace/web/bin/publish/");
         },
     );
 }
 
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_console_app_installs_only_required_shared_frameworks() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/console_with_nuget_package"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                context.pack_stdout,
+                "- Installed shared frameworks: `Microsoft.NETCore.App 8.0."
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- Skipped unused shared frameworks: `Microsoft.AspNetCore.App 8.0."
+            );
+
+            let command_output =
+                context.run_shell_command("ls /layers/heroku_dotnet/runtime/shared");
+            assert_empty!(command_output.stderr);
+            assert_eq!(command_output.stdout, "Microsoft.NETCore.App\n");
+
+            let config = context.config.clone();
+            context.rebuild(config, |rebuild_context| {
+                assert_contains!(rebuild_context.pack_stdout, "Reusing cached runtime");
+            });
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_web_app_installs_aspnetcore_shared_framework() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/basic_web_9.0"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                context.pack_stdout,
+                "- Installed shared frameworks: `Microsoft.AspNetCore.App 9.0."
+            );
+
+            let command_output =
+                context.run_shell_command("ls /layers/heroku_dotnet/runtime/shared");
+            assert_empty!(command_output.stderr);
+            assert_eq!(
+                command_output.stdout,
+                "Microsoft.AspNetCore.App\nMicrosoft.NETCore.App\n"
+            );
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_self_contained_app_skips_runtime_installation() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/console_with_nuget_package")
+            .env("PUBLISH_MODE", "self-contained"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                context.pack_stdout,
+                "- Using configured publish mode: `self-contained`"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- Skipping runtime installation (all launchable projects are self-contained or Native AOT apps)"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- Found `consoleapp`: ./consoleapp (in bin/publish)"
+            );
+
+            let command_output = context.run_shell_command("ls /layers/heroku_dotnet");
+            assert_empty!(command_output.stderr);
+            assert!(!command_output.stdout.contains("runtime"));
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_with_startup_settings() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/basic_web_9.0")
+            .env("PUBLISH_READY_TO_RUN", "true")
+            .env("RUNTIME_TIERED_PGO", "false"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(context.pack_stdout, "\"-p:PublishReadyToRun=true\"");
+            assert_contains!(context.pack_stdout, "- ReadyToRun compilation: `enabled`");
+            assert_contains!(
+                context.pack_stdout,
+                "- Setting `DOTNET_TieredPGO=0` at launch"
+            );
+
+            let command_output = context.run_shell_command("echo $DOTNET_TieredPGO");
+            assert_empty!(command_output.stderr);
+            assert_eq!(command_output.stdout, "0\n");
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_trimmed_app_with_trim_analysis_warnings() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/console_with_trim_warnings"),
+        |context| {
+            assert_contains!(context.pack_stdout, "- Trim analysis");
+            assert_contains!(
+                context.pack_stdout,
+                "trim or Native AOT analysis warning(s): `IL2026`"
+            );
+            assert_contains!(context.pack_stdout, "- /workspace/Program.cs(3,1): IL2026:");
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_trimmed_app_fails_on_trim_analysis_warnings() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/console_with_trim_warnings")
+            .env("PUBLISH_FAIL_ON_TRIM_WARNINGS", "true")
+            .expected_pack_result(PackResult::Failure),
+        |context| {
+            assert_contains!(context.pack_stdout, "! Trim analysis warnings found");
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_with_msbuild_binary_log() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/basic_web_9.0")
+            .env("MSBUILD_BINARY_LOG", "true"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                context.pack_stdout,
+                "-bl:/layers/heroku_dotnet/msbuild-logs/publish.binlog"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- MSBuild binary log written to `/layers/heroku_dotnet/msbuild-logs/publish.binlog`"
+            );
+
+            // Binary logs are kept in the build cache, and aren't added to the app image.
+            let command_output =
+                context.run_shell_command("test ! -e /layers/heroku_dotnet/msbuild-logs");
+            assert_empty!(command_output.stderr);
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_publish_with_msbuild_binary_log_and_compilation_error() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/console_with_compilation_error")
+            .env("MSBUILD_BINARY_LOG", "true")
+            .expected_pack_result(PackResult::Failure),
+        |context| {
+            assert_contains!(
+                context.pack_stdout,
+                "- MSBuild binary log written to `/layers/heroku_dotnet/msbuild-logs/publish.binlog`"
+            );
+        },
+    );
+}
+
 fn get_rid() -> String {
     format!("linux-{}", get_dotnet_arch())
 }
@@ -1,6 +1,8 @@
 use crate::tests::default_build_config;
 use indoc::indoc;
-use libcnb_test::{PackResult, TestRunner, assert_contains, assert_empty};
+use libcnb_test::{
+    ContainerConfig, PackResult, TestRunner, assert_contains, assert_empty, assert_not_contains,
+};
 
 #[test]
 #[ignore = "integration test"]
@@ -61,3 +63,89 @@ fn test_dotnet_restore_dotnet_tool_with_configuration_error() {
         },
     );
 }
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_ef_migrations_bundle_release_process() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/web_with_ef_core_migrations")
+            .env("EF_MIGRATIONS_ENABLED", "true")
+            .env("EF_MIGRATIONS_CONNECTION_ENV_VAR", "DATABASE_CONNECTION_STRING"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                &context.pack_stdout,
+                indoc! { r"
+                    - Entity Framework Core migrations
+                      - Creating a migrations bundle for `webapp` (startup project: `webapp`)
+                      - Running `dotnet ef migrations bundle --project /workspace/webapp.csproj --startup-project /workspace/webapp.csproj --output /layers/heroku_dotnet/ef-migrations/efbundle --runtime linux-"}
+            );
+            assert_contains!(
+                &context.pack_stdout,
+                "- Using the connection string from `$DATABASE_CONNECTION_STRING` at release time"
+            );
+            assert_contains!(
+                &context.pack_stdout,
+                "- Found `release`: /layers/heroku_dotnet/ef-migrations/efbundle --connection $(DATABASE_CONNECTION_STRING)"
+            );
+            context.start_container(
+                ContainerConfig::new()
+                    .entrypoint("release")
+                    .env("DATABASE_CONNECTION_STRING", "Data Source=/tmp/blogging.db"),
+                |container| {
+                    let log_output = container.logs_wait();
+
+                    assert_contains!(
+                        log_output.stdout,
+                        "Applying migration '20250101000000_InitialCreate'."
+                    );
+                    assert_contains!(log_output.stdout, "Done.");
+                },
+            );
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_ef_migrations_bundle_disabled_by_default() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/web_with_ef_core_migrations"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                &context.pack_stdout,
+                indoc! { r"
+                    - Entity Framework Core migrations
+                      - Skipping the migrations bundle for `webapp` (set `EF_MIGRATIONS_ENABLED=true` to run migrations in the release phase)"}
+            );
+            assert_not_contains!(&context.pack_stdout, "- Found `release`");
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_dotnet_workload_restore_enabled_by_configuration() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/basic_web_9.0").env("WORKLOAD_RESTORE", "true"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                &context.pack_stdout,
+                indoc! { r"
+                    - Restore .NET workloads
+                      - Workload restore enabled by configuration
+                      - Running `/layers/heroku_dotnet/workloads/dotnet workload restore /workspace/foo.csproj`"}
+            );
+
+            let config = context.config.clone();
+            context.rebuild(config, |rebuild_context| {
+                assert_contains!(
+                    &rebuild_context.pack_stdout,
+                    "- Reusing cached workloads (SDK version 9.0."
+                );
+            });
+        },
+    );
+}
@@ -0,0 +1,4 @@
+using SkiaSharp;
+
+using var bitmap = new SKBitmap(1, 1);
+Console.WriteLine($"Created a {bitmap.Width}x{bitmap.Height} bitmap");
@@ -0,0 +1,6 @@
+using System.Diagnostics.CodeAnalysis;
+
+PrintGreeting();
+
+[RequiresUnreferencedCode("Uses reflection to find the greeting")]
+static void PrintGreeting() => Console.WriteLine("Hello, World!");
@@ -0,0 +1,12 @@
+using Microsoft.EntityFrameworkCore;
+
+public class BloggingContext(DbContextOptions<BloggingContext> options) : DbContext(options)
+{
+    public DbSet<Blog> Blogs => Set<Blog>();
+}
+
+public class Blog
+{
+    public int BlogId { get; set; }
+    public required string Url { get; set; }
+}
@@ -0,0 +1,10 @@
+using Microsoft.EntityFrameworkCore;
+
+var builder = WebApplication.CreateBuilder(args);
+builder.Services.AddDbContext<BloggingContext>(options =>
+    options.UseSqlite(builder.Configuration.GetConnectionString("Blogging") ?? "Data Source=blogging.db"));
+var app = builder.Build();
+
+app.MapGet("/", () => "Hello World!");
+
+app.Run();
@@ -35,3 +35,45 @@ fn verify_installed_dotnet_runtime_dependencies() {
         },
     );
 }
+
+#[test]
+#[ignore = "integration test"]
+fn test_native_library_dependencies_of_published_app() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/console_with_native_dependencies"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                context.pack_stdout,
+                "- Found 1 native library in `consoleapp`: `libSkiaSharp.so`"
+            );
+            assert_contains!(
+                context.pack_stdout,
+                "- `libgdiplus.so` (needed by the `System.Drawing.Common` package)"
+            );
+        },
+    );
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_globalization_invariant_mode_configuration() {
+    TestRunner::default().build(
+        default_build_config("tests/fixtures/basic_web_8.0").env("GLOBALIZATION_INVARIANT", "true"),
+        |context| {
+            assert_empty!(context.pack_stderr);
+            assert_contains!(
+                context.pack_stdout,
+                indoc! {"
+                    - Globalization
+                      - `foo` uses `globalization-invariant mode`
+                      - Setting `DOTNET_SYSTEM_GLOBALIZATION_INVARIANT=true` at launch
+                "}
+            );
+            let command_output =
+                context.run_shell_command("echo -n $DOTNET_SYSTEM_GLOBALIZATION_INVARIANT");
+            assert_empty!(command_output.stderr);
+            assert_eq!(command_output.stdout, "true");
+        },
+    );
+}
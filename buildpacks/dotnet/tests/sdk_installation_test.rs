@@ -94,14 +94,21 @@ fn test_sdk_basic_install_build_environment() {
 
     TestRunner::default().build(&config, |context| {
         assert_empty!(context.pack_stderr);
+        // W^X is only disabled during the build when the builder image runs under emulation
+        // (e.g. an amd64 image on an ARM64 Mac).
+        let write_xor_execute_env =
+            if context.pack_stdout.contains("during build (emulation detected)") {
+                "DOTNET_EnableWriteXorExecute=0\n"
+            } else {
+                ""
+            };
         assert_contains!(
             context.pack_stdout,
-            &indoc! {"
+            &formatdoc! {"
                 ## Testing buildpack ##
                 DOTNET_CLI_HOME=/layers/heroku_dotnet/dotnet-cli
                 DOTNET_CLI_TELEMETRY_OPTOUT=true
-                DOTNET_EnableWriteXorExecute=0
-                DOTNET_NOLOGO=true
+                {write_xor_execute_env}DOTNET_NOLOGO=true
                 DOTNET_ROOT=/layers/heroku_dotnet/sdk
                 DOTNET_RUNNING_IN_CONTAINER=true
                 NUGET_PACKAGES=/layers/heroku_dotnet/nuget-cache